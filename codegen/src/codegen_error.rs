@@ -0,0 +1,48 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io;
+use std::path::PathBuf;
+
+use inexor_rgf_core_model::ComponentTypeId;
+use inexor_rgf_core_model::PropertyConflict;
+
+#[derive(Debug)]
+pub enum CodegenError {
+    /// The type definitions cannot be read.
+    Io { path: PathBuf, error: io::Error },
+
+    /// The type definitions are not valid.
+    Json { path: PathBuf, error: serde_json::Error },
+
+    /// Two types or properties result in the same generated identifier.
+    DuplicateIdentifier { identifier: String, first: String, second: String },
+
+    /// Components of the type cannot be found.
+    MissingComponents { ty: String, components: Vec<ComponentTypeId> },
+
+    /// Multiple components of the type declare the same property differently.
+    PropertyConflicts { ty: String, conflicts: Vec<PropertyConflict> },
+}
+
+impl Display for CodegenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenError::Io { path, error } => write!(f, "Failed to read {}: {}", path.display(), error),
+            CodegenError::Json { path, error } => write!(f, "Failed to parse {}: {}", path.display(), error),
+            CodegenError::DuplicateIdentifier { identifier, first, second } => {
+                write!(f, "{} and {} result in the same identifier {}", first, second, identifier)
+            }
+            CodegenError::MissingComponents { ty, components } => {
+                let components: Vec<String> = components.iter().map(|component| component.to_string()).collect();
+                write!(f, "The components {} of type {} cannot be found", components.join(", "), ty)
+            }
+            CodegenError::PropertyConflicts { ty, conflicts } => {
+                let conflicts: Vec<String> = conflicts.iter().map(|conflict| conflict.to_string()).collect();
+                write!(f, "The properties of type {} are conflicting: {}", ty, conflicts.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodegenError {}
@@ -0,0 +1,343 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use inexor_rgf_core_model::ComponentOrEntityTypeId;
+use inexor_rgf_core_model::ComponentTypeId;
+use inexor_rgf_core_model::DataType;
+use inexor_rgf_core_model::EffectiveProperty;
+use inexor_rgf_core_model::EntityTypeId;
+use inexor_rgf_core_model::NamespacedTypeGetter;
+use inexor_rgf_core_model::PropertyOrigin;
+use inexor_rgf_core_model::PropertyType;
+
+use crate::resolved_properties;
+use crate::to_snake_case_identifier;
+use crate::to_upper_camel_case;
+use crate::CodegenError;
+use crate::ModelTypes;
+
+/// Returns true, if the given name is a valid GraphQL name which isn't reserved for introspection.
+pub fn is_graphql_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {}
+        _ => return false,
+    }
+    !name.starts_with("__") && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Returns true, if the values of the given enum data type can be used as GraphQL enum values.
+fn is_graphql_enum(values: &[String]) -> bool {
+    !values.is_empty()
+        && values
+            .iter()
+            .all(|value| is_graphql_name(value) && !matches!(value.as_str(), "true" | "false" | "null"))
+}
+
+/// Generates the GraphQL SDL of the given components, entity types and relation types.
+///
+/// Components with properties become interfaces named `{Namespace}{TypeName}Component`. Entity
+/// types and relation types become object types named `{Namespace}{TypeName}` with the resolved
+/// properties of the type as fields. Object types implement the interfaces of their components.
+///
+/// Entity types have an `id` and connection fields to the relation types which have the entity
+/// type or one of its components as outbound type (`outbound_{namespace}_{type_name}`) or as
+/// inbound type (`inbound_{namespace}_{type_name}`). Relation types have the ids of the outbound
+/// and inbound entity instances and fields to the outbound and inbound types.
+///
+/// Fails if a component is missing, if components declare the same property differently or if
+/// multiple types or properties result in the same GraphQL name.
+pub fn graphql_schema(types: &ModelTypes) -> Result<String, CodegenError> {
+    let mut schema = GraphQLSchema::default();
+    let mut interfaces = HashMap::new();
+    let mut objects = HashMap::new();
+    for component in types.components.iter().filter(|component| !component.properties.is_empty()) {
+        let name = format!("{}Component", type_name(&component.namespace(), &component.type_name()));
+        schema.declare(&name, &format!("component {}__{}", component.namespace(), component.type_name()))?;
+        interfaces.insert(component.ty.clone(), name);
+    }
+    for entity_type in types.entity_types.iter() {
+        let name = type_name(&entity_type.namespace(), &entity_type.type_name());
+        schema.declare(&name, &format!("entity type {}__{}", entity_type.namespace(), entity_type.type_name()))?;
+        objects.insert(entity_type.ty.clone(), name);
+    }
+    for relation_type in types.relation_types.iter() {
+        let name = type_name(&relation_type.namespace(), &relation_type.type_name());
+        schema.declare(&name, &format!("relation type {}__{}", relation_type.namespace(), relation_type.type_name()))?;
+    }
+
+    let mut definitions = Vec::new();
+    for component in types.components.iter() {
+        let Some(name) = interfaces.get(&component.ty) else {
+            continue;
+        };
+        let properties: Vec<EffectiveProperty> = component
+            .properties
+            .iter()
+            .map(|property_type| EffectiveProperty {
+                property_type: property_type.clone(),
+                origin: PropertyOrigin::Component(component.ty.clone()),
+            })
+            .collect();
+        let mut fields = Fields::new(format!("component {}__{}", component.namespace(), component.type_name()), name.clone());
+        schema.properties(&mut fields, &properties)?;
+        definitions.push(format!("{}interface {} {{\n{}}}\n", description(&component.description), name, fields.generate()));
+    }
+    for entity_type in types.entity_types.iter() {
+        let name = &objects[&entity_type.ty];
+        let properties = resolved_properties(
+            &format!("{}__{}", entity_type.namespace(), entity_type.type_name()),
+            entity_type.effective_properties(&types.components),
+        )?
+        .properties;
+        let mut fields = Fields::new(format!("entity type {}__{}", entity_type.namespace(), entity_type.type_name()), name.clone());
+        fields.push("field id", "id", "The id of the entity instance", &schema.scalar("UUID", true))?;
+        schema.properties(&mut fields, &properties)?;
+        for relation_type in types.relation_types.iter() {
+            let relations = format!("[{}!]!", type_name(&relation_type.namespace(), &relation_type.type_name()));
+            if is_connected(&relation_type.outbound_type, &entity_type.ty, &entity_type.components) {
+                let field = to_snake_case_identifier(&format!("outbound_{}_{}", relation_type.namespace(), relation_type.type_name()));
+                fields.push(
+                    &format!("connection {}", field),
+                    &field,
+                    &format!("The outgoing relations of type {}__{}", relation_type.namespace(), relation_type.type_name()),
+                    &relations,
+                )?;
+            }
+            if is_connected(&relation_type.inbound_type, &entity_type.ty, &entity_type.components) {
+                let field = to_snake_case_identifier(&format!("inbound_{}_{}", relation_type.namespace(), relation_type.type_name()));
+                fields.push(
+                    &format!("connection {}", field),
+                    &field,
+                    &format!("The incoming relations of type {}__{}", relation_type.namespace(), relation_type.type_name()),
+                    &relations,
+                )?;
+            }
+        }
+        definitions.push(format!(
+            "{}type {}{} {{\n{}}}\n",
+            description(&entity_type.description),
+            name,
+            implements(&entity_type.components, &interfaces),
+            fields.generate()
+        ));
+    }
+    for relation_type in types.relation_types.iter() {
+        let name = type_name(&relation_type.namespace(), &relation_type.type_name());
+        let properties = resolved_properties(
+            &format!("{}__{}", relation_type.namespace(), relation_type.type_name()),
+            relation_type.effective_properties(&types.components),
+        )?
+        .properties;
+        let mut fields = Fields::new(format!("relation type {}__{}", relation_type.namespace(), relation_type.type_name()), name.clone());
+        let uuid = schema.scalar("UUID", true);
+        fields.push("field outbound_id", "outbound_id", "The id of the outbound entity instance", &uuid)?;
+        fields.push("field inbound_id", "inbound_id", "The id of the inbound entity instance", &uuid)?;
+        for (field, ty) in [("outbound", &relation_type.outbound_type), ("inbound", &relation_type.inbound_type)] {
+            let target = match ty {
+                ComponentOrEntityTypeId::Component(ty) => interfaces.get(ty),
+                ComponentOrEntityTypeId::EntityType(ty) => objects.get(ty),
+            };
+            match target {
+                Some(target) => fields.push(&format!("field {}", field), field, &format!("The {} entity instance", field), &format!("{}!", target))?,
+                None => fields.comment(&format!(
+                    "The field {} is skipped, because the {} type {}__{} has no GraphQL type",
+                    field,
+                    field,
+                    ty.namespace(),
+                    ty.type_name()
+                )),
+            }
+        }
+        schema.properties(&mut fields, &properties)?;
+        definitions.push(format!(
+            "{}type {}{} {{\n{}}}\n",
+            description(&relation_type.description),
+            name,
+            implements(&relation_type.components, &interfaces),
+            fields.generate()
+        ));
+    }
+
+    let mut sdl = String::from("# Generated from the type definitions by inexor-rgf-codegen. Do not edit.\n");
+    for scalar in schema.scalars.iter() {
+        sdl.push_str(&format!("\nscalar {}\n", scalar));
+    }
+    for definition in schema.enums.iter().chain(definitions.iter()) {
+        sdl.push('\n');
+        sdl.push_str(definition);
+    }
+    Ok(sdl)
+}
+
+/// Returns the GraphQL name of the type with the given namespace and type name.
+fn type_name(namespace: &str, type_name: &str) -> String {
+    format!("{}{}", to_upper_camel_case(namespace), to_upper_camel_case(type_name))
+}
+
+/// Returns true, if the given outbound or inbound type of a relation type is the given entity type
+/// or one of its components.
+fn is_connected(ty: &ComponentOrEntityTypeId, entity_ty: &EntityTypeId, components: &[ComponentTypeId]) -> bool {
+    ty.eq_entity_type(entity_ty) || components.iter().any(|component| ty.eq_component(component))
+}
+
+/// Returns the implements clause for the components which have interfaces.
+fn implements(components: &[ComponentTypeId], interfaces: &HashMap<ComponentTypeId, String>) -> String {
+    let implemented: Vec<&str> = components
+        .iter()
+        .filter_map(|component| interfaces.get(component))
+        .map(String::as_str)
+        .collect();
+    match implemented.is_empty() {
+        true => String::new(),
+        false => format!(" implements {}", implemented.join(" & ")),
+    }
+}
+
+/// Returns the given description as a GraphQL description.
+fn description(description: &str) -> String {
+    match description.trim() {
+        "" => String::new(),
+        description => format!("{}\n", quote(description)),
+    }
+}
+
+/// Quotes the given text as a GraphQL string. The escape sequences of JSON strings are valid in
+/// GraphQL strings.
+fn quote(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+/// The state of the generated schema.
+#[derive(Default)]
+struct GraphQLSchema {
+    /// The declared type names and the types which declare them.
+    names: HashMap<String, String>,
+    /// The used custom scalars.
+    scalars: BTreeSet<&'static str>,
+    /// The names of the enums by values.
+    enum_names: HashMap<Vec<String>, String>,
+    /// The definitions of the enums.
+    enums: Vec<String>,
+}
+
+impl GraphQLSchema {
+    /// Declares a type name. Fails if the type name is already declared by another type.
+    fn declare(&mut self, name: &str, origin: &str) -> Result<(), CodegenError> {
+        match self.names.insert(name.to_string(), origin.to_string()) {
+            Some(first) => Err(CodegenError::DuplicateIdentifier {
+                identifier: name.to_string(),
+                first,
+                second: origin.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the type of a field of the given custom scalar.
+    fn scalar(&mut self, scalar: &'static str, non_null: bool) -> String {
+        self.scalars.insert(scalar);
+        match non_null {
+            true => format!("{}!", scalar),
+            false => scalar.to_string(),
+        }
+    }
+
+    /// Adds the fields of the given properties.
+    fn properties(&mut self, fields: &mut Fields, properties: &[EffectiveProperty]) -> Result<(), CodegenError> {
+        for property in properties.iter() {
+            let property_type = &property.property_type;
+            if !is_graphql_name(&property_type.name) {
+                fields.comment(&format!("The property {} is skipped, because it isn't a valid GraphQL name", property_type.name));
+                continue;
+            }
+            let enum_prefix = match &property.origin {
+                PropertyOrigin::Own => fields.type_name.clone(),
+                PropertyOrigin::Component(ty) => type_name(&ty.namespace(), &ty.type_name()),
+            };
+            let ty = self.field_type(property_type, &enum_prefix, &fields.origin)?;
+            fields.push(&format!("property {}", property_type.name), &property_type.name, &property_type.description, &ty)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the type of the field of the given property.
+    ///
+    /// Integers are 64-bit and map to the custom scalar `Long`, because the GraphQL `Int` is
+    /// 32-bit. Enums are named after the type which declares the property. Enums with the same
+    /// values are shared, so that object types are compatible with the interfaces they implement.
+    fn field_type(&mut self, property_type: &PropertyType, enum_prefix: &str, origin: &str) -> Result<String, CodegenError> {
+        let ty = match &property_type.data_type {
+            DataType::Null | DataType::Any => self.scalar("JSON", false),
+            DataType::Bool => String::from("Boolean!"),
+            DataType::Integer => self.scalar("Long", true),
+            DataType::Number | DataType::Float => String::from("Float!"),
+            DataType::String | DataType::Bytes => String::from("String!"),
+            DataType::Array => format!("[{}]!", self.scalar("JSON", false)),
+            DataType::Object => self.scalar("JSON", true),
+            DataType::Uuid => self.scalar("UUID", true),
+            DataType::DateTime => self.scalar("DateTime", true),
+            DataType::Enum(values) if is_graphql_enum(values) => match self.enum_names.get(values) {
+                Some(name) => format!("{}!", name),
+                None => {
+                    let name = format!("{}{}", enum_prefix, to_upper_camel_case(&property_type.name));
+                    self.declare(&name, &format!("property {} of {}", property_type.name, origin))?;
+                    self.enums
+                        .push(format!("enum {} {{\n{}}}\n", name, values.iter().map(|value| format!("  {}\n", value)).collect::<String>()));
+                    self.enum_names.insert(values.clone(), name.clone());
+                    format!("{}!", name)
+                }
+            },
+            DataType::Enum(_) => String::from("String!"),
+        };
+        Ok(ty)
+    }
+}
+
+/// The fields of an interface or an object type.
+struct Fields {
+    /// The type which declares the fields.
+    origin: String,
+    /// The GraphQL name of the type which declares the fields.
+    type_name: String,
+    /// The names of the fields and what they are generated from.
+    names: HashMap<String, String>,
+    /// The definitions of the fields.
+    lines: Vec<String>,
+}
+
+impl Fields {
+    fn new(origin: String, type_name: String) -> Fields {
+        Fields {
+            origin,
+            type_name,
+            names: HashMap::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Adds a field. Fails if the type already has a field with the same name.
+    fn push(&mut self, source: &str, name: &str, description: &str, ty: &str) -> Result<(), CodegenError> {
+        if let Some(first) = self.names.insert(name.to_string(), source.to_string()) {
+            return Err(CodegenError::DuplicateIdentifier {
+                identifier: name.to_string(),
+                first: format!("{} of {}", first, self.origin),
+                second: format!("{} of {}", source, self.origin),
+            });
+        }
+        if !description.trim().is_empty() {
+            self.lines.push(format!("  {}\n", quote(description.trim())));
+        }
+        self.lines.push(format!("  {}: {}\n", name, ty));
+        Ok(())
+    }
+
+    /// Adds a comment.
+    fn comment(&mut self, comment: &str) {
+        self.lines.push(format!("  # {}\n", comment));
+    }
+
+    fn generate(&self) -> String {
+        self.lines.concat()
+    }
+}
@@ -0,0 +1,89 @@
+/// The strict and reserved keywords of rust which cannot be used as identifiers.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn",
+    "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Returns true, if the given name is a rust keyword.
+pub fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+/// Returns true, if the given name can be used as an identifier without modifications.
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {}
+        _ => return false,
+    }
+    name != "_" && chars.all(|c| c.is_ascii_alphanumeric() || c == '_') && !is_keyword(name)
+}
+
+/// Splits the given name into words. Non-alphanumeric characters and the transition from a lower
+/// case to an upper case character separate words.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            previous_lowercase = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && previous_lowercase {
+            words.push(std::mem::take(&mut word));
+        }
+        previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Prefixes names which start with a digit with an underscore.
+fn prefix_digit(name: String) -> String {
+    match name.chars().next() {
+        Some(first) if first.is_ascii_digit() => format!("_{}", name),
+        None => String::from("_"),
+        _ => name,
+    }
+}
+
+/// Converts the given name into `UpperCamelCase`.
+pub fn to_upper_camel_case(name: &str) -> String {
+    let camel_case = words(name)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    prefix_digit(camel_case)
+}
+
+/// Converts the given name into `UPPER_SNAKE_CASE`.
+pub fn to_upper_snake_case(name: &str) -> String {
+    let words: Vec<String> = words(name).iter().map(|word| word.to_ascii_uppercase()).collect();
+    prefix_digit(words.join("_"))
+}
+
+/// Converts the given name into a `snake_case` identifier. Keywords are escaped as raw identifiers
+/// or, if they can't be raw identifiers, suffixed with an underscore.
+pub fn to_snake_case_identifier(name: &str) -> String {
+    let words: Vec<String> = words(name).iter().map(|word| word.to_ascii_lowercase()).collect();
+    let snake_case = prefix_digit(words.join("_"));
+    match snake_case.as_str() {
+        "crate" | "self" | "super" => format!("{}_", snake_case),
+        _ if is_keyword(&snake_case) => format!("r#{}", snake_case),
+        _ => snake_case,
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use inexor_rgf_core_model::Component;
+use inexor_rgf_core_model::ComponentLookup;
+use inexor_rgf_core_model::DataType;
+use inexor_rgf_core_model::EntityInstance;
+use inexor_rgf_core_model::EntityType;
+use inexor_rgf_core_model::FlowInstance;
+use inexor_rgf_core_model::FlowType;
+use inexor_rgf_core_model::Mutability;
+use inexor_rgf_core_model::NamespacedTypeGetter;
+use inexor_rgf_core_model::PropertyType;
+use inexor_rgf_core_model::RelationInstance;
+use inexor_rgf_core_model::RelationType;
+use schemars::schema_for;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::resolved_properties;
+use crate::CodegenError;
+use crate::ModelTypes;
+
+pub const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Returns the JSON schemas of the serialized forms of the model types by name.
+pub fn model_json_schemas() -> BTreeMap<String, Value> {
+    let schemas = [
+        ("component", schema_for!(Component)),
+        ("entity_type", schema_for!(EntityType)),
+        ("relation_type", schema_for!(RelationType)),
+        ("flow_type", schema_for!(FlowType)),
+        ("entity_instance", schema_for!(EntityInstance)),
+        ("relation_instance", schema_for!(RelationInstance)),
+        ("flow_instance", schema_for!(FlowInstance)),
+    ];
+    schemas
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), serde_json::to_value(schema).unwrap_or_default()))
+        .collect()
+}
+
+/// Returns the JSON schemas of the properties of the instances of the entity types and the
+/// relation types. The schemas are keyed by `namespace__type_name`. The properties of the
+/// components are resolved using the components of the given types.
+pub fn types_json_schemas(types: &ModelTypes) -> Result<BTreeMap<String, Value>, CodegenError> {
+    let mut schemas = BTreeMap::new();
+    for entity_type in types.entity_types.iter() {
+        let key = format!("{}__{}", entity_type.namespace(), entity_type.type_name());
+        schemas.insert(key, entity_type_json_schema(entity_type, &types.components)?);
+    }
+    for relation_type in types.relation_types.iter() {
+        let key = format!("{}__{}", relation_type.namespace(), relation_type.type_name());
+        schemas.insert(key, relation_type_json_schema(relation_type, &types.components)?);
+    }
+    Ok(schemas)
+}
+
+/// Returns the JSON schema of the properties of the instances of the given entity type. Fails if
+/// a component is missing or if components declare the same property differently.
+pub fn entity_type_json_schema<L: ComponentLookup + ?Sized>(entity_type: &EntityType, components: &L) -> Result<Value, CodegenError> {
+    let title = format!("{}__{}", entity_type.namespace(), entity_type.type_name());
+    let effective_properties = resolved_properties(&title, entity_type.effective_properties(components))?.property_types();
+    Ok(properties_json_schema(&title, &entity_type.description, &effective_properties))
+}
+
+/// Returns the JSON schema of the properties of the instances of the given relation type. Fails if
+/// a component is missing or if components declare the same property differently.
+pub fn relation_type_json_schema<L: ComponentLookup + ?Sized>(relation_type: &RelationType, components: &L) -> Result<Value, CodegenError> {
+    let title = format!("{}__{}", relation_type.namespace(), relation_type.type_name());
+    let effective_properties = resolved_properties(&title, relation_type.effective_properties(components))?.property_types();
+    Ok(properties_json_schema(&title, &relation_type.description, &effective_properties))
+}
+
+/// Returns the JSON schema of a properties map with the given properties.
+///
+/// Like the validation of instances, the schema requires all properties and forbids unknown
+/// properties. Immutable properties are read only.
+pub fn properties_json_schema(title: &str, description: &str, properties: &[PropertyType]) -> Value {
+    let mut schema = Map::new();
+    schema.insert("$schema".to_string(), json!(JSON_SCHEMA_DRAFT));
+    schema.insert("title".to_string(), json!(title));
+    if !description.is_empty() {
+        schema.insert("description".to_string(), json!(description));
+    }
+    schema.insert("type".to_string(), json!("object"));
+    let property_schemas: Map<String, Value> = properties
+        .iter()
+        .map(|property| (property.name.clone(), property_json_schema(property)))
+        .collect();
+    schema.insert("properties".to_string(), Value::Object(property_schemas));
+    schema.insert("required".to_string(), json!(properties.iter().map(|property| &property.name).collect::<Vec<_>>()));
+    schema.insert("additionalProperties".to_string(), json!(false));
+    Value::Object(schema)
+}
+
+/// Returns the JSON schema of the value of the given property.
+pub fn property_json_schema(property: &PropertyType) -> Value {
+    let mut schema = match data_type_json_schema(&property.data_type) {
+        Value::Object(schema) => schema,
+        _ => Map::new(),
+    };
+    if !property.description.is_empty() {
+        schema.insert("description".to_string(), json!(property.description));
+    }
+    let default_value = property.data_type.default_value();
+    if property.mutability == Mutability::Immutable {
+        schema.insert("readOnly".to_string(), json!(true));
+    }
+    schema.insert("default".to_string(), default_value);
+    Value::Object(schema)
+}
+
+/// Returns the JSON schema of the values of the given data type.
+pub fn data_type_json_schema(data_type: &DataType) -> Value {
+    match data_type {
+        DataType::Null => json!({ "type": "null" }),
+        DataType::Bool => json!({ "type": "boolean" }),
+        DataType::Number | DataType::Float => json!({ "type": "number" }),
+        DataType::Integer => json!({ "type": "integer" }),
+        DataType::String => json!({ "type": "string" }),
+        DataType::Array => json!({ "type": "array" }),
+        DataType::Object => json!({ "type": "object" }),
+        DataType::Any => json!({}),
+        DataType::Uuid => json!({ "type": "string", "format": "uuid" }),
+        DataType::DateTime => json!({ "type": "string", "format": "date-time" }),
+        DataType::Bytes => json!({ "type": "string", "contentEncoding": "base64" }),
+        DataType::Enum(values) => json!({ "type": "string", "enum": values }),
+    }
+}
@@ -0,0 +1,19 @@
+//! Code generators which read components, entity types and relation types as deserialized by
+//! `inexor-rgf-core-model` and generate rust models, JSON schemas, GraphQL schemas and
+//! TypeScript definitions from them.
+
+pub use codegen_error::*;
+pub use graphql_schema::*;
+pub use identifier::*;
+pub use json_schema::*;
+pub use model_types::*;
+pub use rust_model::*;
+pub use typescript::*;
+
+pub mod codegen_error;
+pub mod graphql_schema;
+pub mod identifier;
+pub mod json_schema;
+pub mod model_types;
+pub mod rust_model;
+pub mod typescript;
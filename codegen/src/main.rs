@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use inexor_rgf_core_model_codegen::graphql_schema;
+use inexor_rgf_core_model_codegen::model_json_schemas;
+use inexor_rgf_core_model_codegen::types_json_schemas;
+use inexor_rgf_core_model_codegen::typescript_definitions;
+use inexor_rgf_core_model_codegen::ModelTypes;
+use inexor_rgf_core_model_codegen::RustModelGenerator;
+use serde_json::Value;
+
+const USAGE: &str = "Usage: inexor-rgf-codegen <generator> [options]
+
+Generators:
+    rust                        Generates the rust models using the model macros
+    json-schema                 Generates the JSON schemas of the properties of the entity types and relation types
+    model-json-schema           Generates the JSON schemas of the model types
+    graphql                     Generates the GraphQL schema of the components, entity types and relation types
+    typescript                  Generates the TypeScript definitions of the model types and of the properties of the types
+
+Options:
+    -c, --components <path>     Loads components from a JSON file or a directory of JSON files
+    -e, --entity-types <path>   Loads entity types from a JSON file or a directory of JSON files
+    -r, --relation-types <path> Loads relation types from a JSON file or a directory of JSON files
+    -o, --output <file>         Writes the generated code into the file instead of stdout
+        --output-dir <dir>      Writes each JSON schema into <dir>/<name>.schema.json (json-schema, model-json-schema)
+        --model-crate <path>    The path of the crate which provides the model macros (rust)
+    -h, --help                  Prints this help";
+
+struct Arguments {
+    generator: String,
+    types: ModelTypes,
+    output: Option<String>,
+    output_dir: Option<String>,
+    model_crate: Option<String>,
+}
+
+/// The generated code: either a single document or multiple named JSON documents.
+enum Generated {
+    Code(String),
+    Json(BTreeMap<String, Value>),
+}
+
+fn parse_arguments() -> Result<Arguments, String> {
+    let mut args = env::args().skip(1);
+    let mut generator = None;
+    let mut types = ModelTypes::new();
+    let mut output = None;
+    let mut output_dir = None;
+    let mut model_crate = None;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("Missing value of {}", arg));
+        match arg.as_str() {
+            "-c" | "--components" => types.load_components(value()?).map_err(|e| e.to_string())?,
+            "-e" | "--entity-types" => types.load_entity_types(value()?).map_err(|e| e.to_string())?,
+            "-r" | "--relation-types" => types.load_relation_types(value()?).map_err(|e| e.to_string())?,
+            "-o" | "--output" => output = Some(value()?),
+            "--output-dir" => output_dir = Some(value()?),
+            "--model-crate" => model_crate = Some(value()?),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with('-') || generator.is_some() => return Err(format!("Unexpected argument {}\n\n{}", arg, USAGE)),
+            _ => generator = Some(arg),
+        }
+    }
+    Ok(Arguments {
+        generator: generator.ok_or_else(|| USAGE.to_string())?,
+        types,
+        output,
+        output_dir,
+        model_crate,
+    })
+}
+
+fn generate(arguments: &Arguments) -> Result<Generated, String> {
+    match arguments.generator.as_str() {
+        "rust" => {
+            let mut generator = RustModelGenerator::new();
+            if let Some(model_crate) = &arguments.model_crate {
+                generator = generator.with_model_crate(model_crate);
+            }
+            generator.generate(&arguments.types).map(Generated::Code).map_err(|e| e.to_string())
+        }
+        "json-schema" => types_json_schemas(&arguments.types).map(Generated::Json).map_err(|e| e.to_string()),
+        "model-json-schema" => Ok(Generated::Json(model_json_schemas())),
+        "graphql" => graphql_schema(&arguments.types).map(Generated::Code).map_err(|e| e.to_string()),
+        "typescript" => typescript_definitions(&arguments.types).map(Generated::Code).map_err(|e| e.to_string()),
+        generator => Err(format!("Unknown generator {}\n\n{}", generator, USAGE)),
+    }
+}
+
+fn write(path: &Path, content: String) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default() + "\n"
+}
+
+fn output(arguments: &Arguments, generated: Generated) -> Result<(), String> {
+    let code = match (generated, &arguments.output_dir) {
+        (Generated::Json(documents), Some(output_dir)) => {
+            fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create {}: {}", output_dir, e))?;
+            for (name, document) in documents.iter() {
+                write(&Path::new(output_dir).join(format!("{}.schema.json", name)), pretty(document))?;
+            }
+            return Ok(());
+        }
+        (Generated::Json(documents), None) => pretty(&serde_json::to_value(documents).unwrap_or_default()),
+        (Generated::Code(_), Some(_)) => return Err(format!("The generator {} doesn't support --output-dir", arguments.generator)),
+        (Generated::Code(code), None) => code,
+    };
+    match &arguments.output {
+        Some(output) => write(Path::new(output), code),
+        None => {
+            print!("{}", code);
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let result = parse_arguments().and_then(|arguments| output(&arguments, generate(&arguments)?));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use inexor_rgf_core_model::Component;
+use inexor_rgf_core_model::EffectiveProperties;
+use inexor_rgf_core_model::EntityType;
+use inexor_rgf_core_model::RelationType;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::CodegenError;
+
+/// The components, entity types and relation types which are the input of the code generators.
+#[derive(Clone, Debug, Default)]
+pub struct ModelTypes {
+    pub components: Vec<Component>,
+    pub entity_types: Vec<EntityType>,
+    pub relation_types: Vec<RelationType>,
+}
+
+impl ModelTypes {
+    pub fn new() -> ModelTypes {
+        ModelTypes::default()
+    }
+
+    /// Loads components from the given JSON file or from all JSON files in the given directory.
+    pub fn load_components<P: AsRef<Path>>(&mut self, path: P) -> Result<(), CodegenError> {
+        self.components.append(&mut load(path.as_ref())?);
+        Ok(())
+    }
+
+    /// Loads entity types from the given JSON file or from all JSON files in the given directory.
+    pub fn load_entity_types<P: AsRef<Path>>(&mut self, path: P) -> Result<(), CodegenError> {
+        self.entity_types.append(&mut load(path.as_ref())?);
+        Ok(())
+    }
+
+    /// Loads relation types from the given JSON file or from all JSON files in the given directory.
+    pub fn load_relation_types<P: AsRef<Path>>(&mut self, path: P) -> Result<(), CodegenError> {
+        self.relation_types.append(&mut load(path.as_ref())?);
+        Ok(())
+    }
+}
+
+/// Returns the given effective properties of the type with the given name, if all components
+/// have been found and no properties are conflicting.
+pub fn resolved_properties(ty: &str, effective_properties: EffectiveProperties) -> Result<EffectiveProperties, CodegenError> {
+    if !effective_properties.missing_components.is_empty() {
+        return Err(CodegenError::MissingComponents {
+            ty: ty.to_string(),
+            components: effective_properties.missing_components,
+        });
+    }
+    if !effective_properties.conflicts.is_empty() {
+        return Err(CodegenError::PropertyConflicts {
+            ty: ty.to_string(),
+            conflicts: effective_properties.conflicts,
+        });
+    }
+    Ok(effective_properties)
+}
+
+/// Loads the types of a file or of all JSON files in a directory. Files may either contain a
+/// single type or an array of types. The files of a directory are loaded in alphabetical order.
+fn load<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, CodegenError> {
+    if !path.is_dir() {
+        return load_file(path);
+    }
+    let io_error = |error| CodegenError::Io {
+        path: path.to_path_buf(),
+        error,
+    };
+    let mut files: Vec<PathBuf> = fs::read_dir(path)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()
+        .map_err(io_error)?;
+    files.retain(|file| file.is_file() && file.extension().map(|extension| extension == "json").unwrap_or(false));
+    files.sort();
+    let mut types = Vec::new();
+    for file in files {
+        types.append(&mut load_file(&file)?);
+    }
+    Ok(types)
+}
+
+fn load_file<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, CodegenError> {
+    let json = fs::read_to_string(path).map_err(|error| CodegenError::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    let json_error = |error| CodegenError::Json {
+        path: path.to_path_buf(),
+        error,
+    };
+    match serde_json::from_str(&json).map_err(json_error)? {
+        Value::Array(values) => values.into_iter().map(|value| serde_json::from_value(value).map_err(json_error)).collect(),
+        value => Ok(vec![serde_json::from_value(value).map_err(json_error)?]),
+    }
+}
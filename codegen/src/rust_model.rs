@@ -0,0 +1,343 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use inexor_rgf_core_model::ComponentTypeId;
+use inexor_rgf_core_model::DataType;
+use inexor_rgf_core_model::Mutability;
+use inexor_rgf_core_model::NamespacedTypeGetter;
+use inexor_rgf_core_model::PropertyType;
+use inexor_rgf_core_model::SocketType;
+use serde_json::Value;
+
+use crate::is_identifier;
+use crate::to_snake_case_identifier;
+use crate::to_upper_camel_case;
+use crate::to_upper_snake_case;
+use crate::CodegenError;
+use crate::ModelTypes;
+
+pub const DEFAULT_MODEL_CRATE: &str = "inexor_rgf_core_model";
+
+/// Generates rust source code from components, entity types and relation types.
+///
+/// The generated file contains one module per namespace. Each module defines the namespace
+/// constant and for each type the properties enum (`properties!`), the type id (`component_ty!`,
+/// `entity_ty!` or `relation_ty!`) and the model (`component_model!`, `entity_model!` or
+/// `relation_model!`). Entity and relation models implement the component models of their
+/// components, if the components are part of the generated types.
+pub struct RustModelGenerator {
+    /// The path of the crate which provides the model macros.
+    model_crate: String,
+}
+
+impl RustModelGenerator {
+    pub fn new() -> RustModelGenerator {
+        RustModelGenerator {
+            model_crate: DEFAULT_MODEL_CRATE.to_string(),
+        }
+    }
+
+    /// Sets the path of the crate which provides the model macros, for example `crate::model`.
+    pub fn with_model_crate<S: Into<String>>(mut self, model_crate: S) -> RustModelGenerator {
+        self.model_crate = model_crate.into();
+        self
+    }
+
+    /// Generates the rust source code of the given types.
+    pub fn generate(&self, types: &ModelTypes) -> Result<String, CodegenError> {
+        let mut modules: BTreeMap<String, RustModule> = BTreeMap::new();
+        let mut component_models = HashMap::new();
+        for component in types.components.iter() {
+            let module = get_module(&mut modules, &component.namespace());
+            let origin = format!("component {}__{}", component.namespace(), component.type_name());
+            let ident = to_upper_camel_case(&component.type_name());
+            let upper_snake_case = to_upper_snake_case(&component.type_name());
+            let model = format!("{}Component", ident);
+            let mut item = description(&component.description);
+            item.push_str(&module.properties(&origin, &format!("{}Properties", ident), &component.properties)?);
+            module.declare(&format!("COMPONENT_{}", upper_snake_case), &origin)?;
+            module.declare(&format!("COMPONENT_NAME_{}", upper_snake_case), &origin)?;
+            module.declare(&model, &origin)?;
+            module.uses.insert("component_ty");
+            module.uses.insert("component_model");
+            item.push_str(&format!(
+                "component_ty!(COMPONENT_{}, {}, COMPONENT_NAME_{}, {:?});\n\n",
+                upper_snake_case,
+                module.namespace_const,
+                upper_snake_case,
+                component.type_name()
+            ));
+            item.push_str(&missing_accessors(&component.properties));
+            item.push_str(&format!("component_model!({}{});\n", model, accessors(&component.properties)));
+            module.items.push(item);
+            component_models.insert(component.ty.clone(), (module.ident.clone(), model));
+        }
+        for entity_type in types.entity_types.iter() {
+            let module = get_module(&mut modules, &entity_type.namespace());
+            let origin = format!("entity type {}__{}", entity_type.namespace(), entity_type.type_name());
+            let ident = to_upper_camel_case(&entity_type.type_name());
+            let upper_snake_case = to_upper_snake_case(&entity_type.type_name());
+            let model = format!("Reactive{}", ident);
+            let mut item = description(&entity_type.description);
+            item.push_str(&module.properties(&origin, &format!("{}Properties", ident), &entity_type.properties)?);
+            module.declare(&format!("ENTITY_TYPE_{}", upper_snake_case), &origin)?;
+            module.declare(&format!("ENTITY_TYPE_NAME_{}", upper_snake_case), &origin)?;
+            module.declare(&model, &origin)?;
+            module.uses.insert("entity_ty");
+            module.uses.insert("entity_model");
+            item.push_str(&format!(
+                "entity_ty!(ENTITY_TYPE_{}, {}, ENTITY_TYPE_NAME_{}, {:?});\n\n",
+                upper_snake_case,
+                module.namespace_const,
+                upper_snake_case,
+                entity_type.type_name()
+            ));
+            item.push_str(&missing_accessors(&entity_type.properties));
+            item.push_str(&format!("entity_model!({}{});\n", model, accessors(&entity_type.properties)));
+            item.push_str(&component_impls(&module.ident, &model, &entity_type.components, &component_models));
+            module.items.push(item);
+        }
+        for relation_type in types.relation_types.iter() {
+            let module = get_module(&mut modules, &relation_type.namespace());
+            let origin = format!("relation type {}__{}", relation_type.namespace(), relation_type.type_name());
+            let ident = to_upper_camel_case(&relation_type.type_name());
+            let upper_snake_case = to_upper_snake_case(&relation_type.type_name());
+            let model = format!("Reactive{}", ident);
+            let mut item = description(&relation_type.description);
+            item.push_str(&module.properties(&origin, &format!("{}Properties", ident), &relation_type.properties)?);
+            module.declare(&format!("RELATION_TYPE_{}", upper_snake_case), &origin)?;
+            module.declare(&format!("RELATION_TYPE_NAME_{}", upper_snake_case), &origin)?;
+            module.declare(&model, &origin)?;
+            module.uses.insert("relation_ty");
+            module.uses.insert("relation_model");
+            item.push_str(&format!(
+                "relation_ty!(RELATION_TYPE_{}, {}, RELATION_TYPE_NAME_{}, {:?});\n\n",
+                upper_snake_case,
+                module.namespace_const,
+                upper_snake_case,
+                relation_type.type_name()
+            ));
+            item.push_str(&missing_accessors(&relation_type.properties));
+            item.push_str(&format!("relation_model!({}{});\n", model, accessors(&relation_type.properties)));
+            item.push_str(&component_impls(&module.ident, &model, &relation_type.components, &component_models));
+            module.items.push(item);
+        }
+
+        let mut source = String::from(
+            "// Generated from the type definitions by inexor-rgf-codegen. Do not edit.\n\
+             //\n\
+             // The model macros require the crates indradb (indradb-lib), lazy_static, serde_json and strum_macros.\n",
+        );
+        let mut module_idents = HashMap::new();
+        for (namespace, module) in modules.iter() {
+            if let Some(other) = module_idents.insert(module.ident.clone(), namespace.clone()) {
+                return Err(CodegenError::DuplicateIdentifier {
+                    identifier: module.ident.clone(),
+                    first: format!("namespace {}", other),
+                    second: format!("namespace {}", namespace),
+                });
+            }
+            source.push('\n');
+            source.push_str(&module.generate(&self.model_crate));
+        }
+        Ok(source)
+    }
+}
+
+impl Default for RustModelGenerator {
+    fn default() -> Self {
+        RustModelGenerator::new()
+    }
+}
+
+/// The generated module of a namespace.
+struct RustModule {
+    namespace: String,
+    ident: String,
+    namespace_const: String,
+    uses: BTreeSet<&'static str>,
+    items: Vec<String>,
+    /// The declared identifiers and the types which declare them.
+    identifiers: HashMap<String, String>,
+}
+
+impl RustModule {
+    fn new(namespace: &str) -> RustModule {
+        RustModule {
+            namespace: namespace.to_string(),
+            ident: to_snake_case_identifier(namespace),
+            namespace_const: format!("NAMESPACE_{}", to_upper_snake_case(namespace)),
+            uses: BTreeSet::new(),
+            items: Vec::new(),
+            identifiers: HashMap::new(),
+        }
+    }
+
+    /// Declares an identifier of the module. Fails if the identifier is already declared by another type.
+    fn declare(&mut self, identifier: &str, origin: &str) -> Result<(), CodegenError> {
+        match self.identifiers.insert(identifier.to_string(), origin.to_string()) {
+            Some(first) => Err(CodegenError::DuplicateIdentifier {
+                identifier: identifier.to_string(),
+                first,
+                second: origin.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Generates the properties enum. Types without properties have no properties enum.
+    fn properties(&mut self, origin: &str, ident: &str, properties: &[PropertyType]) -> Result<String, CodegenError> {
+        if properties.is_empty() {
+            return Ok(String::new());
+        }
+        self.declare(ident, origin)?;
+        self.uses.insert("properties");
+        let mut property_idents = HashMap::new();
+        let mut variants = Vec::new();
+        for property in properties.iter() {
+            let property_ident = to_upper_snake_case(&property.name);
+            if let Some(first) = property_idents.insert(property_ident.clone(), &property.name) {
+                return Err(CodegenError::DuplicateIdentifier {
+                    identifier: property_ident,
+                    first: format!("property {} of {}", first, origin),
+                    second: format!("property {} of {}", property.name, origin),
+                });
+            }
+            variants.push(format!(
+                "({}, {:?}, {})",
+                property_ident,
+                property.name,
+                default_value_expr(&property.data_type.default_value())
+            ));
+        }
+        Ok(format!("properties!({}, {});\n\n", ident, variants.join(", ")))
+    }
+
+    fn generate(&self, model_crate: &str) -> String {
+        let mut module = format!("pub mod {} {{\n", self.ident);
+        for macro_name in self.uses.iter() {
+            module.push_str(&format!("    use {}::{};\n", model_crate, macro_name));
+        }
+        module.push_str(&format!("\n    pub const {}: &str = {:?};\n", self.namespace_const, self.namespace));
+        for item in self.items.iter() {
+            module.push('\n');
+            for line in item.lines() {
+                if line.is_empty() {
+                    module.push('\n');
+                } else {
+                    module.push_str(&format!("    {}\n", line));
+                }
+            }
+        }
+        module.push_str("}\n");
+        module
+    }
+}
+
+fn get_module<'a>(modules: &'a mut BTreeMap<String, RustModule>, namespace: &str) -> &'a mut RustModule {
+    modules.entry(namespace.to_string()).or_insert_with(|| RustModule::new(namespace))
+}
+
+fn description(description: &str) -> String {
+    description
+        .lines()
+        .map(|line| match line.trim_end() {
+            "" => String::from("//\n"),
+            line => format!("// {}\n", line),
+        })
+        .collect()
+}
+
+/// Generates the accessors of the properties. Mutable properties which are not output sockets
+/// have getters, setters and observers. All other properties have getters and observers.
+///
+/// The getters are prefixed with `get_` and the setters are prefixed with `set_`. Properties whose
+/// names are not snake case identifiers have no accessors.
+fn accessors(properties: &[PropertyType]) -> String {
+    let mut accessors = String::new();
+    for property in properties.iter() {
+        if !has_accessors(property) {
+            continue;
+        }
+        let accessor_type = accessor_type(&property.data_type);
+        if property.mutability == Mutability::Immutable || property.socket_type == SocketType::Output {
+            accessors.push_str(&format!(", readonly {} {}", property.name, accessor_type));
+        } else {
+            accessors.push_str(&format!(", data {} {}", property.name, accessor_type));
+        }
+    }
+    accessors
+}
+
+fn has_accessors(property: &PropertyType) -> bool {
+    is_identifier(&property.name) && property.name == property.name.to_ascii_lowercase()
+}
+
+/// Generates a comment for each property which has no accessors.
+fn missing_accessors(properties: &[PropertyType]) -> String {
+    properties
+        .iter()
+        .filter(|property| !has_accessors(property))
+        .map(|property| format!("// The property {:?} has no accessors because its name is not a snake case identifier\n", property.name))
+        .collect()
+}
+
+/// The data type token which is understood by `rx_accessor!`.
+fn accessor_type(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::Bool => "bool",
+        DataType::Integer => "i64",
+        DataType::Number | DataType::Float => "f64",
+        DataType::String | DataType::Uuid | DataType::DateTime | DataType::Bytes | DataType::Enum(_) => "string",
+        DataType::Array => "array",
+        DataType::Object => "object",
+        DataType::Null | DataType::Any => "value",
+    }
+}
+
+/// Implements the component models of the given components for the model of an entity type or
+/// relation type. Components which are not part of the generated types are skipped.
+fn component_impls(module_ident: &str, model: &str, components: &[ComponentTypeId], component_models: &HashMap<ComponentTypeId, (String, String)>) -> String {
+    let mut impls = String::new();
+    for component in components.iter() {
+        match component_models.get(component) {
+            Some((component_module_ident, component_model)) if component_module_ident == module_ident => {
+                impls.push_str(&format!("\nimpl {} for {} {{}}\n", component_model, model));
+            }
+            Some((component_module_ident, component_model)) => {
+                impls.push_str(&format!("\nimpl super::{}::{} for {} {{}}\n", component_module_ident, component_model, model));
+            }
+            None => {}
+        }
+    }
+    impls
+}
+
+/// Renders the given value as expression for `properties!`. The macro passes the expression to
+/// `serde_json::json!` as a rust expression, so nulls, arrays and objects are wrapped in
+/// `serde_json::json!`.
+fn default_value_expr(value: &Value) -> String {
+    match value {
+        Value::Bool(_) | Value::Number(_) | Value::String(_) => json_literal(value),
+        Value::Null | Value::Array(_) | Value::Object(_) => format!("serde_json::json!({})", json_literal(value)),
+    }
+}
+
+/// Renders the given value as input of `serde_json::json!`.
+fn json_literal(value: &Value) -> String {
+    match value {
+        Value::Null => String::from("null"),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => format!("{:?}", s),
+        Value::Array(values) => format!("[{}]", values.iter().map(json_literal).collect::<Vec<String>>().join(", ")),
+        Value::Object(map) => format!(
+            "{{{}}}",
+            map.iter()
+                .map(|(key, value)| format!("{:?}: {}", key, json_literal(value)))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
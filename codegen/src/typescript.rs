@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use inexor_rgf_core_model::DataType;
+use inexor_rgf_core_model::Mutability;
+use inexor_rgf_core_model::NamespacedTypeGetter;
+use inexor_rgf_core_model::PropertyType;
+use inexor_rgf_core_model::SocketType;
+
+use crate::resolved_properties;
+use crate::to_upper_camel_case;
+use crate::CodegenError;
+use crate::ModelTypes;
+
+/// The TypeScript definitions of the serialized forms of the model types.
+///
+/// Fields which are optional when deserializing are always present in serialized types, so all
+/// fields are required. The instances are generic over the type of their properties.
+pub const MODEL_TYPESCRIPT: &str = r#"export interface NamespacedType {
+  namespace: string;
+  type_name: string;
+}
+
+export type ComponentOrEntityTypeId = { component: NamespacedType } | { entity_type: NamespacedType };
+
+export type DataType =
+  | "null"
+  | "bool"
+  | "number"
+  | "string"
+  | "array"
+  | "object"
+  | "any"
+  | "integer"
+  | "float"
+  | "uuid"
+  | "datetime"
+  | "bytes"
+  | { enum: string[] };
+
+export type SocketType = "none" | "input" | "output";
+
+export type Mutability = "mutable" | "immutable";
+
+export type PropagationMode = "always" | "on_change" | { on_change_with_epsilon: number };
+
+export interface Extension extends NamespacedType {
+  description: string;
+  extension: unknown;
+}
+
+export interface PropertyType {
+  name: string;
+  description: string;
+  data_type: DataType;
+  socket_type: SocketType;
+  mutability: Mutability;
+  propagation: PropagationMode;
+  extensions: Extension[];
+}
+
+export interface ValueMapping {
+  from: unknown;
+  to: unknown;
+}
+
+export type ValueConverter = "convert" | "convert_or_default" | "default" | { map: ValueMapping[] };
+
+export type MigrationStep =
+  | { step: "rename_property"; from: string; to: string }
+  | { step: "change_data_type"; name: string; data_type: DataType; converter: ValueConverter }
+  | { step: "add_property"; name: string; value: unknown }
+  | { step: "drop_property"; name: string }
+  | { step: "add_component"; component: NamespacedType }
+  | { step: "remove_component"; component: NamespacedType }
+  | { step: "migrate_component"; component: NamespacedType; from_version: number };
+
+export interface TypeMigration {
+  version: number;
+  description: string;
+  steps: MigrationStep[];
+}
+
+export interface Component extends NamespacedType {
+  version: number;
+  description: string;
+  properties: PropertyType[];
+  migrations: TypeMigration[];
+  extensions: Extension[];
+}
+
+export interface EntityType extends NamespacedType {
+  version: number;
+  description: string;
+  components: NamespacedType[];
+  properties: PropertyType[];
+  migrations: TypeMigration[];
+  extensions: Extension[];
+}
+
+export interface RelationType extends NamespacedType {
+  outbound: ComponentOrEntityTypeId;
+  inbound: ComponentOrEntityTypeId;
+  version: number;
+  description: string;
+  components: NamespacedType[];
+  properties: PropertyType[];
+  migrations: TypeMigration[];
+  extensions: Extension[];
+}
+
+export interface EntityInstance<P = Record<string, unknown>> extends NamespacedType {
+  id: string;
+  version?: number;
+  description: string;
+  properties: P;
+  extensions: Extension[];
+}
+
+export interface RelationInstance<P = Record<string, unknown>> extends NamespacedType {
+  outbound_id: string;
+  instance_id: string;
+  inbound_id: string;
+  version?: number;
+  description: string;
+  properties: P;
+  extensions: Extension[];
+}
+
+export interface FlowInstance extends NamespacedType {
+  id: string;
+  name: string;
+  description: string;
+  entity_instances: EntityInstance[];
+  relation_instances: RelationInstance[];
+}
+
+export interface FlowType extends NamespacedType {
+  description: string;
+  wrapper_entity_instance: EntityInstance;
+  entity_instances: EntityInstance[];
+  relation_instances: RelationInstance[];
+  variables: PropertyType[];
+  extensions: Extension[];
+}
+"#;
+
+/// The names which are declared by the model types.
+const MODEL_TYPESCRIPT_NAMES: &[&str] = &[
+    "NamespacedType",
+    "ComponentOrEntityTypeId",
+    "DataType",
+    "SocketType",
+    "Mutability",
+    "PropagationMode",
+    "Extension",
+    "PropertyType",
+    "ValueMapping",
+    "ValueConverter",
+    "MigrationStep",
+    "TypeMigration",
+    "Component",
+    "EntityType",
+    "RelationType",
+    "EntityInstance",
+    "RelationInstance",
+    "FlowInstance",
+    "FlowType",
+];
+
+/// Generates TypeScript definitions of the model types and of the given types.
+///
+/// Besides the definitions of the model types, each component has an interface
+/// `{Namespace}{TypeName}ComponentProperties` of its properties. Entity types and relation types
+/// have an interface `{Namespace}{TypeName}Properties` of the resolved properties of the type and
+/// an instance type `{Namespace}{TypeName}` which is an `EntityInstance` or a `RelationInstance`
+/// with these properties. Immutable properties are read only.
+///
+/// Fails if a component is missing, if components declare the same property differently or if
+/// multiple types result in the same TypeScript name.
+pub fn typescript_definitions(types: &ModelTypes) -> Result<String, CodegenError> {
+    let mut names: HashMap<String, String> = MODEL_TYPESCRIPT_NAMES
+        .iter()
+        .map(|name| (name.to_string(), String::from("the model types")))
+        .collect();
+    let mut declare = |name: &str, origin: &str| match names.insert(name.to_string(), origin.to_string()) {
+        Some(first) => Err(CodegenError::DuplicateIdentifier {
+            identifier: name.to_string(),
+            first,
+            second: origin.to_string(),
+        }),
+        None => Ok(()),
+    };
+
+    let mut definitions = vec![MODEL_TYPESCRIPT.to_string()];
+    for component in types.components.iter() {
+        let origin = format!("component {}__{}", component.namespace(), component.type_name());
+        let name = format!("{}ComponentProperties", type_name(&component.namespace(), &component.type_name()));
+        declare(&name, &origin)?;
+        definitions.push(properties_interface(&name, &component.description, &component.properties));
+    }
+    for entity_type in types.entity_types.iter() {
+        let ty = format!("{}__{}", entity_type.namespace(), entity_type.type_name());
+        let origin = format!("entity type {}", ty);
+        let name = type_name(&entity_type.namespace(), &entity_type.type_name());
+        declare(&name, &origin)?;
+        declare(&format!("{}Properties", name), &origin)?;
+        let properties = resolved_properties(&ty, entity_type.effective_properties(&types.components))?.property_types();
+        let mut definition = properties_interface(&format!("{}Properties", name), &entity_type.description, &properties);
+        definition.push_str(&format!("\nexport type {} = EntityInstance<{}Properties>;\n", name, name));
+        definitions.push(definition);
+    }
+    for relation_type in types.relation_types.iter() {
+        let ty = format!("{}__{}", relation_type.namespace(), relation_type.type_name());
+        let origin = format!("relation type {}", ty);
+        let name = type_name(&relation_type.namespace(), &relation_type.type_name());
+        declare(&name, &origin)?;
+        declare(&format!("{}Properties", name), &origin)?;
+        let properties = resolved_properties(&ty, relation_type.effective_properties(&types.components))?.property_types();
+        let mut definition = properties_interface(&format!("{}Properties", name), &relation_type.description, &properties);
+        definition.push_str(&format!("\nexport type {} = RelationInstance<{}Properties>;\n", name, name));
+        definitions.push(definition);
+    }
+
+    let mut source = String::from("// Generated from the type definitions by inexor-rgf-codegen. Do not edit.\n");
+    for definition in definitions.iter() {
+        source.push('\n');
+        source.push_str(definition);
+    }
+    Ok(source)
+}
+
+/// Returns the TypeScript type of the values of the given data type.
+pub fn typescript_type(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Null => String::from("null"),
+        DataType::Bool => String::from("boolean"),
+        DataType::Number | DataType::Integer | DataType::Float => String::from("number"),
+        DataType::String | DataType::Uuid | DataType::DateTime | DataType::Bytes => String::from("string"),
+        DataType::Array => String::from("unknown[]"),
+        DataType::Object => String::from("Record<string, unknown>"),
+        DataType::Any => String::from("unknown"),
+        DataType::Enum(values) if values.is_empty() => String::from("string"),
+        DataType::Enum(values) => values.iter().map(|value| quote(value)).collect::<Vec<_>>().join(" | "),
+    }
+}
+
+/// Returns the TypeScript name of the type with the given namespace and type name.
+fn type_name(namespace: &str, type_name: &str) -> String {
+    format!("{}{}", to_upper_camel_case(namespace), to_upper_camel_case(type_name))
+}
+
+/// Generates the interface of the given properties.
+fn properties_interface(name: &str, description: &str, properties: &[PropertyType]) -> String {
+    let mut interface = doc_comment("", &description.lines().collect::<Vec<_>>());
+    interface.push_str(&format!("export interface {} {{\n", name));
+    for property in properties.iter() {
+        let mut lines: Vec<&str> = property.description.lines().collect();
+        if !lines.is_empty() {
+            lines.push("");
+        }
+        let socket_type = match property.socket_type {
+            SocketType::None => "Socket: none",
+            SocketType::Input => "Socket: input",
+            SocketType::Output => "Socket: output",
+        };
+        let mutability = match property.mutability {
+            Mutability::Mutable => "Mutability: mutable",
+            Mutability::Immutable => "Mutability: immutable",
+        };
+        lines.push(socket_type);
+        lines.push(mutability);
+        interface.push_str(&doc_comment("  ", &lines));
+        let readonly = match property.mutability {
+            Mutability::Mutable => "",
+            Mutability::Immutable => "readonly ",
+        };
+        interface.push_str(&format!("  {}{}: {};\n", readonly, property_key(&property.name), typescript_type(&property.data_type)));
+    }
+    interface.push_str("}\n");
+    interface
+}
+
+/// Generates a doc comment of the given lines.
+fn doc_comment(indent: &str, lines: &[&str]) -> String {
+    if lines.iter().all(|line| line.trim().is_empty()) {
+        return String::new();
+    }
+    let mut comment = format!("{}/**\n", indent);
+    for line in lines.iter() {
+        match line.trim_end() {
+            "" => comment.push_str(&format!("{} *\n", indent)),
+            line => comment.push_str(&format!("{} * {}\n", indent, line.replace("*/", "*\\/"))),
+        }
+    }
+    comment.push_str(&format!("{} */\n", indent));
+    comment
+}
+
+/// Returns the key of the property. Property names which aren't identifiers are quoted.
+fn property_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = match chars.next() {
+        Some(first) => (first.is_ascii_alphabetic() || first == '_' || first == '$') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$'),
+        None => false,
+    };
+    match is_identifier {
+        true => name.to_string(),
+        false => quote(name),
+    }
+}
+
+/// Quotes the given text as a TypeScript string literal.
+fn quote(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
@@ -0,0 +1,145 @@
+use inexor_rgf_core_model_codegen::graphql_schema;
+use inexor_rgf_core_model_codegen::is_graphql_name;
+use inexor_rgf_core_model_codegen::CodegenError;
+use inexor_rgf_core_model_codegen::ModelTypes;
+use serde_json::json;
+
+fn model_types() -> ModelTypes {
+    let mut types = ModelTypes::new();
+    types.components.push(
+        serde_json::from_value(json!({
+            "namespace": "core",
+            "type_name": "labeled",
+            "description": "A labeled instance",
+            "properties": [
+                { "name": "label", "data_type": "string" },
+                { "name": "kind", "data_type": { "enum": ["small", "large"] } }
+            ]
+        }))
+        .unwrap(),
+    );
+    types.components.push(
+        serde_json::from_value(json!({
+            "namespace": "core",
+            "type_name": "marker"
+        }))
+        .unwrap(),
+    );
+    types.entity_types.push(
+        serde_json::from_value(json!({
+            "namespace": "logical",
+            "type_name": "and_gate",
+            "description": "Logical \"and\"",
+            "components": [{ "namespace": "core", "type_name": "labeled" }, { "namespace": "core", "type_name": "marker" }],
+            "properties": [
+                { "name": "lhs", "data_type": "bool", "socket_type": "input", "description": "The left hand side" },
+                { "name": "result", "data_type": "bool", "socket_type": "output" },
+                { "name": "count", "data_type": "integer" },
+                { "name": "values", "data_type": "array" },
+                { "name": "created", "data_type": "datetime" },
+                { "name": "mode", "data_type": { "enum": ["fast mode", "slow mode"] } },
+                { "name": "not-valid", "data_type": "string" }
+            ]
+        }))
+        .unwrap(),
+    );
+    types.relation_types.push(
+        serde_json::from_value(json!({
+            "outbound": { "component": { "namespace": "core", "type_name": "labeled" } },
+            "namespace": "connector",
+            "type_name": "default_connector",
+            "inbound": { "entity_type": { "namespace": "logical", "type_name": "and_gate" } },
+            "properties": [
+                { "name": "outbound_property_name", "data_type": "string" },
+                { "name": "payload", "data_type": "any" }
+            ]
+        }))
+        .unwrap(),
+    );
+    types
+}
+
+#[test]
+fn is_graphql_name_test() {
+    assert!(is_graphql_name("label"));
+    assert!(is_graphql_name("_label2"));
+    assert!(!is_graphql_name("2label"));
+    assert!(!is_graphql_name("not-valid"));
+    assert!(!is_graphql_name("__typename"));
+    assert!(!is_graphql_name(""));
+}
+
+#[test]
+fn graphql_schema_test() {
+    let sdl = graphql_schema(&model_types()).unwrap();
+    assert!(sdl.starts_with("# Generated from the type definitions by inexor-rgf-codegen. Do not edit.\n"));
+    assert!(sdl.contains("\nscalar DateTime\n"));
+    assert!(sdl.contains("\nscalar JSON\n"));
+    assert!(sdl.contains("\nscalar Long\n"));
+    assert!(sdl.contains("\nscalar UUID\n"));
+    assert!(sdl.contains("enum CoreLabeledKind {\n  small\n  large\n}\n"));
+    assert!(sdl.contains("\"A labeled instance\"\ninterface CoreLabeledComponent {\n  label: String!\n  kind: CoreLabeledKind!\n}\n"));
+    // Components without properties have no interface
+    assert!(!sdl.contains("CoreMarkerComponent"));
+    assert!(sdl.contains(
+        "\"Logical \\\"and\\\"\"\ntype LogicalAndGate implements CoreLabeledComponent {\n\
+         \x20 \"The id of the entity instance\"\n\
+         \x20 id: UUID!\n\
+         \x20 \"The left hand side\"\n\
+         \x20 lhs: Boolean!\n\
+         \x20 result: Boolean!\n\
+         \x20 count: Long!\n\
+         \x20 values: [JSON]!\n\
+         \x20 created: DateTime!\n\
+         \x20 mode: String!\n\
+         \x20 # The property not-valid is skipped, because it isn't a valid GraphQL name\n\
+         \x20 label: String!\n\
+         \x20 kind: CoreLabeledKind!\n\
+         \x20 \"The outgoing relations of type connector__default_connector\"\n\
+         \x20 outbound_connector_default_connector: [ConnectorDefaultConnector!]!\n\
+         \x20 \"The incoming relations of type connector__default_connector\"\n\
+         \x20 inbound_connector_default_connector: [ConnectorDefaultConnector!]!\n\
+         }\n"
+    ));
+    assert!(sdl.contains(
+        "type ConnectorDefaultConnector {\n\
+         \x20 \"The id of the outbound entity instance\"\n\
+         \x20 outbound_id: UUID!\n\
+         \x20 \"The id of the inbound entity instance\"\n\
+         \x20 inbound_id: UUID!\n\
+         \x20 \"The outbound entity instance\"\n\
+         \x20 outbound: CoreLabeledComponent!\n\
+         \x20 \"The inbound entity instance\"\n\
+         \x20 inbound: LogicalAndGate!\n\
+         \x20 outbound_property_name: String!\n\
+         \x20 payload: JSON\n\
+         }\n"
+    ));
+}
+
+#[test]
+fn graphql_schema_errors_test() {
+    // A property collides with a generated field
+    let mut types = model_types();
+    types.relation_types[0].properties[0].name = "inbound".to_string();
+    match graphql_schema(&types) {
+        Err(CodegenError::DuplicateIdentifier { identifier, first, second }) => {
+            assert_eq!("inbound", identifier);
+            assert_eq!("field inbound of relation type connector__default_connector", first);
+            assert_eq!("property inbound of relation type connector__default_connector", second);
+        }
+        _ => panic!("Expected duplicate identifier"),
+    }
+
+    // Two types have the same GraphQL name
+    let mut types = model_types();
+    let mut entity_type = types.entity_types[0].clone();
+    entity_type.ty = serde_json::from_value(json!({ "namespace": "logical", "type_name": "AndGate" })).unwrap();
+    types.entity_types.push(entity_type);
+    assert!(matches!(graphql_schema(&types), Err(CodegenError::DuplicateIdentifier { .. })));
+
+    // Missing component
+    let mut types = model_types();
+    types.components.remove(0);
+    assert!(matches!(graphql_schema(&types), Err(CodegenError::MissingComponents { .. })));
+}
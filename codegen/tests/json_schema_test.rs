@@ -0,0 +1,107 @@
+use inexor_rgf_core_model::Component;
+use inexor_rgf_core_model::ComponentTypeId;
+use inexor_rgf_core_model::DataType;
+use inexor_rgf_core_model::EntityType;
+use inexor_rgf_core_model::PropertyType;
+use inexor_rgf_core_model::SocketType;
+use inexor_rgf_core_model_codegen::entity_type_json_schema;
+use inexor_rgf_core_model_codegen::model_json_schemas;
+use inexor_rgf_core_model_codegen::types_json_schemas;
+use inexor_rgf_core_model_codegen::CodegenError;
+use inexor_rgf_core_model_codegen::ModelTypes;
+use inexor_rgf_core_model_codegen::JSON_SCHEMA_DRAFT;
+use serde_json::json;
+
+#[test]
+fn model_json_schemas_test() {
+    let schemas = model_json_schemas();
+    assert_eq!(
+        vec![
+            "component",
+            "entity_instance",
+            "entity_type",
+            "flow_instance",
+            "flow_type",
+            "relation_instance",
+            "relation_type"
+        ],
+        schemas.keys().collect::<Vec<_>>()
+    );
+    let entity_type = &schemas["entity_type"];
+    assert_eq!(json!(JSON_SCHEMA_DRAFT), entity_type["$schema"]);
+    assert_eq!(json!("EntityType"), entity_type["title"]);
+    // The type id is flattened into the entity type
+    assert_eq!(json!([{ "$ref": "#/definitions/NamespacedType" }]), entity_type["allOf"]);
+    assert_eq!(json!(["type_name"]), entity_type["definitions"]["NamespacedType"]["required"]);
+    assert!(entity_type["properties"]["components"].is_object());
+    assert!(entity_type["required"].is_null());
+    let entity_instance = &schemas["entity_instance"];
+    assert_eq!(json!("uuid"), entity_instance["properties"]["id"]["format"]);
+    let relation_type = &schemas["relation_type"];
+    assert!(relation_type["properties"]["outbound"].is_object());
+    assert!(relation_type["properties"]["inbound"].is_object());
+}
+
+#[test]
+fn entity_type_json_schema_test() {
+    let mut types = ModelTypes::new();
+    types.components.push(Component::new_from_type(
+        "core",
+        "labeled",
+        "",
+        vec![PropertyType::new("label", DataType::String), PropertyType::new("id", DataType::Uuid)],
+        Vec::new(),
+    ));
+    let mut result = PropertyType::output("result", DataType::Bool);
+    result.description = "The result".to_string();
+    types.entity_types.push(EntityType::new_from_type(
+        "logical",
+        "and",
+        "Logical and",
+        vec![ComponentTypeId::new_from_type("core", "labeled")],
+        vec![
+            PropertyType::input("lhs", DataType::Bool),
+            result,
+            PropertyType::new("kind", DataType::Enum(vec!["a".to_string(), "b".to_string()])),
+        ],
+        Vec::new(),
+    ));
+    let schemas = types_json_schemas(&types).unwrap();
+    let schema = &schemas["logical__and"];
+    assert_eq!(json!("logical__and"), schema["title"]);
+    assert_eq!(json!("Logical and"), schema["description"]);
+    assert_eq!(json!(["lhs", "result", "kind", "label", "id"]), schema["required"]);
+    assert_eq!(json!(false), schema["additionalProperties"]);
+    assert_eq!(json!({ "type": "boolean", "default": false }), schema["properties"]["lhs"]);
+    assert_eq!(
+        json!({ "type": "boolean", "description": "The result", "readOnly": true, "default": false }),
+        schema["properties"]["result"]
+    );
+    assert_eq!(json!({ "type": "string", "enum": ["a", "b"], "default": "a" }), schema["properties"]["kind"]);
+    assert_eq!(json!("uuid"), schema["properties"]["id"]["format"]);
+
+    // Missing components
+    let entity_type = &types.entity_types[0];
+    match entity_type_json_schema(entity_type, &Vec::<Component>::new()) {
+        Err(CodegenError::MissingComponents { ty, components }) => {
+            assert_eq!("logical__and", ty);
+            assert_eq!(vec![ComponentTypeId::new_from_type("core", "labeled")], components);
+        }
+        _ => panic!("Expected missing components"),
+    }
+
+    // Conflicting components
+    let mut entity_type = entity_type.clone();
+    entity_type.components.push(ComponentTypeId::new_from_type("core", "other"));
+    types.components.push(Component::new_from_type(
+        "core",
+        "other",
+        "",
+        vec![PropertyType::new_with_socket("label", DataType::String, SocketType::Input)],
+        Vec::new(),
+    ));
+    assert!(matches!(
+        entity_type_json_schema(&entity_type, &types.components),
+        Err(CodegenError::PropertyConflicts { .. })
+    ));
+}
@@ -0,0 +1,175 @@
+use std::env;
+use std::fs;
+
+use inexor_rgf_core_model::entity_model;
+use inexor_rgf_core_model_codegen::to_snake_case_identifier;
+use inexor_rgf_core_model_codegen::to_upper_camel_case;
+use inexor_rgf_core_model_codegen::to_upper_snake_case;
+use inexor_rgf_core_model_codegen::CodegenError;
+use inexor_rgf_core_model_codegen::ModelTypes;
+use inexor_rgf_core_model_codegen::RustModelGenerator;
+use serde_json::json;
+
+fn model_types() -> ModelTypes {
+    let mut types = ModelTypes::new();
+    types.components.push(
+        serde_json::from_value(json!({
+            "namespace": "core",
+            "type_name": "labeled",
+            "description": "A labeled instance",
+            "properties": [
+                { "name": "label", "data_type": "string" },
+                { "name": "type", "data_type": "string" }
+            ]
+        }))
+        .unwrap(),
+    );
+    types.entity_types.push(
+        serde_json::from_value(json!({
+            "namespace": "logical",
+            "type_name": "and_gate",
+            "components": [{ "namespace": "core", "type_name": "labeled" }, { "namespace": "core", "type_name": "unknown" }],
+            "properties": [
+                { "name": "lhs", "data_type": "bool", "socket_type": "input" },
+                { "name": "result", "data_type": "bool", "socket_type": "output" },
+                { "name": "count", "data_type": "integer", "mutability": "immutable" }
+            ]
+        }))
+        .unwrap(),
+    );
+    types.relation_types.push(
+        serde_json::from_value(json!({
+            "namespace": "core",
+            "type_name": "connector",
+            "outbound": { "component": { "namespace": "core", "type_name": "labeled" } },
+            "inbound": { "entity_type": { "namespace": "logical", "type_name": "and_gate" } },
+            "components": [{ "namespace": "core", "type_name": "labeled" }]
+        }))
+        .unwrap(),
+    );
+    types
+}
+
+#[test]
+fn identifier_test() {
+    assert_eq!("AndGate", to_upper_camel_case("and_gate"));
+    assert_eq!("HttpRequest", to_upper_camel_case("HTTP-request"));
+    assert_eq!("_3d", to_upper_camel_case("3d"));
+    assert_eq!("OUTBOUND_PROPERTY_NAME", to_upper_snake_case("outboundPropertyName"));
+    assert_eq!("A_B", to_upper_snake_case("a-b"));
+    assert_eq!("r#type", to_snake_case_identifier("type"));
+    assert_eq!("self_", to_snake_case_identifier("self"));
+}
+
+#[test]
+fn rust_model_generator_test() {
+    let source = RustModelGenerator::new().generate(&model_types()).unwrap();
+    let expected_core = r#"pub mod core {
+    use inexor_rgf_core_model::component_model;
+    use inexor_rgf_core_model::component_ty;
+    use inexor_rgf_core_model::properties;
+    use inexor_rgf_core_model::relation_model;
+    use inexor_rgf_core_model::relation_ty;
+
+    pub const NAMESPACE_CORE: &str = "core";
+
+    // A labeled instance
+    properties!(LabeledProperties, (LABEL, "label", ""), (TYPE, "type", ""));
+
+    component_ty!(COMPONENT_LABELED, NAMESPACE_CORE, COMPONENT_NAME_LABELED, "labeled");
+
+    // The property "type" has no accessors because its name is not a snake case identifier
+    component_model!(LabeledComponent, data label string);
+
+    relation_ty!(RELATION_TYPE_CONNECTOR, NAMESPACE_CORE, RELATION_TYPE_NAME_CONNECTOR, "connector");
+
+    relation_model!(ReactiveConnector);
+
+    impl LabeledComponent for ReactiveConnector {}
+}
+"#;
+    assert!(source.contains(expected_core), "{}", source);
+    let expected_logical = r#"pub mod logical {
+    use inexor_rgf_core_model::entity_model;
+    use inexor_rgf_core_model::entity_ty;
+    use inexor_rgf_core_model::properties;
+
+    pub const NAMESPACE_LOGICAL: &str = "logical";
+
+    properties!(AndGateProperties, (LHS, "lhs", false), (RESULT, "result", false), (COUNT, "count", 0));
+
+    entity_ty!(ENTITY_TYPE_AND_GATE, NAMESPACE_LOGICAL, ENTITY_TYPE_NAME_AND_GATE, "and_gate");
+
+    entity_model!(ReactiveAndGate, data lhs bool, readonly result bool, readonly count i64);
+
+    impl super::core::LabeledComponent for ReactiveAndGate {}
+}
+"#;
+    assert!(source.contains(expected_logical), "{}", source);
+
+    let source = RustModelGenerator::new().with_model_crate("crate::model").generate(&model_types()).unwrap();
+    assert!(source.contains("    use crate::model::entity_model;\n"));
+
+    // Arrays and objects are no valid rust expressions
+    let mut types = ModelTypes::new();
+    types.components.push(
+        serde_json::from_value(json!({
+            "namespace": "core",
+            "type_name": "flags",
+            "properties": [{ "name": "flags", "data_type": "object" }, { "name": "list", "data_type": "array" }]
+        }))
+        .unwrap(),
+    );
+    let source = RustModelGenerator::new().generate(&types).unwrap();
+    assert!(
+        source.contains(r#"properties!(FlagsProperties, (FLAGS, "flags", serde_json::json!({})), (LIST, "list", serde_json::json!([])));"#),
+        "{}",
+        source
+    );
+}
+
+// The accessors which are generated for the entity type "and_gate"
+entity_model!(ReactiveAndGate, data lhs bool, readonly result bool, readonly count i64);
+
+#[test]
+fn rust_model_generator_accessors_test() {
+    assert_eq!("lhs", ReactiveAndGate::LHS);
+    assert_eq!("result", ReactiveAndGate::RESULT);
+    assert_eq!("count", ReactiveAndGate::COUNT);
+    // All getters are prefixed with get_
+    let _: fn(&ReactiveAndGate) -> Option<bool> = ReactiveAndGate::get_lhs;
+    let _: fn(&ReactiveAndGate) -> Option<bool> = ReactiveAndGate::get_result;
+    let _: fn(&ReactiveAndGate) -> Option<i64> = ReactiveAndGate::get_count;
+}
+
+#[test]
+fn rust_model_generator_duplicate_identifier_test() {
+    let mut types = model_types();
+    types
+        .entity_types
+        .push(serde_json::from_value(json!({ "namespace": "logical", "type_name": "AndGate" })).unwrap());
+    match RustModelGenerator::new().generate(&types) {
+        Err(CodegenError::DuplicateIdentifier { identifier, first, second }) => {
+            assert_eq!("ENTITY_TYPE_AND_GATE", identifier);
+            assert_eq!("entity type logical__and_gate", first);
+            assert_eq!("entity type logical__AndGate", second);
+        }
+        _ => panic!("Expected a duplicate identifier"),
+    }
+}
+
+#[test]
+fn load_model_types_test() {
+    let dir = env::temp_dir().join(format!("inexor-rgf-codegen-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let types = model_types();
+    fs::write(dir.join("a.json"), serde_json::to_string(&types.entity_types[0]).unwrap()).unwrap();
+    fs::write(dir.join("b.json"), serde_json::to_string(&types.entity_types).unwrap()).unwrap();
+    fs::write(dir.join("c.txt"), "not a type").unwrap();
+    let mut loaded = ModelTypes::new();
+    loaded.load_entity_types(&dir).unwrap();
+    assert_eq!(2, loaded.entity_types.len());
+    loaded.load_relation_types(dir.join("a.json")).unwrap_err();
+    assert!(matches!(loaded.load_components(dir.join("missing.json")), Err(CodegenError::Io { .. })));
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeSet;
+
+use inexor_rgf_core_model::DataType;
+use inexor_rgf_core_model::EntityInstance;
+use inexor_rgf_core_model::FlowInstance;
+use inexor_rgf_core_model::PropertyType;
+use inexor_rgf_core_model::RelationInstance;
+use inexor_rgf_core_model::RelationType;
+use inexor_rgf_core_model::TypeMigration;
+use inexor_rgf_core_model_codegen::typescript_definitions;
+use inexor_rgf_core_model_codegen::typescript_type;
+use inexor_rgf_core_model_codegen::CodegenError;
+use inexor_rgf_core_model_codegen::ModelTypes;
+use inexor_rgf_core_model_codegen::MODEL_TYPESCRIPT;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use serde_json::Value;
+
+/// Returns the fields of the interface with the given name including the fields of NamespacedType and the optional fields.
+fn interface_fields(name: &str) -> BTreeSet<String> {
+    let start = MODEL_TYPESCRIPT
+        .find(&format!("export interface {}", name))
+        .unwrap_or_else(|| panic!("Missing interface {}", name));
+    let interface = &MODEL_TYPESCRIPT[start..];
+    let (declaration, body) = interface.split_once("{\n").unwrap();
+    let (body, _) = body.split_once("\n}").unwrap();
+    let mut fields: BTreeSet<String> = body
+        .lines()
+        .map(|line| line.trim().split(':').next().unwrap().trim_end_matches('?').to_string())
+        .collect();
+    if declaration.contains("extends NamespacedType") {
+        fields.insert("namespace".to_string());
+        fields.insert("type_name".to_string());
+    }
+    fields
+}
+
+/// Asserts that the serialized value has exactly the fields of the interface.
+fn assert_serialized_fields<T: Serialize + DeserializeOwned>(name: &str, value: Value) {
+    let value: T = serde_json::from_value(value).unwrap();
+    let keys: BTreeSet<String> = serde_json::to_value(value).unwrap().as_object().unwrap().keys().cloned().collect();
+    assert_eq!(interface_fields(name), keys, "The fields of {} don't match", name);
+}
+
+#[test]
+fn model_typescript_test() {
+    assert_serialized_fields::<EntityInstance>(
+        "EntityInstance",
+        json!({ "namespace": "logical", "type_name": "and", "id": "2f5b5da2-09b8-4b4c-a43f-4a3b2a3c2a10", "version": 1 }),
+    );
+    assert_serialized_fields::<RelationInstance>(
+        "RelationInstance",
+        json!({
+            "namespace": "connector",
+            "type_name": "default",
+            "instance_id": "",
+            "version": 1,
+            "outbound_id": "2f5b5da2-09b8-4b4c-a43f-4a3b2a3c2a10",
+            "inbound_id": "2f5b5da2-09b8-4b4c-a43f-4a3b2a3c2a11"
+        }),
+    );
+    assert_serialized_fields::<FlowInstance>(
+        "FlowInstance",
+        json!({ "namespace": "logical", "type_name": "and", "id": "2f5b5da2-09b8-4b4c-a43f-4a3b2a3c2a10" }),
+    );
+    assert_serialized_fields::<PropertyType>("PropertyType", json!({ "name": "result", "data_type": "bool" }));
+    assert_serialized_fields::<TypeMigration>("TypeMigration", json!({ "version": 1 }));
+    assert_serialized_fields::<RelationType>(
+        "RelationType",
+        json!({
+            "outbound": { "entity_type": { "namespace": "logical", "type_name": "and" } },
+            "namespace": "connector",
+            "type_name": "default",
+            "inbound": { "component": { "namespace": "core", "type_name": "labeled" } }
+        }),
+    );
+}
+
+#[test]
+fn typescript_type_test() {
+    assert_eq!("boolean", typescript_type(&DataType::Bool));
+    assert_eq!("number", typescript_type(&DataType::Integer));
+    assert_eq!("string", typescript_type(&DataType::DateTime));
+    assert_eq!("unknown[]", typescript_type(&DataType::Array));
+    assert_eq!("Record<string, unknown>", typescript_type(&DataType::Object));
+    assert_eq!("\"a\" | \"b c\"", typescript_type(&DataType::Enum(vec!["a".to_string(), "b c".to_string()])));
+    assert_eq!("string", typescript_type(&DataType::Enum(Vec::new())));
+}
+
+#[test]
+fn typescript_definitions_test() {
+    let mut types = ModelTypes::new();
+    types.components.push(
+        serde_json::from_value(json!({
+            "namespace": "core",
+            "type_name": "labeled",
+            "description": "A labeled instance",
+            "properties": [{ "name": "label", "data_type": "string", "mutability": "immutable" }]
+        }))
+        .unwrap(),
+    );
+    types.entity_types.push(
+        serde_json::from_value(json!({
+            "namespace": "logical",
+            "type_name": "and_gate",
+            "components": [{ "namespace": "core", "type_name": "labeled" }],
+            "properties": [
+                { "name": "lhs", "data_type": "bool", "socket_type": "input", "description": "The left hand side" },
+                { "name": "not-valid", "data_type": "any" }
+            ]
+        }))
+        .unwrap(),
+    );
+    types.relation_types.push(
+        serde_json::from_value(json!({
+            "outbound": { "component": { "namespace": "core", "type_name": "labeled" } },
+            "namespace": "connector",
+            "type_name": "default_connector",
+            "inbound": { "entity_type": { "namespace": "logical", "type_name": "and_gate" } }
+        }))
+        .unwrap(),
+    );
+    let source = typescript_definitions(&types).unwrap();
+    assert!(source.starts_with("// Generated from the type definitions by inexor-rgf-codegen. Do not edit.\n"));
+    assert!(source.contains(MODEL_TYPESCRIPT));
+    assert!(source.contains(
+        "/**\n * A labeled instance\n */\n\
+         export interface CoreLabeledComponentProperties {\n\
+         \x20 /**\n\
+         \x20  * Socket: none\n\
+         \x20  * Mutability: immutable\n\
+         \x20  */\n\
+         \x20 readonly label: string;\n\
+         }\n"
+    ));
+    assert!(source.contains(
+        "export interface LogicalAndGateProperties {\n\
+         \x20 /**\n\
+         \x20  * The left hand side\n\
+         \x20  *\n\
+         \x20  * Socket: input\n\
+         \x20  * Mutability: mutable\n\
+         \x20  */\n\
+         \x20 lhs: boolean;\n\
+         \x20 /**\n\
+         \x20  * Socket: none\n\
+         \x20  * Mutability: mutable\n\
+         \x20  */\n\
+         \x20 \"not-valid\": unknown;\n\
+         \x20 /**\n\
+         \x20  * Socket: none\n\
+         \x20  * Mutability: immutable\n\
+         \x20  */\n\
+         \x20 readonly label: string;\n\
+         }\n\
+         \n\
+         export type LogicalAndGate = EntityInstance<LogicalAndGateProperties>;\n"
+    ));
+    assert!(source.contains("export interface ConnectorDefaultConnectorProperties {\n}\n\nexport type ConnectorDefaultConnector = RelationInstance<ConnectorDefaultConnectorProperties>;\n"));
+
+    // Types which collide with the model types
+    types.entity_types[0].ty = serde_json::from_value(json!({ "namespace": "entity", "type_name": "instance" })).unwrap();
+    match typescript_definitions(&types) {
+        Err(CodegenError::DuplicateIdentifier { identifier, .. }) => assert_eq!("EntityInstance", identifier),
+        _ => panic!("Expected duplicate identifier"),
+    }
+}
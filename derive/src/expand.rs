@@ -0,0 +1,247 @@
+use proc_macro2::Ident;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Fields;
+use syn::Result;
+
+use crate::property_field::PropertyField;
+use crate::type_attributes::ComponentOrEntityTypeReference;
+use crate::type_attributes::TypeAttributes;
+use crate::type_attributes::TypeReference;
+
+fn parse_fields(input: &DeriveInput) -> Result<Vec<PropertyField>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().map(PropertyField::parse).collect(),
+            Fields::Unit => Ok(Vec::new()),
+            Fields::Unnamed(_) => Err(Error::new_spanned(&input.ident, "expected a struct with named fields")),
+        },
+        _ => Err(Error::new_spanned(&input.ident, "expected a struct")),
+    }
+}
+
+fn component_type_ids(components: &[TypeReference]) -> Vec<TokenStream> {
+    components
+        .iter()
+        .map(|TypeReference { namespace, type_name }| quote!(::inexor_rgf_core_model::ComponentTypeId::new_from_type(#namespace, #type_name)))
+        .collect()
+}
+
+fn component_or_entity_type_id(ty: &ComponentOrEntityTypeReference) -> TokenStream {
+    match ty {
+        ComponentOrEntityTypeReference::Component(TypeReference { namespace, type_name }) => quote! {
+            ::inexor_rgf_core_model::ComponentOrEntityTypeId::Component(::inexor_rgf_core_model::ComponentTypeId::new_from_type(#namespace, #type_name))
+        },
+        ComponentOrEntityTypeReference::EntityType(TypeReference { namespace, type_name }) => quote! {
+            ::inexor_rgf_core_model::ComponentOrEntityTypeId::EntityType(::inexor_rgf_core_model::EntityTypeId::new_from_type(#namespace, #type_name))
+        },
+    }
+}
+
+/// Generates the property enum and the functions which are common to all model structs.
+fn expand_properties(ident: &Ident, type_attributes: &TypeAttributes, fields: &[PropertyField]) -> TokenStream {
+    let properties_ident = format_ident!("{}Properties", ident);
+    let TypeAttributes { namespace, type_name, .. } = type_attributes;
+    let variants: Vec<&Ident> = fields.iter().map(|field| &field.variant).collect();
+    let names: Vec<&String> = fields.iter().map(|field| &field.name).collect();
+    let field_idents: Vec<&Ident> = fields.iter().map(|field| &field.ident).collect();
+    let default_values: Vec<TokenStream> = fields.iter().map(|field| field.default_value()).collect();
+    let property_types: Vec<TokenStream> = fields.iter().map(|field| field.property_type()).collect();
+    let getters: Vec<TokenStream> = fields.iter().map(|field| field.data_type.getter(&field.name)).collect();
+    let doc = format!("The properties of [`{}`].", ident);
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum #properties_ident {
+            #(#variants,)*
+        }
+
+        impl #properties_ident {
+            /// Returns all properties.
+            pub fn all() -> Vec<#properties_ident> {
+                vec![#(#properties_ident::#variants,)*]
+            }
+        }
+
+        impl ::inexor_rgf_core_model::PropertyTypeDefinition for #properties_ident {
+            fn property_name(&self) -> String {
+                match self {
+                    #(#properties_ident::#variants => #names.to_string(),)*
+                }
+            }
+
+            fn default_value(&self) -> ::inexor_rgf_core_model::serde_json::Value {
+                match self {
+                    #(#properties_ident::#variants => #default_values,)*
+                }
+            }
+        }
+
+        impl std::fmt::Display for #properties_ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", ::inexor_rgf_core_model::PropertyTypeDefinition::property_name(self))
+            }
+        }
+
+        impl From<#properties_ident> for String {
+            fn from(property: #properties_ident) -> Self {
+                ::inexor_rgf_core_model::PropertyTypeDefinition::property_name(&property)
+            }
+        }
+
+        impl #ident {
+            pub const NAMESPACE: &'static str = #namespace;
+
+            pub const TYPE_NAME: &'static str = #type_name;
+
+            /// Returns the property types.
+            pub fn property_types() -> Vec<::inexor_rgf_core_model::PropertyType> {
+                vec![#(#property_types,)*]
+            }
+
+            /// Returns the default values of the properties.
+            pub fn default_properties() -> std::collections::HashMap<String, ::inexor_rgf_core_model::serde_json::Value> {
+                #properties_ident::all()
+                    .iter()
+                    .map(|property| {
+                        (
+                            ::inexor_rgf_core_model::PropertyTypeDefinition::property_name(property),
+                            ::inexor_rgf_core_model::PropertyTypeDefinition::default_value(property),
+                        )
+                    })
+                    .collect()
+            }
+
+            /// Returns the values of the properties.
+            pub fn properties(&self) -> std::collections::HashMap<String, ::inexor_rgf_core_model::serde_json::Value> {
+                let mut properties = std::collections::HashMap::new();
+                #(properties.insert(#names.to_string(), ::inexor_rgf_core_model::serde_json::json!(self.#field_idents));)*
+                properties
+            }
+
+            /// Reads the values of the properties from the given instance. Returns None, if a
+            /// property is missing or has another data type.
+            pub fn from_properties<T: ::inexor_rgf_core_model::PropertyInstanceGetter>(instance: &T) -> Option<#ident> {
+                Some(#ident {
+                    #(#field_idents: #getters?,)*
+                })
+            }
+        }
+    }
+}
+
+fn accessors(fields: &[PropertyField]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .map(|field| {
+            let ident = &field.ident;
+            let accessor_type = field.data_type.accessor_type();
+            quote!(data #ident #accessor_type)
+        })
+        .collect()
+}
+
+pub fn expand_entity_model(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let type_attributes = TypeAttributes::parse(&input.attrs, "entity_type", ident)?;
+    let fields = parse_fields(&input)?;
+    let properties = expand_properties(ident, &type_attributes, &fields);
+    let description = &type_attributes.description;
+    let components = component_type_ids(&type_attributes.components);
+    let model = type_attributes.model.clone().unwrap_or_else(|| format_ident!("Reactive{}", ident));
+    let accessors = accessors(&fields);
+    Ok(quote! {
+        #properties
+
+        impl #ident {
+            /// Returns the type id of the entity type.
+            pub fn type_id() -> ::inexor_rgf_core_model::EntityTypeId {
+                ::inexor_rgf_core_model::EntityTypeId::new_from_type(#ident::NAMESPACE, #ident::TYPE_NAME)
+            }
+
+            /// Returns the definition of the entity type.
+            pub fn entity_type() -> ::inexor_rgf_core_model::EntityType {
+                ::inexor_rgf_core_model::EntityType::new(#ident::type_id(), #description, vec![#(#components,)*], #ident::property_types(), Vec::new())
+            }
+        }
+
+        ::inexor_rgf_core_model::entity_model!(#model #(, #accessors)*);
+    })
+}
+
+pub fn expand_relation_model(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let type_attributes = TypeAttributes::parse(&input.attrs, "relation_type", ident)?;
+    let outbound = type_attributes
+        .outbound
+        .as_ref()
+        .map(component_or_entity_type_id)
+        .ok_or_else(|| Error::new_spanned(ident, "missing outbound_component or outbound_entity_type"))?;
+    let inbound = type_attributes
+        .inbound
+        .as_ref()
+        .map(component_or_entity_type_id)
+        .ok_or_else(|| Error::new_spanned(ident, "missing inbound_component or inbound_entity_type"))?;
+    let fields = parse_fields(&input)?;
+    let properties = expand_properties(ident, &type_attributes, &fields);
+    let description = &type_attributes.description;
+    let components = component_type_ids(&type_attributes.components);
+    let model = type_attributes.model.clone().unwrap_or_else(|| format_ident!("Reactive{}", ident));
+    let accessors = accessors(&fields);
+    Ok(quote! {
+        #properties
+
+        impl #ident {
+            /// Returns the type id of the relation type.
+            pub fn type_id() -> ::inexor_rgf_core_model::RelationTypeId {
+                ::inexor_rgf_core_model::RelationTypeId::new_from_type(#ident::NAMESPACE, #ident::TYPE_NAME)
+            }
+
+            /// Returns the definition of the relation type.
+            pub fn relation_type() -> ::inexor_rgf_core_model::RelationType {
+                ::inexor_rgf_core_model::RelationType::new(
+                    #outbound,
+                    #ident::type_id(),
+                    #inbound,
+                    #description,
+                    vec![#(#components,)*],
+                    #ident::property_types(),
+                    Vec::new(),
+                )
+            }
+        }
+
+        ::inexor_rgf_core_model::relation_model!(#model #(, #accessors)*);
+    })
+}
+
+pub fn expand_component_model(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let type_attributes = TypeAttributes::parse(&input.attrs, "component", ident)?;
+    let fields = parse_fields(&input)?;
+    let properties = expand_properties(ident, &type_attributes, &fields);
+    let description = &type_attributes.description;
+    let model = type_attributes.model.clone().unwrap_or_else(|| format_ident!("{}Component", ident));
+    let accessors = accessors(&fields);
+    Ok(quote! {
+        #properties
+
+        impl #ident {
+            /// Returns the type id of the component.
+            pub fn type_id() -> ::inexor_rgf_core_model::ComponentTypeId {
+                ::inexor_rgf_core_model::ComponentTypeId::new_from_type(#ident::NAMESPACE, #ident::TYPE_NAME)
+            }
+
+            /// Returns the definition of the component.
+            pub fn component() -> ::inexor_rgf_core_model::Component {
+                ::inexor_rgf_core_model::Component::new(#ident::type_id(), #description, #ident::property_types(), Vec::new())
+            }
+        }
+
+        ::inexor_rgf_core_model::component_model!(#model #(, #accessors)*);
+    })
+}
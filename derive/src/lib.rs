@@ -0,0 +1,76 @@
+//! Derive macros which generate the type definitions and the reactive models of the Inexor
+//! Reactive Graph Flow from plain structs.
+//!
+//! Each named field of the struct defines a property. The data type of the property is derived
+//! from the rust type of the field: `bool`, `u64`, `i64`, `f64`, `String`, `Vec<Value>`,
+//! `Map<String, Value>` or `Value`. The field attribute `#[property(...)]` accepts
+//! `description = "..."`, `socket = "none" | "input" | "output"`,
+//! `mutability = "mutable" | "immutable"` and `default = <expression>`.
+//!
+//! Types are referenced in the form `"namespace__type_name"`.
+//!
+//! Fields of other types are rejected, including arrays and maps of other element types:
+//!
+//! ```compile_fail
+//! use inexor_rgf_core_model_derive::ComponentModel;
+//!
+//! #[derive(ComponentModel)]
+//! #[component(namespace = "core")]
+//! pub struct Tagged {
+//!     tags: Vec<String>,
+//! }
+//! ```
+
+use proc_macro::TokenStream;
+use syn::parse_macro_input;
+use syn::DeriveInput;
+
+mod case;
+mod expand;
+mod property_field;
+mod type_attributes;
+
+/// Generates the entity type, the properties enum and the reactive entity model.
+///
+/// ```ignore
+/// #[derive(EntityModel)]
+/// #[entity_type(namespace = "logical", type_name = "and", description = "Logical and", components = ["logical__gate"])]
+/// pub struct And {
+///     #[property(socket = "input")]
+///     lhs: bool,
+///     #[property(socket = "input")]
+///     rhs: bool,
+///     #[property(socket = "output")]
+///     result: bool,
+/// }
+/// ```
+///
+/// The struct attribute `#[entity_type(...)]` accepts `namespace`, `type_name` (defaults to the
+/// struct name in snake case), `description`, `components` and `model` (the name of the
+/// reactive model, defaults to `Reactive` + struct name).
+#[proc_macro_derive(EntityModel, attributes(entity_type, property))]
+pub fn derive_entity_model(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::expand_entity_model(input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+/// Generates the relation type, the properties enum and the reactive relation model.
+///
+/// The struct attribute `#[relation_type(...)]` accepts the same keys as `#[entity_type(...)]`
+/// and additionally either `outbound_component` or `outbound_entity_type` and either
+/// `inbound_component` or `inbound_entity_type`.
+#[proc_macro_derive(RelationModel, attributes(relation_type, property))]
+pub fn derive_relation_model(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::expand_relation_model(input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+/// Generates the component, the properties enum and the component model trait.
+///
+/// The struct attribute `#[component(...)]` accepts `namespace`, `type_name`, `description` and
+/// `model` (the name of the trait, defaults to struct name + `Component`).
+#[proc_macro_derive(ComponentModel, attributes(component, property))]
+pub fn derive_component_model(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::expand_component_model(input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
@@ -0,0 +1,227 @@
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Error;
+use syn::Expr;
+use syn::Field;
+use syn::GenericArgument;
+use syn::LitStr;
+use syn::PathArguments;
+use syn::PathSegment;
+use syn::Result;
+use syn::Type;
+
+use crate::case::to_upper_camel_case;
+
+/// The supported rust types of a property.
+#[derive(Clone, Copy)]
+pub enum PropertyDataType {
+    Value,
+    Bool,
+    U64,
+    I64,
+    F64,
+    String,
+    Array,
+    Object,
+}
+
+impl PropertyDataType {
+    fn from_type(ty: &Type) -> Result<PropertyDataType> {
+        let segment = match ty {
+            Type::Path(type_path) => type_path.path.segments.last(),
+            _ => None,
+        };
+        let arguments = segment.map(type_arguments).unwrap_or_default();
+        let data_type = match (segment.map(|segment| segment.ident.to_string()).as_deref(), arguments.as_slice()) {
+            (Some("Value"), []) => Some(PropertyDataType::Value),
+            (Some("bool"), []) => Some(PropertyDataType::Bool),
+            (Some("u64"), []) => Some(PropertyDataType::U64),
+            (Some("i64"), []) => Some(PropertyDataType::I64),
+            (Some("f64"), []) => Some(PropertyDataType::F64),
+            (Some("String"), []) => Some(PropertyDataType::String),
+            (Some("Vec"), [value]) if is_type(value, "Value") => Some(PropertyDataType::Array),
+            (Some("Map"), [key, value]) if is_type(key, "String") && is_type(value, "Value") => Some(PropertyDataType::Object),
+            _ => None,
+        };
+        data_type.ok_or_else(|| {
+            Error::new_spanned(
+                ty,
+                "unsupported property type, expected one of: bool, u64, i64, f64, String, Vec<Value>, Map<String, Value>, Value",
+            )
+        })
+    }
+
+    /// The data type token which is understood by `rx_accessor!`.
+    pub fn accessor_type(&self) -> Ident {
+        let accessor_type = match self {
+            PropertyDataType::Value => "value",
+            PropertyDataType::Bool => "bool",
+            PropertyDataType::U64 => "u64",
+            PropertyDataType::I64 => "i64",
+            PropertyDataType::F64 => "f64",
+            PropertyDataType::String => "string",
+            PropertyDataType::Array => "array",
+            PropertyDataType::Object => "object",
+        };
+        Ident::new(accessor_type, Span::call_site())
+    }
+
+    /// The data type of the property type.
+    pub fn data_type(&self) -> TokenStream {
+        match self {
+            PropertyDataType::Value => quote!(::inexor_rgf_core_model::DataType::Any),
+            PropertyDataType::Bool => quote!(::inexor_rgf_core_model::DataType::Bool),
+            PropertyDataType::U64 | PropertyDataType::I64 => quote!(::inexor_rgf_core_model::DataType::Integer),
+            PropertyDataType::F64 => quote!(::inexor_rgf_core_model::DataType::Float),
+            PropertyDataType::String => quote!(::inexor_rgf_core_model::DataType::String),
+            PropertyDataType::Array => quote!(::inexor_rgf_core_model::DataType::Array),
+            PropertyDataType::Object => quote!(::inexor_rgf_core_model::DataType::Object),
+        }
+    }
+
+    /// Reads the typed value of the property with the given name from `instance`.
+    pub fn getter(&self, name: &str) -> TokenStream {
+        let getter = match self {
+            PropertyDataType::Value => quote!(get),
+            PropertyDataType::Bool => quote!(as_bool),
+            PropertyDataType::U64 => quote!(as_u64),
+            PropertyDataType::I64 => quote!(as_i64),
+            PropertyDataType::F64 => quote!(as_f64),
+            PropertyDataType::String => quote!(as_string),
+            PropertyDataType::Array => quote!(as_array),
+            PropertyDataType::Object => quote!(as_object),
+        };
+        quote!(::inexor_rgf_core_model::PropertyInstanceGetter::#getter(instance, #name))
+    }
+}
+
+/// A field of the model struct which defines a property, for example
+/// `#[property(socket = "input", description = "The left hand side")] lhs: bool`.
+pub struct PropertyField {
+    pub ident: Ident,
+    pub name: String,
+    pub variant: Ident,
+    pub data_type: PropertyDataType,
+    pub description: String,
+    pub socket_type: TokenStream,
+    pub mutability: TokenStream,
+    pub default_value: Option<Expr>,
+}
+
+impl PropertyField {
+    pub fn parse(field: &Field) -> Result<PropertyField> {
+        let ident = field.ident.clone().ok_or_else(|| Error::new_spanned(field, "expected a named field"))?;
+        let name = ident.to_string();
+        let mut property_field = PropertyField {
+            variant: Ident::new(&to_upper_camel_case(&name), ident.span()),
+            ident,
+            name,
+            data_type: PropertyDataType::from_type(&field.ty)?,
+            description: String::new(),
+            socket_type: quote!(::inexor_rgf_core_model::SocketType::None),
+            mutability: quote!(::inexor_rgf_core_model::Mutability::Mutable),
+            default_value: None,
+        };
+        let mut mutability = None;
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("property")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("description") {
+                    property_field.description = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("socket") {
+                    let lit = meta.value()?.parse::<LitStr>()?;
+                    property_field.socket_type = match lit.value().as_str() {
+                        "none" => quote!(::inexor_rgf_core_model::SocketType::None),
+                        "input" => quote!(::inexor_rgf_core_model::SocketType::Input),
+                        "output" => {
+                            // Output sockets are immutable by default, like `PropertyType::output`
+                            mutability.get_or_insert(quote!(::inexor_rgf_core_model::Mutability::Immutable));
+                            quote!(::inexor_rgf_core_model::SocketType::Output)
+                        }
+                        _ => return Err(Error::new(lit.span(), "expected one of: none, input, output")),
+                    };
+                } else if meta.path.is_ident("mutability") {
+                    let lit = meta.value()?.parse::<LitStr>()?;
+                    mutability = Some(match lit.value().as_str() {
+                        "mutable" => quote!(::inexor_rgf_core_model::Mutability::Mutable),
+                        "immutable" => quote!(::inexor_rgf_core_model::Mutability::Immutable),
+                        _ => return Err(Error::new(lit.span(), "expected one of: mutable, immutable")),
+                    });
+                } else if meta.path.is_ident("default") {
+                    property_field.default_value = Some(meta.value()?.parse::<Expr>()?);
+                } else {
+                    return Err(meta.error("unsupported property attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        if let Some(mutability) = mutability {
+            property_field.mutability = mutability;
+        }
+        Ok(property_field)
+    }
+
+    /// The default value of the property.
+    pub fn default_value(&self) -> TokenStream {
+        match &self.default_value {
+            Some(default_value) => quote!(::inexor_rgf_core_model::serde_json::json!(#default_value)),
+            None => {
+                let data_type = self.data_type.data_type();
+                quote!(#data_type.default_value())
+            }
+        }
+    }
+
+    /// The property type definition.
+    pub fn property_type(&self) -> TokenStream {
+        let PropertyField {
+            name,
+            description,
+            socket_type,
+            mutability,
+            ..
+        } = self;
+        let data_type = self.data_type.data_type();
+        quote! {
+            ::inexor_rgf_core_model::PropertyType {
+                name: #name.to_string(),
+                description: #description.to_string(),
+                data_type: #data_type,
+                socket_type: #socket_type,
+                mutability: #mutability,
+                propagation: ::inexor_rgf_core_model::PropagationMode::Always,
+                extensions: Vec::new(),
+            }
+        }
+    }
+}
+
+/// Returns the generic type arguments of the given path segment.
+fn type_arguments(segment: &PathSegment) -> Vec<&Type> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .filter_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns true, if the given type is a path without generic arguments which ends with the given
+/// name.
+fn is_type(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == name && segment.arguments.is_empty())
+            .unwrap_or(false),
+        _ => false,
+    }
+}
@@ -0,0 +1,104 @@
+use syn::Attribute;
+use syn::Error;
+use syn::Expr;
+use syn::ExprArray;
+use syn::ExprLit;
+use syn::Ident;
+use syn::Lit;
+use syn::LitStr;
+use syn::Result;
+
+use crate::case::to_snake_case;
+
+/// A reference to a type in the form `namespace__type_name`.
+pub struct TypeReference {
+    pub namespace: String,
+    pub type_name: String,
+}
+
+impl TypeReference {
+    fn parse(lit: &LitStr) -> Result<TypeReference> {
+        let value = lit.value();
+        match value.split_once("__") {
+            Some((namespace, type_name)) if !namespace.is_empty() && !type_name.is_empty() && !type_name.contains("__") => Ok(TypeReference {
+                namespace: namespace.to_string(),
+                type_name: type_name.to_string(),
+            }),
+            _ => Err(Error::new(lit.span(), "expected a type reference in the form \"namespace__type_name\"")),
+        }
+    }
+}
+
+/// The outbound or inbound type of a relation type.
+pub enum ComponentOrEntityTypeReference {
+    Component(TypeReference),
+    EntityType(TypeReference),
+}
+
+/// The attributes of the type definition, for example `#[entity_type(namespace = "logical", type_name = "and")]`.
+pub struct TypeAttributes {
+    pub namespace: String,
+    pub type_name: String,
+    pub description: String,
+    pub components: Vec<TypeReference>,
+    pub outbound: Option<ComponentOrEntityTypeReference>,
+    pub inbound: Option<ComponentOrEntityTypeReference>,
+    pub model: Option<Ident>,
+}
+
+impl TypeAttributes {
+    /// Parses the attribute with the given name. The type name defaults to the name of the struct
+    /// in snake case.
+    pub fn parse(attrs: &[Attribute], attribute_name: &str, ident: &Ident) -> Result<TypeAttributes> {
+        let attr = attrs
+            .iter()
+            .find(|attr| attr.path().is_ident(attribute_name))
+            .ok_or_else(|| Error::new(ident.span(), format!("missing attribute #[{}(namespace = \"...\")]", attribute_name)))?;
+        let mut namespace = None;
+        let mut type_attributes = TypeAttributes {
+            namespace: String::new(),
+            type_name: to_snake_case(&ident.to_string()),
+            description: String::new(),
+            components: Vec::new(),
+            outbound: None,
+            inbound: None,
+            model: None,
+        };
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("namespace") {
+                namespace = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("type_name") {
+                type_attributes.type_name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("description") {
+                type_attributes.description = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("components") {
+                let components = meta.value()?.parse::<ExprArray>()?;
+                for component in components.elems.iter() {
+                    match component {
+                        Expr::Lit(ExprLit { lit: Lit::Str(lit), .. }) => type_attributes.components.push(TypeReference::parse(lit)?),
+                        _ => return Err(Error::new_spanned(component, "expected a string literal")),
+                    }
+                }
+            } else if meta.path.is_ident("outbound_component") {
+                let lit = meta.value()?.parse::<LitStr>()?;
+                type_attributes.outbound = Some(ComponentOrEntityTypeReference::Component(TypeReference::parse(&lit)?));
+            } else if meta.path.is_ident("outbound_entity_type") {
+                let lit = meta.value()?.parse::<LitStr>()?;
+                type_attributes.outbound = Some(ComponentOrEntityTypeReference::EntityType(TypeReference::parse(&lit)?));
+            } else if meta.path.is_ident("inbound_component") {
+                let lit = meta.value()?.parse::<LitStr>()?;
+                type_attributes.inbound = Some(ComponentOrEntityTypeReference::Component(TypeReference::parse(&lit)?));
+            } else if meta.path.is_ident("inbound_entity_type") {
+                let lit = meta.value()?.parse::<LitStr>()?;
+                type_attributes.inbound = Some(ComponentOrEntityTypeReference::EntityType(TypeReference::parse(&lit)?));
+            } else if meta.path.is_ident("model") {
+                type_attributes.model = Some(meta.value()?.parse::<LitStr>()?.parse::<Ident>()?);
+            } else {
+                return Err(meta.error("unsupported attribute"));
+            }
+            Ok(())
+        })?;
+        type_attributes.namespace = namespace.ok_or_else(|| Error::new_spanned(attr, "missing namespace"))?;
+        Ok(type_attributes)
+    }
+}
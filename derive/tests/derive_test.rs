@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use inexor_rgf_core_model::ComponentOrEntityTypeId;
+use inexor_rgf_core_model::ComponentTypeId;
+use inexor_rgf_core_model::DataType;
+use inexor_rgf_core_model::EntityInstance;
+use inexor_rgf_core_model::EntityTypeId;
+use inexor_rgf_core_model::Mutability;
+use inexor_rgf_core_model::PropertyInstanceGetter;
+use inexor_rgf_core_model::PropertyInstanceSetter;
+use inexor_rgf_core_model::PropertyTypeDefinition;
+use inexor_rgf_core_model::ReactiveEntityInstance;
+use inexor_rgf_core_model::ReactiveRelationInstance;
+use inexor_rgf_core_model::RelationInstance;
+use inexor_rgf_core_model::RelationInstanceTypeId;
+use inexor_rgf_core_model::SocketType;
+use inexor_rgf_core_model_derive::ComponentModel;
+use inexor_rgf_core_model_derive::EntityModel;
+use inexor_rgf_core_model_derive::RelationModel;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(EntityModel)]
+#[entity_type(namespace = "arithmetic_f64", description = "Adds two numbers", components = ["logical__operation"])]
+pub struct AddNumbers {
+    #[property(socket = "input", description = "The left hand side")]
+    lhs: f64,
+    #[property(socket = "input", default = 1.0)]
+    rhs: f64,
+    #[property(socket = "output")]
+    result: f64,
+    label: String,
+}
+
+#[derive(RelationModel)]
+#[relation_type(
+    namespace = "connector",
+    type_name = "default_connector",
+    outbound_component = "core__output",
+    inbound_entity_type = "arithmetic_f64__add_numbers",
+    model = "Connector"
+)]
+pub struct DefaultConnector {
+    outbound_property_name: String,
+    inbound_property_name: String,
+    #[property(mutability = "immutable")]
+    count: u64,
+}
+
+#[derive(ComponentModel)]
+#[component(namespace = "core", description = "A named instance")]
+pub struct Named {
+    name: String,
+    #[property(default = "unknown")]
+    kind: Value,
+    tags: Vec<Value>,
+    attributes: Map<String, Value>,
+}
+
+impl NamedComponent for ReactiveEntityInstance {}
+
+#[test]
+fn entity_model_derive_test() {
+    assert_eq!("arithmetic_f64", AddNumbers::NAMESPACE);
+    assert_eq!("add_numbers", AddNumbers::TYPE_NAME);
+    assert_eq!(EntityTypeId::new_from_type("arithmetic_f64", "add_numbers"), AddNumbers::type_id());
+
+    let entity_type = AddNumbers::entity_type();
+    assert_eq!(AddNumbers::type_id(), entity_type.ty);
+    assert_eq!("Adds two numbers", entity_type.description);
+    assert_eq!(vec![ComponentTypeId::new_from_type("logical", "operation")], entity_type.components);
+    assert_eq!(4, entity_type.properties.len());
+    let lhs = entity_type.properties.iter().find(|property| property.name == "lhs").unwrap();
+    assert_eq!(DataType::Float, lhs.data_type);
+    assert_eq!(SocketType::Input, lhs.socket_type);
+    assert_eq!(Mutability::Mutable, lhs.mutability);
+    assert_eq!("The left hand side", lhs.description);
+    let result = entity_type.properties.iter().find(|property| property.name == "result").unwrap();
+    assert_eq!(SocketType::Output, result.socket_type);
+    assert_eq!(Mutability::Immutable, result.mutability);
+    let label = entity_type.properties.iter().find(|property| property.name == "label").unwrap();
+    assert_eq!(DataType::String, label.data_type);
+    assert_eq!(SocketType::None, label.socket_type);
+
+    assert_eq!(
+        vec![
+            AddNumbersProperties::Lhs,
+            AddNumbersProperties::Rhs,
+            AddNumbersProperties::Result,
+            AddNumbersProperties::Label
+        ],
+        AddNumbersProperties::all()
+    );
+    assert_eq!("lhs", AddNumbersProperties::Lhs.property_name());
+    assert_eq!("result", AddNumbersProperties::Result.to_string());
+    assert_eq!("label", String::from(AddNumbersProperties::Label));
+    assert_eq!(json!(0.0), AddNumbersProperties::Lhs.default_value());
+    assert_eq!(json!(1.0), AddNumbersProperties::Rhs.default_value());
+    assert_eq!(json!(""), AddNumbersProperties::Label.default_value());
+
+    let entity_instance = Arc::new(ReactiveEntityInstance::from(EntityInstance::new(
+        AddNumbers::type_id(),
+        Uuid::new_v4(),
+        AddNumbers::default_properties(),
+    )));
+    let add_numbers = ReactiveAddNumbers::from(entity_instance.clone());
+    add_numbers.set_lhs(2.0);
+    add_numbers.set_result(3.0);
+    add_numbers.set_label("sum");
+    assert_eq!(Some(2.0), add_numbers.get_lhs());
+    assert_eq!(Some(1.0), add_numbers.get_rhs());
+    assert_eq!(Some(3.0), entity_instance.as_f64(ReactiveAddNumbers::RESULT));
+
+    let values = AddNumbers::from_properties(&add_numbers).unwrap();
+    assert_eq!(2.0, values.lhs);
+    assert_eq!(1.0, values.rhs);
+    assert_eq!(3.0, values.result);
+    assert_eq!("sum", values.label);
+    assert_eq!(json!(2.0), values.properties()["lhs"]);
+    assert_eq!(json!("sum"), values.properties()["label"]);
+
+    // A property with another data type can't be read
+    add_numbers.set(ReactiveAddNumbers::LABEL, json!(1));
+    assert!(AddNumbers::from_properties(&add_numbers).is_none());
+}
+
+#[test]
+fn relation_model_derive_test() {
+    let relation_type = DefaultConnector::relation_type();
+    assert_eq!(DefaultConnector::type_id(), relation_type.ty);
+    assert_eq!(
+        ComponentOrEntityTypeId::Component(ComponentTypeId::new_from_type("core", "output")),
+        relation_type.outbound_type
+    );
+    assert_eq!(ComponentOrEntityTypeId::EntityType(AddNumbers::type_id()), relation_type.inbound_type);
+    let count = relation_type.properties.iter().find(|property| property.name == "count").unwrap();
+    assert_eq!(DataType::Integer, count.data_type);
+    assert_eq!(Mutability::Immutable, count.mutability);
+    assert_eq!(json!(0), DefaultConnectorProperties::Count.default_value());
+
+    let outbound = Arc::new(ReactiveEntityInstance::from(EntityInstance::new_without_properties(AddNumbers::type_id(), Uuid::new_v4())));
+    let inbound = Arc::new(ReactiveEntityInstance::from(EntityInstance::new_without_properties(AddNumbers::type_id(), Uuid::new_v4())));
+    let relation_instance = RelationInstance::new(
+        outbound.id,
+        RelationInstanceTypeId::new_unique_id(DefaultConnector::type_id()),
+        inbound.id,
+        DefaultConnector::default_properties(),
+    );
+    let relation_instance = Arc::new(ReactiveRelationInstance::new_from_instance(outbound, inbound, relation_instance));
+    let connector = Connector::from(relation_instance);
+    connector.set_outbound_property_name("result");
+    connector.set_inbound_property_name("lhs");
+    let values = DefaultConnector::from_properties(&connector).unwrap();
+    assert_eq!("result", values.outbound_property_name);
+    assert_eq!("lhs", values.inbound_property_name);
+    assert_eq!(0, values.count);
+}
+
+#[test]
+fn component_model_derive_test() {
+    let component = Named::component();
+    assert_eq!(ComponentTypeId::new_from_type("core", "named"), component.ty);
+    assert_eq!("A named instance", component.description);
+    let kind = component.properties.iter().find(|property| property.name == "kind").unwrap();
+    assert_eq!(DataType::Any, kind.data_type);
+    assert_eq!(json!("unknown"), NamedProperties::Kind.default_value());
+    let tags = component.properties.iter().find(|property| property.name == "tags").unwrap();
+    assert_eq!(DataType::Array, tags.data_type);
+    let attributes = component.properties.iter().find(|property| property.name == "attributes").unwrap();
+    assert_eq!(DataType::Object, attributes.data_type);
+
+    let entity_instance = ReactiveEntityInstance::from(EntityInstance::new(AddNumbers::type_id(), Uuid::new_v4(), Named::default_properties()));
+    entity_instance.set_name("instance");
+    assert_eq!(Some("instance".to_string()), NamedComponent::get_name(&entity_instance));
+    assert_eq!(Some(json!("unknown")), entity_instance.get_kind());
+    let values = Named::from_properties(&entity_instance).unwrap();
+    assert_eq!("instance", values.name);
+    assert_eq!(json!("unknown"), values.kind);
+    assert!(values.tags.is_empty());
+    assert!(values.attributes.is_empty());
+}
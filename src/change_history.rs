@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::sync::Weak;
+use std::thread;
+use std::thread::ThreadId;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::ReactiveEntityInstance;
+use crate::ReactiveFlowInstance;
+use crate::ReactivePropertyInstance;
+use crate::ReactiveRelationInstance;
+
+/// A recorded change of the value of a reactive property.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyChange {
+    /// The monotonic sequence number of the change.
+    pub sequence: u64,
+
+    /// The id of the property instance. For entity instances this is the id of the entity
+    /// instance.
+    pub id: Uuid,
+
+    /// The name of the property.
+    pub property_name: String,
+
+    /// The value before the change.
+    pub old_value: Value,
+
+    /// The value after the change.
+    pub new_value: Value,
+
+    /// True, if the value has been set using `set`. False, if the value has been set using
+    /// `set_no_propagate`. Undo and redo are using the same method.
+    pub propagate: bool,
+}
+
+#[derive(Clone)]
+enum ChangeTarget {
+    Entity(Weak<ReactiveEntityInstance>),
+    Relation(Weak<ReactiveRelationInstance>),
+}
+
+impl ChangeTarget {
+    fn apply(&self, property_name: &str, value: Value, propagate: bool) {
+        match self {
+            ChangeTarget::Entity(entity_instance) => {
+                if let Some(property_instance) = entity_instance.upgrade().as_ref().and_then(|e| e.properties.get(property_name)) {
+                    apply(&property_instance, value, propagate);
+                }
+            }
+            ChangeTarget::Relation(relation_instance) => {
+                if let Some(property_instance) = relation_instance.upgrade().as_ref().and_then(|r| r.properties.get(property_name)) {
+                    apply(&property_instance, value, propagate);
+                }
+            }
+        }
+    }
+}
+
+fn apply(property_instance: &ReactivePropertyInstance, value: Value, propagate: bool) {
+    if propagate {
+        property_instance.set(value);
+    } else {
+        property_instance.set_no_propagate(value);
+    }
+}
+
+#[derive(Default)]
+struct ChangeHistoryState {
+    sequence: u64,
+    undo: Vec<Vec<PropertyChange>>,
+    redo: Vec<Vec<PropertyChange>>,
+    group: Option<Vec<PropertyChange>>,
+}
+
+/// Opt-in recorder of the changes of the values of reactive properties with undo and redo.
+///
+/// The history is attached to entity instances, relation instances or flow instances. Every
+/// `set` and `set_no_propagate` of an attached property is recorded. Properties which are added
+/// after attaching the history are not recorded.
+///
+/// Changes are undone and redone in groups. Outside of a group every change is a group of its
+/// own. Recording a new change clears the redo stack.
+#[derive(Default)]
+pub struct ChangeHistory {
+    /// The maximum number of groups which can be undone. Zero means unlimited.
+    limit: usize,
+
+    state: Mutex<ChangeHistoryState>,
+
+    targets: RwLock<HashMap<Uuid, ChangeTarget>>,
+
+    /// The threads which currently undo or redo changes. Their changes are not recorded.
+    replaying: RwLock<HashSet<ThreadId>>,
+}
+
+impl ChangeHistory {
+    pub fn new() -> ChangeHistory {
+        ChangeHistory::default()
+    }
+
+    /// Creates a history which keeps at most the given number of groups.
+    pub fn with_limit(limit: usize) -> ChangeHistory {
+        ChangeHistory { limit, ..Default::default() }
+    }
+
+    /// Records the changes of the properties of the given entity instance.
+    pub fn attach_entity(self: &Arc<Self>, entity_instance: &Arc<ReactiveEntityInstance>) {
+        self.targets
+            .write()
+            .unwrap()
+            .insert(entity_instance.id, ChangeTarget::Entity(Arc::downgrade(entity_instance)));
+        for property_instance in entity_instance.properties.iter() {
+            property_instance.set_history(Some(self.clone()));
+        }
+    }
+
+    /// Records the changes of the properties of the given relation instance.
+    pub fn attach_relation(self: &Arc<Self>, relation_instance: &Arc<ReactiveRelationInstance>) {
+        let mut targets = self.targets.write().unwrap();
+        for property_instance in relation_instance.properties.iter() {
+            targets.insert(property_instance.id, ChangeTarget::Relation(Arc::downgrade(relation_instance)));
+            property_instance.set_history(Some(self.clone()));
+        }
+    }
+
+    /// Records the changes of the properties of all entity instances and relation instances of
+    /// the given flow instance.
+    pub fn attach_flow(self: &Arc<Self>, flow_instance: &ReactiveFlowInstance) {
+        for entity_instance in flow_instance.entity_instances.read().unwrap().values() {
+            self.attach_entity(entity_instance);
+        }
+        for relation_instance in flow_instance.relation_instances.read().unwrap().values() {
+            self.attach_relation(relation_instance);
+        }
+    }
+
+    /// Stops recording the changes of the properties of the given entity instance.
+    pub fn detach_entity(&self, entity_instance: &ReactiveEntityInstance) {
+        self.targets.write().unwrap().remove(&entity_instance.id);
+        for property_instance in entity_instance.properties.iter() {
+            property_instance.set_history(None);
+        }
+    }
+
+    /// Stops recording the changes of the properties of the given relation instance.
+    pub fn detach_relation(&self, relation_instance: &ReactiveRelationInstance) {
+        let mut targets = self.targets.write().unwrap();
+        for property_instance in relation_instance.properties.iter() {
+            targets.remove(&property_instance.id);
+            property_instance.set_history(None);
+        }
+    }
+
+    /// Stops recording the changes of the properties of all entity instances and relation
+    /// instances of the given flow instance.
+    pub fn detach_flow(&self, flow_instance: &ReactiveFlowInstance) {
+        for entity_instance in flow_instance.entity_instances.read().unwrap().values() {
+            self.detach_entity(entity_instance);
+        }
+        for relation_instance in flow_instance.relation_instances.read().unwrap().values() {
+            self.detach_relation(relation_instance);
+        }
+    }
+
+    /// Records a change. Called by the attached reactive property instances.
+    pub fn record(&self, id: Uuid, property_name: &str, old_value: &Value, new_value: &Value, propagate: bool) {
+        if self.replaying.read().unwrap().contains(&thread::current().id()) {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.sequence += 1;
+        let change = PropertyChange {
+            sequence: state.sequence,
+            id,
+            property_name: property_name.to_string(),
+            old_value: old_value.clone(),
+            new_value: new_value.clone(),
+            propagate,
+        };
+        state.redo.clear();
+        match state.group.as_mut() {
+            Some(group) => group.push(change),
+            None => self.push_undo(&mut state, vec![change]),
+        }
+    }
+
+    /// Starts a group of changes which are undone and redone together.
+    pub fn begin_group(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.group.is_none() {
+            state.group = Some(Vec::new());
+        }
+    }
+
+    /// Ends the current group of changes.
+    pub fn end_group(&self) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(group) = state.group.take() {
+            if !group.is_empty() {
+                self.push_undo(&mut state, group);
+            }
+        }
+    }
+
+    /// Records all changes made by the given function as one group.
+    pub fn group<F: FnOnce()>(&self, f: F) {
+        self.begin_group();
+        f();
+        self.end_group();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.state.lock().unwrap().undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.state.lock().unwrap().redo.is_empty()
+    }
+
+    /// Returns the number of groups which can be undone.
+    pub fn undo_len(&self) -> usize {
+        self.state.lock().unwrap().undo.len()
+    }
+
+    /// Returns the number of groups which can be redone.
+    pub fn redo_len(&self) -> usize {
+        self.state.lock().unwrap().redo.len()
+    }
+
+    /// Returns the changes of the groups which can be undone, oldest first.
+    pub fn changes(&self) -> Vec<PropertyChange> {
+        self.state.lock().unwrap().undo.iter().flatten().cloned().collect()
+    }
+
+    /// Undoes the last group of changes by restoring the old values in reverse order. Returns
+    /// the undone changes.
+    pub fn undo(&self) -> Option<Vec<PropertyChange>> {
+        let group = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(group) = state.group.take() {
+                if !group.is_empty() {
+                    self.push_undo(&mut state, group);
+                }
+            }
+            state.undo.pop()?
+        };
+        self.replay(group.iter().rev().map(|change| (change, change.old_value.clone())));
+        self.state.lock().unwrap().redo.push(group.clone());
+        Some(group)
+    }
+
+    /// Redoes the last undone group of changes by applying the new values. Returns the redone
+    /// changes.
+    pub fn redo(&self) -> Option<Vec<PropertyChange>> {
+        let group = self.state.lock().unwrap().redo.pop()?;
+        self.replay(group.iter().map(|change| (change, change.new_value.clone())));
+        let mut state = self.state.lock().unwrap();
+        self.push_undo(&mut state, group.clone());
+        Some(group)
+    }
+
+    /// Removes all recorded changes.
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.undo.clear();
+        state.redo.clear();
+        state.group = None;
+    }
+
+    fn push_undo(&self, state: &mut ChangeHistoryState, group: Vec<PropertyChange>) {
+        state.undo.push(group);
+        if self.limit > 0 && state.undo.len() > self.limit {
+            state.undo.remove(0);
+        }
+    }
+
+    fn replay<'a, I: Iterator<Item = (&'a PropertyChange, Value)>>(&self, changes: I) {
+        let _replay_guard = ReplayGuard::new(&self.replaying);
+        let targets = self.targets.read().unwrap().clone();
+        for (change, value) in changes {
+            if let Some(target) = targets.get(&change.id) {
+                target.apply(&change.property_name, value, change.propagate);
+            }
+        }
+    }
+}
+
+/// Marks the current thread as replaying until the guard is dropped, even if an observer panics
+/// while a change is applied.
+struct ReplayGuard<'a> {
+    replaying: &'a RwLock<HashSet<ThreadId>>,
+}
+
+impl<'a> ReplayGuard<'a> {
+    fn new(replaying: &'a RwLock<HashSet<ThreadId>>) -> Self {
+        replaying.write().unwrap().insert(thread::current().id());
+        ReplayGuard { replaying }
+    }
+}
+
+impl Drop for ReplayGuard<'_> {
+    fn drop(&mut self) {
+        self.replaying.write().unwrap_or_else(PoisonError::into_inner).remove(&thread::current().id());
+    }
+}
@@ -9,25 +9,37 @@ use crate::PropertyType;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
 use crate::TypeIdType;
+use crate::TypeMigration;
 
 /// A component defines a set of properties to be applied to entity
 /// types and relation types.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Component {
     /// The type definition of the component.
     #[serde(flatten)]
     pub ty: ComponentTypeId,
 
+    /// The version of the component. The version is increased when the component changes.
+    #[serde(default)]
+    pub version: u32,
+
     /// Textual description of the component.
     #[serde(default = "String::new")]
     pub description: String,
 
     /// The properties which are applied on entity or relation instances.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub properties: Vec<PropertyType>,
 
+    /// The migrations of the properties of the component. Entity types and relation types apply
+    /// them to their instances with
+    /// [`MigrationStep::MigrateComponent`](crate::MigrationStep::MigrateComponent).
+    #[serde(default)]
+    pub migrations: Vec<TypeMigration>,
+
     /// Component specific extensions
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub extensions: Vec<Extension>,
 }
 
@@ -35,8 +47,10 @@ impl Component {
     pub fn new<T: Into<ComponentTypeId>, S: Into<String>>(ty: T, description: S, properties: Vec<PropertyType>, extensions: Vec<Extension>) -> Component {
         Component {
             ty: ty.into(),
+            version: 0,
             description: description.into(),
             properties,
+            migrations: Vec::new(),
             extensions,
         }
     }
@@ -45,8 +59,10 @@ impl Component {
     pub fn new_from_type<S: Into<String>>(namespace: S, type_name: S, description: S, properties: Vec<PropertyType>, extensions: Vec<Extension>) -> Component {
         Component {
             ty: ComponentTypeId::new_from_type(namespace, type_name),
+            version: 0,
             description: description.into(),
             properties,
+            migrations: Vec::new(),
             extensions,
         }
     }
@@ -55,8 +71,10 @@ impl Component {
     pub fn new_without_extensions<T: Into<ComponentTypeId>, S: Into<String>>(ty: T, description: S, properties: Vec<PropertyType>) -> Component {
         Component {
             ty: ty.into(),
+            version: 0,
             description: description.into(),
             properties,
+            migrations: Vec::new(),
             extensions: Vec::new(),
         }
     }
@@ -65,8 +83,10 @@ impl Component {
     pub fn new_without_properties<T: Into<ComponentTypeId>, S: Into<String>>(ty: T, description: S, extensions: Vec<Extension>) -> Component {
         Component {
             ty: ty.into(),
+            version: 0,
             description: description.into(),
             properties: Vec::new(),
+            migrations: Vec::new(),
             extensions,
         }
     }
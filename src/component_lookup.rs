@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+
+use crate::Component;
+use crate::ComponentTypeId;
+
+/// Provides access to components by their type.
+pub trait ComponentLookup {
+    /// Returns the component with the given type.
+    fn get_component(&self, ty: &ComponentTypeId) -> Option<Component>;
+}
+
+impl ComponentLookup for [Component] {
+    fn get_component(&self, ty: &ComponentTypeId) -> Option<Component> {
+        self.iter().find(|component| &component.ty == ty).cloned()
+    }
+}
+
+impl ComponentLookup for Vec<Component> {
+    fn get_component(&self, ty: &ComponentTypeId) -> Option<Component> {
+        self.as_slice().get_component(ty)
+    }
+}
+
+impl ComponentLookup for HashMap<ComponentTypeId, Component> {
+    fn get_component(&self, ty: &ComponentTypeId) -> Option<Component> {
+        self.get(ty).cloned()
+    }
+}
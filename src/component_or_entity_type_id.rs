@@ -5,12 +5,15 @@ use std::fmt::Formatter;
 
 use crate::ComponentTypeId;
 use crate::EntityTypeId;
+use crate::ModelError;
 use crate::NamespacedType;
 use crate::NamespacedTypeGetter;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
+use crate::TypeIdType;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ComponentOrEntityTypeId {
     #[serde(rename = "component")]
     Component(ComponentTypeId),
@@ -49,12 +52,15 @@ impl From<ComponentTypeId> for ComponentOrEntityTypeId {
 }
 
 impl TryFrom<ComponentOrEntityTypeId> for ComponentTypeId {
-    type Error = ();
+    type Error = ModelError;
 
     fn try_from(ty: ComponentOrEntityTypeId) -> Result<Self, Self::Error> {
         match ty {
             ComponentOrEntityTypeId::Component(ty) => Ok(ty),
-            ComponentOrEntityTypeId::EntityType(_) => Err(()),
+            ComponentOrEntityTypeId::EntityType(_) => Err(ModelError::TypeIdTypeMismatch {
+                expected: TypeIdType::Component,
+                actual: TypeIdType::EntityType,
+            }),
         }
     }
 }
@@ -66,11 +72,14 @@ impl From<EntityTypeId> for ComponentOrEntityTypeId {
 }
 
 impl TryFrom<ComponentOrEntityTypeId> for EntityTypeId {
-    type Error = ();
+    type Error = ModelError;
 
     fn try_from(ty: ComponentOrEntityTypeId) -> Result<Self, Self::Error> {
         match ty {
-            ComponentOrEntityTypeId::Component(_) => Err(()),
+            ComponentOrEntityTypeId::Component(_) => Err(ModelError::TypeIdTypeMismatch {
+                expected: TypeIdType::EntityType,
+                actual: TypeIdType::Component,
+            }),
             ComponentOrEntityTypeId::EntityType(ty) => Ok(ty),
         }
     }
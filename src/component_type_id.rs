@@ -5,6 +5,7 @@ use indradb::Identifier;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::ModelError;
 use crate::NamespacedType;
 use crate::NamespacedTypeGetter;
 use crate::TypeDefinition;
@@ -13,6 +14,7 @@ use crate::TypeIdType;
 use crate::TYPE_ID_TYPE_SEPARATOR;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ComponentTypeId(NamespacedType);
 
 impl ComponentTypeId {
@@ -66,62 +68,51 @@ impl From<NamespacedType> for ComponentTypeId {
 }
 
 impl TryFrom<&TypeDefinition> for ComponentTypeId {
-    type Error = ();
+    type Error = ModelError;
 
     fn try_from(type_definition: &TypeDefinition) -> Result<Self, Self::Error> {
         match type_definition.type_id_type {
             TypeIdType::Component => Ok(ComponentTypeId::new_from_type(type_definition.namespace.clone(), type_definition.type_name.clone())),
-            _ => Err(()),
+            _ => Err(ModelError::TypeIdTypeMismatch {
+                expected: TypeIdType::Component,
+                actual: type_definition.type_id_type,
+            }),
         }
     }
 }
 
 impl TryFrom<&Identifier> for ComponentTypeId {
-    type Error = ();
+    type Error = ModelError;
 
     fn try_from(t: &Identifier) -> Result<Self, Self::Error> {
-        let s = t.to_string();
-        let mut s = s.split(&TYPE_ID_TYPE_SEPARATOR);
-        let type_type = s.next().ok_or(())?.try_into()?;
-        if TypeIdType::Component == type_type {
-            let namespace = s.next().ok_or(())?;
-            if namespace.is_empty() {
-                return Err(());
-            }
-            let type_name = s.next().ok_or(())?;
-            if type_name.is_empty() {
-                return Err(());
-            }
-            if s.next().is_some() {
-                return Err(());
-            }
-            return Ok(ComponentTypeId(NamespacedType::new(namespace, type_name)));
-        }
-        Err(())
+        ComponentTypeId::try_from(&t.to_string())
     }
 }
 
 impl TryFrom<&String> for ComponentTypeId {
-    type Error = ();
-
-    fn try_from(s: &String) -> Result<Self, Self::Error> {
-        let mut s = s.split(&TYPE_ID_TYPE_SEPARATOR);
-        let type_type = s.next().ok_or(())?.try_into()?;
-        if TypeIdType::Component == type_type {
-            let namespace = s.next().ok_or(())?;
-            if namespace.is_empty() {
-                return Err(());
-            }
-            let type_name = s.next().ok_or(())?;
-            if type_name.is_empty() {
-                return Err(());
-            }
-            if s.next().is_some() {
-                return Err(());
-            }
-            return Ok(ComponentTypeId(NamespacedType::new(namespace, type_name)));
+    type Error = ModelError;
+
+    fn try_from(type_id: &String) -> Result<Self, Self::Error> {
+        let mut s = type_id.split(&TYPE_ID_TYPE_SEPARATOR);
+        let type_type: TypeIdType = s.next().unwrap_or_default().try_into()?;
+        if TypeIdType::Component != type_type {
+            return Err(ModelError::TypeIdTypeMismatch {
+                expected: TypeIdType::Component,
+                actual: type_type,
+            });
+        }
+        let namespace = s.next().ok_or_else(|| ModelError::MissingSeparator(type_id.clone()))?;
+        if namespace.is_empty() {
+            return Err(ModelError::EmptyNamespace(type_id.clone()));
+        }
+        let type_name = s.next().ok_or_else(|| ModelError::MissingSeparator(type_id.clone()))?;
+        if type_name.is_empty() {
+            return Err(ModelError::EmptyTypeName(type_id.clone()));
+        }
+        if s.next().is_some() {
+            return Err(ModelError::TrailingSegment(type_id.clone()));
         }
-        Err(())
+        Ok(ComponentTypeId(NamespacedType::new(namespace, type_name)))
     }
 }
 
@@ -3,13 +3,22 @@ use fmt::Display;
 use std::collections::HashMap;
 use std::fmt::Formatter;
 
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::DateTime;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::json;
 use serde_json::Value;
+use uuid::Uuid;
 
 /// Derived from serde_json::Value but without value payload.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Copy, Eq)]
+///
+/// In addition to the JSON types there are data types which are stored as JSON values but
+/// which are restricted to a subset of the values (for example UUIDs which are stored as
+/// strings).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum DataType {
     /// Represents a JSON null value.
@@ -32,6 +41,24 @@ pub enum DataType {
 
     /// Represents any type (relations).
     Any,
+
+    /// Represents a JSON number which is an integer.
+    Integer,
+
+    /// Represents a JSON number which is a floating point number.
+    Float,
+
+    /// Represents a UUID which is stored as JSON string.
+    Uuid,
+
+    /// Represents a date time which is stored as JSON string in RFC 3339 format.
+    DateTime,
+
+    /// Represents a byte array which is stored as base64 encoded JSON string.
+    Bytes,
+
+    /// Represents a JSON string which is one of the given values.
+    Enum(Vec<String>),
 }
 
 impl DataType {
@@ -47,6 +74,16 @@ impl DataType {
         DataType::String
     }
 
+    pub fn integer() -> Self {
+        DataType::Integer
+    }
+
+    pub fn float() -> Self {
+        DataType::Float
+    }
+
+    /// Returns the default value of the data type. Enums without values have no valid value, so
+    /// their default value is `null`.
     pub fn default_value(&self) -> Value {
         match self {
             DataType::Bool => json!(false),
@@ -54,20 +91,65 @@ impl DataType {
             DataType::String => json!(""),
             DataType::Array => json!(Vec::<Value>::new()),
             DataType::Object => json!(HashMap::<String, Value>::new()),
+            DataType::Integer => json!(0),
+            DataType::Float => json!(0.0),
+            DataType::Uuid => json!(Uuid::nil()),
+            DataType::DateTime => json!("1970-01-01T00:00:00Z"),
+            DataType::Bytes => json!(""),
+            DataType::Enum(values) => values.first().map(|value| json!(value)).unwrap_or(Value::Null),
             _ => json!(""),
         }
     }
+
+    /// Returns true, if the given value is compatible with the data type.
+    pub fn is_valid_value(&self, value: &Value) -> bool {
+        match self {
+            DataType::Null => value.is_null(),
+            DataType::Bool => value.is_boolean(),
+            DataType::Number => value.is_number(),
+            DataType::String => value.is_string(),
+            DataType::Array => value.is_array(),
+            DataType::Object => value.is_object(),
+            DataType::Any => true,
+            DataType::Integer => value.is_i64() || value.is_u64(),
+            DataType::Float => value.is_number(),
+            DataType::Uuid => value.as_str().map(|v| Uuid::parse_str(v).is_ok()).unwrap_or(false),
+            DataType::DateTime => value.as_str().map(|v| DateTime::parse_from_rfc3339(v).is_ok()).unwrap_or(false),
+            DataType::Bytes => value.as_str().map(|v| STANDARD.decode(v).is_ok()).unwrap_or(false),
+            DataType::Enum(values) => value.as_str().map(|v| values.iter().any(|value| value == v)).unwrap_or(false),
+        }
+    }
 }
 
+/// Parses the string form of a data type, see [`Display`]. Enums are parsed from `enum` followed
+/// by the JSON array of their values in parentheses, e.g. `Enum(["on", "off"])`. Unknown data
+/// types are strings.
 impl From<&str> for DataType {
     fn from(value: &str) -> Self {
+        if let Some(values) = value
+            .get(..5)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("enum("))
+            .and_then(|_| value[5..].strip_suffix(')'))
+        {
+            return match serde_json::from_str(values) {
+                Ok(values) => Self::Enum(values),
+                Err(_) => Self::String,
+            };
+        }
         return match value.to_lowercase().as_str() {
+            "null" => Self::Null,
             "bool" => Self::Bool,
             "number" => Self::Number,
             "string" => Self::String,
             "array" => Self::Array,
             "object" => Self::Object,
             "any" => Self::Any,
+            "integer" => Self::Integer,
+            "float" => Self::Float,
+            "uuid" => Self::Uuid,
+            "datetime" => Self::DateTime,
+            "bytes" => Self::Bytes,
+            "enum" => Self::Enum(Vec::new()),
             _ => Self::String,
         };
     }
@@ -0,0 +1,181 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ComponentLookup;
+use crate::ComponentTypeId;
+use crate::DataType;
+use crate::PropertyType;
+use crate::SocketType;
+
+/// The origin of an effective property.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PropertyOrigin {
+    /// The property is declared by the type itself.
+    Own,
+
+    /// The property is declared by the component with the given type.
+    Component(ComponentTypeId),
+}
+
+impl Display for PropertyOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyOrigin::Own => write!(f, "the type itself"),
+            PropertyOrigin::Component(ty) => write!(f, "component {}", ty),
+        }
+    }
+}
+
+/// A property of a type, either declared by the type itself or by one of its components.
+#[derive(Clone, Debug)]
+pub struct EffectiveProperty {
+    /// The definition of the property.
+    pub property_type: PropertyType,
+
+    /// The origin of the property.
+    pub origin: PropertyOrigin,
+}
+
+/// The declaration of a property by a type or by one of its components.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertyDeclaration {
+    /// The type itself or the component which declares the property.
+    pub origin: PropertyOrigin,
+
+    /// The data type of the declared property.
+    pub data_type: DataType,
+
+    /// The socket type of the declared property.
+    pub socket_type: SocketType,
+}
+
+/// A property is declared with different data types or socket types, either by the type itself
+/// and one of its components or by multiple components.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertyConflict {
+    /// The name of the property.
+    pub property_name: String,
+
+    /// The declaration which has been resolved first. This declaration is part of the effective
+    /// properties.
+    pub first: PropertyDeclaration,
+
+    /// The conflicting declaration.
+    pub second: PropertyDeclaration,
+}
+
+impl Display for PropertyConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The property {} is declared as {} {} by {} and as {} {} by {}",
+            self.property_name,
+            self.first.socket_type,
+            self.first.data_type,
+            self.first.origin,
+            self.second.socket_type,
+            self.second.data_type,
+            self.second.origin
+        )
+    }
+}
+
+/// The effective properties of a type: the own properties of the type and the properties of its
+/// components.
+#[derive(Clone, Debug, Default)]
+pub struct EffectiveProperties {
+    /// The resolved properties. Own properties come first, followed by the properties of the
+    /// components in the order of the components.
+    pub properties: Vec<EffectiveProperty>,
+
+    /// The components which cannot be found.
+    pub missing_components: Vec<ComponentTypeId>,
+
+    /// The properties which are declared differently by the type and its components or by
+    /// multiple components.
+    pub conflicts: Vec<PropertyConflict>,
+}
+
+impl EffectiveProperties {
+    /// Returns true, if all components have been found and no conflicts exist.
+    pub fn is_ok(&self) -> bool {
+        self.missing_components.is_empty() && self.conflicts.is_empty()
+    }
+
+    /// Returns true, if an effective property with the given name exists.
+    pub fn has_property<S: Into<String>>(&self, property_name: S) -> bool {
+        let property_name = property_name.into();
+        self.properties.iter().any(|p| p.property_type.name == property_name)
+    }
+
+    /// Returns the effective property with the given name.
+    pub fn get_property<S: Into<String>>(&self, property_name: S) -> Option<&EffectiveProperty> {
+        let property_name = property_name.into();
+        self.properties.iter().find(|p| p.property_type.name == property_name)
+    }
+
+    /// Returns the definitions of the effective properties.
+    pub fn property_types(&self) -> Vec<PropertyType> {
+        self.properties.iter().map(|p| p.property_type.clone()).collect()
+    }
+}
+
+/// Resolves the effective properties of a type from its own properties and the properties of
+/// its components.
+///
+/// Own properties win over the properties of the components. If multiple components declare
+/// the same property, the declaration of the first component wins. Declarations which differ
+/// from the winning declaration in data type or socket type are reported as conflicts.
+pub fn resolve_effective_properties<L: ComponentLookup + ?Sized>(
+    own_properties: &[PropertyType],
+    components: &[ComponentTypeId],
+    component_lookup: &L,
+) -> EffectiveProperties {
+    let mut effective_properties = EffectiveProperties {
+        properties: own_properties
+            .iter()
+            .map(|property_type| EffectiveProperty {
+                property_type: property_type.clone(),
+                origin: PropertyOrigin::Own,
+            })
+            .collect(),
+        ..Default::default()
+    };
+    for ty in components {
+        let Some(component) = component_lookup.get_component(ty) else {
+            effective_properties.missing_components.push(ty.clone());
+            continue;
+        };
+        for property_type in component.properties {
+            match effective_properties.get_property(&property_type.name) {
+                Some(EffectiveProperty {
+                    property_type: existing,
+                    origin,
+                }) => {
+                    if existing.data_type != property_type.data_type || existing.socket_type != property_type.socket_type {
+                        let conflict = PropertyConflict {
+                            property_name: property_type.name.clone(),
+                            first: PropertyDeclaration {
+                                origin: origin.clone(),
+                                data_type: existing.data_type.clone(),
+                                socket_type: existing.socket_type,
+                            },
+                            second: PropertyDeclaration {
+                                origin: PropertyOrigin::Component(ty.clone()),
+                                data_type: property_type.data_type,
+                                socket_type: property_type.socket_type,
+                            },
+                        };
+                        effective_properties.conflicts.push(conflict);
+                    }
+                }
+                None => effective_properties.properties.push(EffectiveProperty {
+                    property_type,
+                    origin: PropertyOrigin::Component(ty.clone()),
+                }),
+            }
+        }
+    }
+    effective_properties
+}
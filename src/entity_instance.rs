@@ -2,22 +2,36 @@ use std::collections::HashMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use indradb::Vertex;
 use indradb::VertexProperties;
 use serde::Deserialize;
 use serde::Serialize;
+use serde_json::json;
 use serde_json::Map;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::reserved_property::named_properties;
+use crate::reserved_property::split_named_properties;
+use crate::type_migration::migrate_properties;
+use crate::validation::validate_properties;
+use crate::ComponentLookup;
+use crate::EntityType;
 use crate::EntityTypeId;
 use crate::Extension;
 use crate::ExtensionContainer;
 use crate::ExtensionTypeId;
+use crate::MigrationError;
+use crate::ModelError;
 use crate::MutablePropertyInstanceSetter;
 use crate::NamespacedTypeGetter;
 use crate::PropertyInstanceGetter;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
+use crate::ValidationViolation;
+use crate::DESCRIPTION_PROPERTY;
+use crate::EXTENSIONS_PROPERTY;
+use crate::VERSION_PROPERTY;
 
 /// Entity instances represents an typed object which contains properties.
 ///
@@ -25,7 +39,8 @@ use crate::TypeDefinitionGetter;
 ///
 /// In contrast to the entity type the entity instance stores values in it's
 /// properties.
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct EntityInstance {
     /// The type definition of the entity type.
     #[serde(flatten)]
@@ -34,6 +49,12 @@ pub struct EntityInstance {
     /// The unique identifier of the entity instance.
     pub id: Uuid,
 
+    /// The version of the entity type which the properties of the entity instance conform to.
+    /// A missing version is version 0: the instance has been saved before the type had a version,
+    /// so all migrations of the type are applied to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+
     /// The description of the entity instance.
     #[serde(default = "String::new")]
     pub description: String,
@@ -44,11 +65,11 @@ pub struct EntityInstance {
     /// a representation of a JSON. Therefore the value can be boolean, number, string,
     /// array or an object. For more information about the data types please look at
     /// https://docs.serde.rs/serde_json/value/enum.Value.html
-    #[serde(default = "HashMap::new")]
+    #[serde(default)]
     pub properties: HashMap<String, Value>,
 
     /// Entity instance specific extensions.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub extensions: Vec<Extension>,
 }
 
@@ -58,6 +79,7 @@ impl EntityInstance {
         EntityInstance {
             ty: ty.into(),
             id,
+            version: None,
             description: String::new(),
             properties,
             extensions: Vec::new(),
@@ -69,6 +91,7 @@ impl EntityInstance {
         EntityInstance {
             ty: EntityTypeId::new_from_type(namespace, type_name),
             id,
+            version: None,
             description: String::new(),
             properties,
             extensions: Vec::new(),
@@ -80,30 +103,123 @@ impl EntityInstance {
         EntityInstance {
             ty: ty.into(),
             id,
+            version: None,
             description: String::new(),
             properties: HashMap::new(),
             extensions: Vec::new(),
         }
     }
+
+    /// Validates the entity instance against the given entity type.
+    ///
+    /// The components of the entity type are resolved using the given component lookup. Returns
+    /// all violations of the schema of the entity type. The values of immutable outputs are not
+    /// checked because they may have been computed by behaviours, see
+    /// [`validate_import`](Self::validate_import).
+    pub fn validate<L: ComponentLookup + ?Sized>(&self, entity_type: &EntityType, components: &L) -> Result<(), Vec<ValidationViolation>> {
+        self.validate_with(entity_type, components, false)
+    }
+
+    /// Validates the entity instance against the given entity type like [`validate`](Self::validate)
+    /// and additionally reports immutable outputs which contain another value than the default
+    /// value of their data type.
+    ///
+    /// Use this for instances which haven't been processed by behaviours yet, like imported
+    /// instances. The values of immutable outputs are computed by behaviours, so imports have to
+    /// contain the default values of immutable outputs.
+    pub fn validate_import<L: ComponentLookup + ?Sized>(&self, entity_type: &EntityType, components: &L) -> Result<(), Vec<ValidationViolation>> {
+        self.validate_with(entity_type, components, true)
+    }
+
+    fn validate_with<L: ComponentLookup + ?Sized>(
+        &self,
+        entity_type: &EntityType,
+        components: &L,
+        check_immutable_outputs: bool,
+    ) -> Result<(), Vec<ValidationViolation>> {
+        let mut violations = Vec::new();
+        if self.ty != entity_type.ty {
+            violations.push(ValidationViolation::TypeMismatch {
+                expected: entity_type.type_definition(),
+                actual: self.type_definition(),
+            });
+        }
+        violations.append(&mut validate_properties(
+            &entity_type.properties,
+            &entity_type.components,
+            components,
+            &self.properties,
+            check_immutable_outputs,
+        ));
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Migrates the properties of the entity instance from its version to the version of the given
+    /// entity type. The components of the entity type are resolved using the given component
+    /// lookup. The entity instance is only changed if all migrations succeed.
+    pub fn migrate<L: ComponentLookup + ?Sized>(&mut self, entity_type: &EntityType, components: &L) -> Result<(), MigrationError> {
+        if self.ty != entity_type.ty {
+            return Err(MigrationError::TypeMismatch {
+                expected: entity_type.type_definition(),
+                actual: self.type_definition(),
+            });
+        }
+        migrate_properties(
+            entity_type.version,
+            &entity_type.migrations,
+            &entity_type.properties,
+            &entity_type.components,
+            components,
+            self.version.unwrap_or_default(),
+            &mut self.properties,
+        )?;
+        self.version = Some(entity_type.version);
+        Ok(())
+    }
 }
 
 impl TryFrom<VertexProperties> for EntityInstance {
-    type Error = ();
+    type Error = ModelError;
 
     fn try_from(properties: VertexProperties) -> Result<Self, Self::Error> {
         let ty = EntityTypeId::try_from(&properties.vertex.t)?;
         let id = properties.vertex.id;
-        let properties: HashMap<String, Value> = properties.props.iter().map(|p| (p.name.to_string(), p.value.clone())).collect();
+        let (properties, mut reserved) = split_named_properties(properties.props)?;
         Ok(EntityInstance {
             ty,
             id,
-            description: String::new(),
+            version: reserved.take(VERSION_PROPERTY)?,
+            description: reserved.take(DESCRIPTION_PROPERTY)?.unwrap_or_default(),
             properties,
-            extensions: Vec::new(),
+            extensions: reserved.take(EXTENSIONS_PROPERTY)?.unwrap_or_default(),
         })
     }
 }
 
+impl From<&EntityInstance> for Vertex {
+    fn from(instance: &EntityInstance) -> Self {
+        Vertex::with_id(instance.id, instance.type_id())
+    }
+}
+
+impl From<&EntityInstance> for VertexProperties {
+    fn from(instance: &EntityInstance) -> Self {
+        let props = named_properties(
+            instance.properties.iter().map(|(name, value)| (name.clone(), value.clone())),
+            vec![
+                (VERSION_PROPERTY, instance.version.map(|version| json!(version))),
+                (DESCRIPTION_PROPERTY, (!instance.description.is_empty()).then(|| json!(instance.description))),
+                (EXTENSIONS_PROPERTY, (!instance.extensions.is_empty()).then(|| json!(instance.extensions))),
+            ],
+        );
+        VertexProperties::new(instance.into(), props)
+    }
+}
+
 impl PropertyInstanceGetter for EntityInstance {
     fn get<S: Into<String>>(&self, property_name: S) -> Option<Value> {
         self.properties.get(&property_name.into()).cloned()
@@ -2,7 +2,10 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::extension::Extension;
+use crate::resolve_effective_properties;
+use crate::ComponentLookup;
 use crate::ComponentTypeId;
+use crate::EffectiveProperties;
 use crate::EntityTypeId;
 use crate::ExtensionContainer;
 use crate::ExtensionTypeId;
@@ -12,28 +15,38 @@ use crate::TypeContainer;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
 use crate::TypeIdType;
+use crate::TypeMigration;
 
 /// Entity types defines the type of an entity instance.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct EntityType {
     /// The type definition contains the namespace and the type name.
     #[serde(flatten)]
     pub ty: EntityTypeId,
 
+    /// The version of the entity type. The version is increased when the entity type changes.
+    #[serde(default)]
+    pub version: u32,
+
     /// Textual description of the entity type.
     #[serde(default = "String::new")]
     pub description: String,
 
     /// The names of the components of the entity type.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub components: Vec<ComponentTypeId>,
 
     /// The properties which are defined by the entity type.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub properties: Vec<PropertyType>,
 
+    /// The migrations of instances which have been saved against older versions of the entity type.
+    #[serde(default)]
+    pub migrations: Vec<TypeMigration>,
+
     /// Entity type specific extensions.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub extensions: Vec<Extension>,
 }
 
@@ -48,9 +61,11 @@ impl EntityType {
     ) -> EntityType {
         EntityType {
             ty: ty.into(),
+            version: 0,
             description: description.into(),
             components,
             properties,
+            migrations: Vec::new(),
             extensions,
         }
     }
@@ -65,12 +80,20 @@ impl EntityType {
     ) -> EntityType {
         EntityType {
             ty: EntityTypeId::new_from_type(namespace, type_name),
+            version: 0,
             description: description.into(),
             components,
             properties,
+            migrations: Vec::new(),
             extensions,
         }
     }
+
+    /// Returns the effective properties of the entity type: the own properties and the properties of
+    /// the components which are resolved using the given component lookup.
+    pub fn effective_properties<L: ComponentLookup + ?Sized>(&self, components: &L) -> EffectiveProperties {
+        resolve_effective_properties(&self.properties, &self.components, components)
+    }
 }
 
 impl TypeContainer for EntityType {
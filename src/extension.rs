@@ -9,7 +9,8 @@ use crate::TypeDefinitionGetter;
 use crate::TypeIdType;
 
 /// Extension on a type. The extension allows to extend information
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Extension {
     /// The type definition contains the namespace and the type name.
     #[serde(flatten)]
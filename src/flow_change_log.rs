@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::RwLock;
+
+use indradb::EdgeKey;
+use uuid::Uuid;
+
+/// A structural change of a reactive flow instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FlowChangeEvent {
+    /// The entity instance with the given id has been added to the flow.
+    EntityAdded(Uuid),
+
+    /// The entity instance with the given id has been removed from the flow.
+    EntityRemoved(Uuid),
+
+    /// The relation instance with the given edge key has been added to the flow.
+    RelationAdded(EdgeKey),
+
+    /// The relation instance with the given edge key has been removed from the flow.
+    RelationRemoved(EdgeKey),
+}
+
+/// Identifies the instance which is affected by an event.
+#[derive(PartialEq, Eq, Hash)]
+enum FlowChangeTarget {
+    Entity(Uuid),
+    Relation(EdgeKey),
+}
+
+impl FlowChangeEvent {
+    fn target(&self) -> FlowChangeTarget {
+        match self {
+            FlowChangeEvent::EntityAdded(id) | FlowChangeEvent::EntityRemoved(id) => FlowChangeTarget::Entity(*id),
+            FlowChangeEvent::RelationAdded(edge_key) | FlowChangeEvent::RelationRemoved(edge_key) => FlowChangeTarget::Relation(edge_key.clone()),
+        }
+    }
+}
+
+/// An event of the change log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlowChange {
+    /// The sequence number of the event. The first event has the sequence number 1.
+    pub sequence: u64,
+
+    /// The change.
+    pub event: FlowChangeEvent,
+}
+
+/// The requested changes are not available.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FlowChangeLogError {
+    /// The events after the given cursor have already been drained or dropped because of the
+    /// limit of the change log. The consumer has to resynchronize the whole flow.
+    CursorExpired { cursor: u64, discarded_until: u64 },
+    /// The given cursor is ahead of the latest event, for example because it belongs to another
+    /// change log. The consumer has to resynchronize the whole flow.
+    CursorAhead { cursor: u64, sequence: u64 },
+}
+
+impl Display for FlowChangeLogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowChangeLogError::CursorExpired { cursor, discarded_until } => {
+                write!(
+                    f,
+                    "The changes after {} are not available anymore. The changes until {} have been discarded",
+                    cursor, discarded_until
+                )
+            }
+            FlowChangeLogError::CursorAhead { cursor, sequence } => {
+                write!(f, "The cursor {} is ahead of the latest change {}", cursor, sequence)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct FlowChangeLogState {
+    /// The sequence number of the latest event.
+    sequence: u64,
+
+    /// The retained events ordered by their sequence number.
+    changes: VecDeque<FlowChange>,
+
+    /// The sequence number of the latest event which has been discarded.
+    discarded_until: u64,
+}
+
+/// An ordered log of the structural changes of a reactive flow instance.
+///
+/// Consumers keep a cursor, which is the sequence number of the latest event they have
+/// processed, and fetch the events after the cursor. A cursor of zero means from the beginning.
+///
+/// Compaction keeps only the latest event of each instance. The compacted log has the same net
+/// effect as long as the consumer applies the events idempotently, i.e. adding an existing
+/// instance updates it and removing a missing instance does nothing.
+#[derive(Default)]
+pub struct FlowChangeLog {
+    /// The maximum number of retained events. Zero means unlimited.
+    limit: usize,
+
+    state: RwLock<FlowChangeLogState>,
+}
+
+impl FlowChangeLog {
+    pub fn new() -> FlowChangeLog {
+        FlowChangeLog::default()
+    }
+
+    /// Creates a change log which retains at most the given number of events. If the limit is
+    /// exceeded, the log is compacted. If this is not sufficient, the oldest events are
+    /// discarded.
+    pub fn with_limit(limit: usize) -> FlowChangeLog {
+        FlowChangeLog { limit, ..Default::default() }
+    }
+
+    /// Appends the given event and returns its sequence number.
+    pub fn record(&self, event: FlowChangeEvent) -> u64 {
+        let mut state = self.state.write().unwrap();
+        state.sequence += 1;
+        let sequence = state.sequence;
+        state.changes.push_back(FlowChange { sequence, event });
+        if self.limit > 0 && state.changes.len() > self.limit {
+            compact(&mut state.changes);
+            while state.changes.len() > self.limit {
+                if let Some(change) = state.changes.pop_front() {
+                    state.discarded_until = change.sequence;
+                }
+            }
+        }
+        sequence
+    }
+
+    /// Returns the sequence number of the latest event.
+    pub fn cursor(&self) -> u64 {
+        self.state.read().unwrap().sequence
+    }
+
+    /// Returns the number of retained events.
+    pub fn len(&self) -> usize {
+        self.state.read().unwrap().changes.len()
+    }
+
+    /// Returns true, if no events are retained.
+    pub fn is_empty(&self) -> bool {
+        self.state.read().unwrap().changes.is_empty()
+    }
+
+    /// Returns the events after the given cursor.
+    pub fn changes_since(&self, cursor: u64) -> Result<Vec<FlowChange>, FlowChangeLogError> {
+        let state = self.state.read().unwrap();
+        check_cursor(&state, cursor)?;
+        Ok(state.changes.iter().filter(|change| change.sequence > cursor).cloned().collect())
+    }
+
+    /// Returns the events after the given cursor and discards all events until the latest
+    /// returned event. Consumers with an older cursor can't fetch the discarded events anymore.
+    pub fn drain_since(&self, cursor: u64) -> Result<Vec<FlowChange>, FlowChangeLogError> {
+        let mut state = self.state.write().unwrap();
+        check_cursor(&state, cursor)?;
+        let changes: Vec<FlowChange> = state.changes.drain(..).filter(|change| change.sequence > cursor).collect();
+        state.discarded_until = state.sequence;
+        Ok(changes)
+    }
+
+    /// Keeps only the latest event of each instance.
+    pub fn compact(&self) {
+        compact(&mut self.state.write().unwrap().changes);
+    }
+
+    /// Discards all events. The sequence numbers are not reset.
+    pub fn clear(&self) {
+        let mut state = self.state.write().unwrap();
+        state.changes.clear();
+        state.discarded_until = state.sequence;
+    }
+}
+
+fn check_cursor(state: &FlowChangeLogState, cursor: u64) -> Result<(), FlowChangeLogError> {
+    if cursor < state.discarded_until {
+        return Err(FlowChangeLogError::CursorExpired {
+            cursor,
+            discarded_until: state.discarded_until,
+        });
+    }
+    if cursor > state.sequence {
+        return Err(FlowChangeLogError::CursorAhead {
+            cursor,
+            sequence: state.sequence,
+        });
+    }
+    Ok(())
+}
+
+fn compact(changes: &mut VecDeque<FlowChange>) {
+    let mut targets = HashSet::new();
+    let mut compacted: VecDeque<FlowChange> = changes.drain(..).rev().filter(|change| targets.insert(change.event.target())).collect();
+    compacted.make_contiguous().reverse();
+    *changes = compacted;
+}
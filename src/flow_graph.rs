@@ -0,0 +1,273 @@
+use std::fmt::Write;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::EntityTypeId;
+use crate::FlowInstance;
+use crate::PropertyInstanceGetter;
+use crate::ReactiveFlowInstance;
+use crate::RelationInstanceTypeId;
+
+/// An entity instance as node of a flow graph.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowGraphNode {
+    /// The id of the entity instance.
+    pub id: Uuid,
+
+    /// The type of the entity instance.
+    pub ty: EntityTypeId,
+
+    /// The selected properties of the entity instance.
+    pub properties: Vec<(String, Value)>,
+
+    /// True, if the entity instance is the wrapper entity instance of the flow.
+    pub wrapper: bool,
+}
+
+impl FlowGraphNode {
+    /// Returns the label of the node (the entity type and the selected properties).
+    pub fn label_lines(&self) -> Vec<String> {
+        let mut lines = vec![self.ty.to_string()];
+        for (property_name, value) in self.properties.iter() {
+            lines.push(format!("{} = {}", property_name, value));
+        }
+        lines
+    }
+}
+
+/// A relation instance as edge of a flow graph.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowGraphEdge {
+    /// The id of the outbound entity instance.
+    pub outbound_id: Uuid,
+
+    /// The type of the relation instance.
+    pub ty: RelationInstanceTypeId,
+
+    /// The id of the inbound entity instance.
+    pub inbound_id: Uuid,
+}
+
+/// The graph of a flow which can be exported into Graphviz DOT, Mermaid or GraphML.
+///
+/// Entity instances are the nodes of the graph and relation instances are the edges of the
+/// graph. The wrapper entity instance is the first node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowGraph {
+    /// The id of the flow.
+    pub id: Uuid,
+
+    /// The nodes of the graph.
+    pub nodes: Vec<FlowGraphNode>,
+
+    /// The edges of the graph.
+    pub edges: Vec<FlowGraphEdge>,
+}
+
+impl FlowGraph {
+    /// Creates the graph of the given flow instance. The nodes are labelled with the values of
+    /// the given properties.
+    pub fn from_flow_instance(flow_instance: &FlowInstance, property_names: &[&str]) -> FlowGraph {
+        let mut nodes: Vec<FlowGraphNode> = flow_instance
+            .entity_instances
+            .iter()
+            .map(|entity_instance| FlowGraphNode {
+                id: entity_instance.id,
+                ty: entity_instance.ty.clone(),
+                properties: select_properties(property_names, |property_name| entity_instance.get(property_name)),
+                wrapper: entity_instance.id == flow_instance.id,
+            })
+            .collect();
+        nodes.sort_by_key(|node| !node.wrapper);
+        let edges = flow_instance
+            .relation_instances
+            .iter()
+            .map(|relation_instance| FlowGraphEdge {
+                outbound_id: relation_instance.outbound_id,
+                ty: relation_instance.ty.clone(),
+                inbound_id: relation_instance.inbound_id,
+            })
+            .collect();
+        FlowGraph {
+            id: flow_instance.id,
+            nodes,
+            edges,
+        }
+    }
+
+    /// Creates the graph of the given reactive flow instance. The nodes are labelled with the
+    /// current values of the given properties.
+    ///
+    /// Because the entity instances and relation instances of a reactive flow instance are not
+    /// ordered, the nodes and edges are sorted by their ids.
+    pub fn from_reactive_flow_instance(reactive_flow_instance: &ReactiveFlowInstance, property_names: &[&str]) -> FlowGraph {
+        let mut nodes: Vec<FlowGraphNode> = reactive_flow_instance
+            .entity_instances
+            .read()
+            .unwrap()
+            .values()
+            .map(|entity_instance| FlowGraphNode {
+                id: entity_instance.id,
+                ty: entity_instance.ty.clone(),
+                properties: select_properties(property_names, |property_name| entity_instance.get(property_name)),
+                wrapper: entity_instance.id == reactive_flow_instance.id,
+            })
+            .collect();
+        nodes.sort_by_key(|node| (!node.wrapper, node.id));
+        let mut edges: Vec<FlowGraphEdge> = reactive_flow_instance
+            .relation_instances
+            .read()
+            .unwrap()
+            .values()
+            .map(|relation_instance| FlowGraphEdge {
+                outbound_id: relation_instance.outbound.id,
+                ty: relation_instance.ty.clone(),
+                inbound_id: relation_instance.inbound.id,
+            })
+            .collect();
+        edges.sort_by_key(|edge| (edge.outbound_id, edge.ty.to_string(), edge.inbound_id));
+        FlowGraph {
+            id: reactive_flow_instance.id,
+            nodes,
+            edges,
+        }
+    }
+
+    /// Renders the graph in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph \"{}\" {{", self.id);
+        let _ = writeln!(dot, "  node [shape=box];");
+        for node in self.nodes.iter() {
+            let label = node.label_lines().iter().map(|line| escape_dot(line)).collect::<Vec<String>>().join("\\n");
+            if node.wrapper {
+                let _ = writeln!(dot, "  \"{}\" [label=\"{}\", style=\"bold,filled\", fillcolor=\"lightblue\"];", node.id, label);
+            } else {
+                let _ = writeln!(dot, "  \"{}\" [label=\"{}\"];", node.id, label);
+            }
+        }
+        for edge in self.edges.iter() {
+            let _ = writeln!(dot, "  \"{}\" -> \"{}\" [label=\"{}\"];", edge.outbound_id, edge.inbound_id, escape_dot(&edge.ty.to_string()));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the graph as Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::new();
+        mermaid.push_str("flowchart LR\n");
+        for node in self.nodes.iter() {
+            let label = node
+                .label_lines()
+                .iter()
+                .map(|line| escape_mermaid(line))
+                .collect::<Vec<String>>()
+                .join("<br/>");
+            let _ = writeln!(mermaid, "  {}[\"{}\"]", mermaid_id(node.id), label);
+        }
+        for edge in self.edges.iter() {
+            let _ = writeln!(
+                mermaid,
+                "  {} -->|\"{}\"| {}",
+                mermaid_id(edge.outbound_id),
+                escape_mermaid(&edge.ty.to_string()),
+                mermaid_id(edge.inbound_id)
+            );
+        }
+        if let Some(wrapper) = self.nodes.iter().find(|node| node.wrapper) {
+            mermaid.push_str("  classDef wrapper fill:#add8e6,stroke-width:3px\n");
+            let _ = writeln!(mermaid, "  class {} wrapper", mermaid_id(wrapper.id));
+        }
+        mermaid
+    }
+
+    /// Renders the graph in the GraphML format.
+    pub fn to_graphml(&self) -> String {
+        let mut property_names: Vec<&String> = Vec::new();
+        for node in self.nodes.iter() {
+            for (property_name, _) in node.properties.iter() {
+                if !property_names.contains(&property_name) {
+                    property_names.push(property_name);
+                }
+            }
+        }
+        let mut graphml = String::new();
+        graphml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        graphml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        graphml.push_str("  <key id=\"type\" for=\"all\" attr.name=\"type\" attr.type=\"string\"/>\n");
+        graphml.push_str("  <key id=\"wrapper\" for=\"node\" attr.name=\"wrapper\" attr.type=\"boolean\">\n");
+        graphml.push_str("    <default>false</default>\n");
+        graphml.push_str("  </key>\n");
+        for (index, property_name) in property_names.iter().enumerate() {
+            let _ = writeln!(
+                graphml,
+                "  <key id=\"p{}\" for=\"node\" attr.name=\"{}\" attr.type=\"string\"/>",
+                index,
+                escape_xml(property_name)
+            );
+        }
+        let _ = writeln!(graphml, "  <graph id=\"{}\" edgedefault=\"directed\">", self.id);
+        for node in self.nodes.iter() {
+            let _ = writeln!(graphml, "    <node id=\"{}\">", node.id);
+            let _ = writeln!(graphml, "      <data key=\"type\">{}</data>", escape_xml(&node.ty.to_string()));
+            if node.wrapper {
+                graphml.push_str("      <data key=\"wrapper\">true</data>\n");
+            }
+            for (property_name, value) in node.properties.iter() {
+                if let Some(index) = property_names.iter().position(|p| *p == property_name) {
+                    let _ = writeln!(graphml, "      <data key=\"p{}\">{}</data>", index, escape_xml(&value.to_string()));
+                }
+            }
+            graphml.push_str("    </node>\n");
+        }
+        for edge in self.edges.iter() {
+            let _ = writeln!(graphml, "    <edge source=\"{}\" target=\"{}\">", edge.outbound_id, edge.inbound_id);
+            let _ = writeln!(graphml, "      <data key=\"type\">{}</data>", escape_xml(&edge.ty.to_string()));
+            graphml.push_str("    </edge>\n");
+        }
+        graphml.push_str("  </graph>\n");
+        graphml.push_str("</graphml>\n");
+        graphml
+    }
+}
+
+impl From<&FlowInstance> for FlowGraph {
+    fn from(flow_instance: &FlowInstance) -> Self {
+        FlowGraph::from_flow_instance(flow_instance, &[])
+    }
+}
+
+impl From<&ReactiveFlowInstance> for FlowGraph {
+    fn from(reactive_flow_instance: &ReactiveFlowInstance) -> Self {
+        FlowGraph::from_reactive_flow_instance(reactive_flow_instance, &[])
+    }
+}
+
+fn select_properties<F: Fn(&str) -> Option<Value>>(property_names: &[&str], get: F) -> Vec<(String, Value)> {
+    property_names
+        .iter()
+        .filter_map(|property_name| get(property_name).map(|value| (property_name.to_string(), value)))
+        .collect()
+}
+
+fn mermaid_id(id: Uuid) -> String {
+    format!("e{}", id.simple())
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn escape_mermaid(s: &str) -> String {
+    s.replace('"', "#quot;").replace('<', "#lt;").replace('>', "#gt;").replace('\n', "<br/>")
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
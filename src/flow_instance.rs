@@ -7,11 +7,13 @@ use uuid::Uuid;
 
 use crate::EntityInstance;
 use crate::EntityTypeId;
+use crate::MigrationError;
 use crate::NamespacedTypeGetter;
 use crate::ReactiveFlowInstance;
 use crate::RelationInstance;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
+use crate::TypeRegistry;
 
 #[derive(Debug)]
 pub struct FlowInstanceCreationError;
@@ -31,6 +33,7 @@ pub struct FlowInstanceCreationError;
 /// instances.
 ///
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct FlowInstance {
     /// The id of the flow corresponds to the id of the wrapper entity instance
     ///
@@ -56,13 +59,13 @@ pub struct FlowInstance {
     ///
     /// It can't have a default because the wrapper entity instance must be
     /// present in the list of entities.
-    #[serde(default = "Vec::new", alias = "entities")]
+    #[serde(default, alias = "entities")]
     pub entity_instances: Vec<EntityInstance>,
 
     /// The relation instances which are contained in this flow instance.
     ///
     /// By default, no relation instances are contained in this flow instance.
-    #[serde(default = "Vec::new", alias = "relations")]
+    #[serde(default, alias = "relations")]
     pub relation_instances: Vec<RelationInstance>,
 }
 
@@ -78,6 +81,40 @@ impl FlowInstance {
             relation_instances: Vec::new(),
         }
     }
+
+    /// Migrates the entity instances and the relation instances of the flow instance to the
+    /// versions of their types. The types are looked up in the given type registry. The flow
+    /// instance is only changed if all instances can be migrated.
+    pub fn migrate(&mut self, type_registry: &TypeRegistry) -> Result<(), MigrationError> {
+        let mut entity_instances = self.entity_instances.clone();
+        for entity_instance in entity_instances.iter_mut() {
+            let entity_type = type_registry.get_entity_type(&entity_instance.ty).ok_or_else(|| MigrationError::Instance {
+                instance: entity_instance.id.to_string(),
+                error: Box::new(MigrationError::UnknownType(entity_instance.type_definition())),
+            })?;
+            entity_instance.migrate(&entity_type, type_registry).map_err(|error| MigrationError::Instance {
+                instance: entity_instance.id.to_string(),
+                error: Box::new(error),
+            })?;
+        }
+        let mut relation_instances = self.relation_instances.clone();
+        for relation_instance in relation_instances.iter_mut() {
+            let ty = relation_instance.relation_type_id();
+            let relation_type = type_registry.get_relation_type(&ty).ok_or_else(|| MigrationError::Instance {
+                instance: relation_instance.to_string(),
+                error: Box::new(MigrationError::UnknownType(ty.type_definition())),
+            })?;
+            relation_instance
+                .migrate(&relation_type, type_registry)
+                .map_err(|error| MigrationError::Instance {
+                    instance: relation_instance.to_string(),
+                    error: Box::new(error),
+                })?;
+        }
+        self.entity_instances = entity_instances;
+        self.relation_instances = relation_instances;
+        Ok(())
+    }
 }
 
 impl From<EntityInstance> for FlowInstance {
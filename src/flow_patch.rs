@@ -0,0 +1,851 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use indradb::EdgeKey;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::EntityInstance;
+use crate::Extension;
+use crate::ExtensionTypeId;
+use crate::FlowInstance;
+use crate::Mutability::Mutable;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+use crate::ReactiveEntityInstance;
+use crate::ReactiveFlowInstance;
+use crate::ReactivePropertyContainer;
+use crate::ReactivePropertyInstance;
+use crate::ReactiveRelationInstance;
+use crate::RelationInstance;
+use crate::RelationInstanceTypeId;
+use crate::TypeDefinitionGetter;
+
+/// Identifies a relation instance by its outbound entity instance, its type and its inbound
+/// entity instance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RelationInstanceKey {
+    /// The id of the outbound entity instance.
+    pub outbound_id: Uuid,
+
+    /// The type of the relation instance.
+    #[serde(flatten)]
+    pub ty: RelationInstanceTypeId,
+
+    /// The id of the inbound entity instance.
+    pub inbound_id: Uuid,
+}
+
+impl RelationInstanceKey {
+    /// Returns the edge key of the relation instance.
+    pub fn get_key(&self) -> EdgeKey {
+        EdgeKey::new(self.outbound_id, self.ty.type_id(), self.inbound_id)
+    }
+}
+
+impl From<&RelationInstance> for RelationInstanceKey {
+    fn from(relation_instance: &RelationInstance) -> Self {
+        RelationInstanceKey {
+            outbound_id: relation_instance.outbound_id,
+            ty: relation_instance.ty.clone(),
+            inbound_id: relation_instance.inbound_id,
+        }
+    }
+}
+
+impl Display for RelationInstanceKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}--[{}]-->{}", self.outbound_id, self.ty, self.inbound_id)
+    }
+}
+
+/// A single operation of a flow patch.
+///
+/// Every operation contains the old value and the new value. This makes it possible to detect
+/// whether the operation is applicable and to invert the operation. A property, a description
+/// or an extension which doesn't exist is represented by `None`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum FlowPatchOperation {
+    /// Changes the name of the flow instance.
+    SetName { old_value: String, new_value: String },
+
+    /// Changes the description of the flow instance.
+    SetDescription { old_value: String, new_value: String },
+
+    /// Adds an entity instance to the flow instance.
+    AddEntityInstance { entity_instance: EntityInstance },
+
+    /// Removes an entity instance from the flow instance.
+    RemoveEntityInstance { entity_instance: EntityInstance },
+
+    /// Adds a relation instance to the flow instance.
+    AddRelationInstance { relation_instance: RelationInstance },
+
+    /// Removes a relation instance from the flow instance.
+    RemoveRelationInstance { relation_instance: RelationInstance },
+
+    /// Adds, changes or removes a property of an entity instance.
+    SetEntityProperty {
+        id: Uuid,
+        property_name: String,
+        old_value: Option<Value>,
+        new_value: Option<Value>,
+    },
+
+    /// Changes the description of an entity instance.
+    SetEntityDescription { id: Uuid, old_value: String, new_value: String },
+
+    /// Adds, changes or removes an extension of an entity instance.
+    SetEntityExtension {
+        id: Uuid,
+        extension_ty: ExtensionTypeId,
+        old_value: Option<Extension>,
+        new_value: Option<Extension>,
+    },
+
+    /// Adds, changes or removes a property of a relation instance.
+    SetRelationProperty {
+        key: RelationInstanceKey,
+        property_name: String,
+        old_value: Option<Value>,
+        new_value: Option<Value>,
+    },
+
+    /// Changes the description of a relation instance.
+    SetRelationDescription {
+        key: RelationInstanceKey,
+        old_value: String,
+        new_value: String,
+    },
+
+    /// Adds, changes or removes an extension of a relation instance.
+    SetRelationExtension {
+        key: RelationInstanceKey,
+        extension_ty: ExtensionTypeId,
+        old_value: Option<Extension>,
+        new_value: Option<Extension>,
+    },
+}
+
+/// The part of a flow instance which is modified by an operation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum PatchTarget {
+    Name,
+    Description,
+    Entity(Uuid),
+    EntityProperty(Uuid, String),
+    EntityDescription(Uuid),
+    EntityExtension(Uuid, ExtensionTypeId),
+    Relation(RelationInstanceKey),
+    RelationProperty(RelationInstanceKey, String),
+    RelationDescription(RelationInstanceKey),
+    RelationExtension(RelationInstanceKey, ExtensionTypeId),
+}
+
+impl FlowPatchOperation {
+    /// Returns the operation which reverts this operation.
+    pub fn invert(&self) -> FlowPatchOperation {
+        match self.clone() {
+            FlowPatchOperation::SetName { old_value, new_value } => FlowPatchOperation::SetName {
+                old_value: new_value,
+                new_value: old_value,
+            },
+            FlowPatchOperation::SetDescription { old_value, new_value } => FlowPatchOperation::SetDescription {
+                old_value: new_value,
+                new_value: old_value,
+            },
+            FlowPatchOperation::AddEntityInstance { entity_instance } => FlowPatchOperation::RemoveEntityInstance { entity_instance },
+            FlowPatchOperation::RemoveEntityInstance { entity_instance } => FlowPatchOperation::AddEntityInstance { entity_instance },
+            FlowPatchOperation::AddRelationInstance { relation_instance } => FlowPatchOperation::RemoveRelationInstance { relation_instance },
+            FlowPatchOperation::RemoveRelationInstance { relation_instance } => FlowPatchOperation::AddRelationInstance { relation_instance },
+            FlowPatchOperation::SetEntityProperty {
+                id,
+                property_name,
+                old_value,
+                new_value,
+            } => FlowPatchOperation::SetEntityProperty {
+                id,
+                property_name,
+                old_value: new_value,
+                new_value: old_value,
+            },
+            FlowPatchOperation::SetEntityDescription { id, old_value, new_value } => FlowPatchOperation::SetEntityDescription {
+                id,
+                old_value: new_value,
+                new_value: old_value,
+            },
+            FlowPatchOperation::SetEntityExtension {
+                id,
+                extension_ty,
+                old_value,
+                new_value,
+            } => FlowPatchOperation::SetEntityExtension {
+                id,
+                extension_ty,
+                old_value: new_value,
+                new_value: old_value,
+            },
+            FlowPatchOperation::SetRelationProperty {
+                key,
+                property_name,
+                old_value,
+                new_value,
+            } => FlowPatchOperation::SetRelationProperty {
+                key,
+                property_name,
+                old_value: new_value,
+                new_value: old_value,
+            },
+            FlowPatchOperation::SetRelationDescription { key, old_value, new_value } => FlowPatchOperation::SetRelationDescription {
+                key,
+                old_value: new_value,
+                new_value: old_value,
+            },
+            FlowPatchOperation::SetRelationExtension {
+                key,
+                extension_ty,
+                old_value,
+                new_value,
+            } => FlowPatchOperation::SetRelationExtension {
+                key,
+                extension_ty,
+                old_value: new_value,
+                new_value: old_value,
+            },
+        }
+    }
+
+    /// Returns true, if both operations can't be applied together.
+    ///
+    /// Two operations are conflicting if they modify the same part of the flow instance
+    /// differently, or if one operation removes an instance which is used by the other operation.
+    /// Removing a relation instance doesn't conflict with removing one of its entity instances,
+    /// because both operations agree on the removal of the relation instance.
+    pub fn conflicts_with(&self, other: &FlowPatchOperation) -> bool {
+        if self == other {
+            return false;
+        }
+        self.target() == other.target() || self.removes_dependency_of(other) || other.removes_dependency_of(self)
+    }
+
+    fn target(&self) -> PatchTarget {
+        match self {
+            FlowPatchOperation::SetName { .. } => PatchTarget::Name,
+            FlowPatchOperation::SetDescription { .. } => PatchTarget::Description,
+            FlowPatchOperation::AddEntityInstance { entity_instance } | FlowPatchOperation::RemoveEntityInstance { entity_instance } => {
+                PatchTarget::Entity(entity_instance.id)
+            }
+            FlowPatchOperation::AddRelationInstance { relation_instance } | FlowPatchOperation::RemoveRelationInstance { relation_instance } => {
+                PatchTarget::Relation(relation_instance.into())
+            }
+            FlowPatchOperation::SetEntityProperty { id, property_name, .. } => PatchTarget::EntityProperty(*id, property_name.clone()),
+            FlowPatchOperation::SetEntityDescription { id, .. } => PatchTarget::EntityDescription(*id),
+            FlowPatchOperation::SetEntityExtension { id, extension_ty, .. } => PatchTarget::EntityExtension(*id, extension_ty.clone()),
+            FlowPatchOperation::SetRelationProperty { key, property_name, .. } => PatchTarget::RelationProperty(key.clone(), property_name.clone()),
+            FlowPatchOperation::SetRelationDescription { key, .. } => PatchTarget::RelationDescription(key.clone()),
+            FlowPatchOperation::SetRelationExtension { key, extension_ty, .. } => PatchTarget::RelationExtension(key.clone(), extension_ty.clone()),
+        }
+    }
+
+    /// Returns the ids of the entity instances the operation depends on.
+    fn entity_ids(&self) -> Vec<Uuid> {
+        match self {
+            FlowPatchOperation::SetName { .. } | FlowPatchOperation::SetDescription { .. } => Vec::new(),
+            FlowPatchOperation::AddEntityInstance { entity_instance } | FlowPatchOperation::RemoveEntityInstance { entity_instance } => {
+                vec![entity_instance.id]
+            }
+            FlowPatchOperation::AddRelationInstance { relation_instance } | FlowPatchOperation::RemoveRelationInstance { relation_instance } => {
+                vec![relation_instance.outbound_id, relation_instance.inbound_id]
+            }
+            FlowPatchOperation::SetEntityProperty { id, .. }
+            | FlowPatchOperation::SetEntityDescription { id, .. }
+            | FlowPatchOperation::SetEntityExtension { id, .. } => {
+                vec![*id]
+            }
+            FlowPatchOperation::SetRelationProperty { key, .. }
+            | FlowPatchOperation::SetRelationDescription { key, .. }
+            | FlowPatchOperation::SetRelationExtension { key, .. } => vec![key.outbound_id, key.inbound_id],
+        }
+    }
+
+    /// Returns the key of the relation instance the operation depends on.
+    fn relation_key(&self) -> Option<RelationInstanceKey> {
+        match self {
+            FlowPatchOperation::AddRelationInstance { relation_instance } | FlowPatchOperation::RemoveRelationInstance { relation_instance } => {
+                Some(relation_instance.into())
+            }
+            FlowPatchOperation::SetRelationProperty { key, .. }
+            | FlowPatchOperation::SetRelationDescription { key, .. }
+            | FlowPatchOperation::SetRelationExtension { key, .. } => Some(key.clone()),
+            _ => None,
+        }
+    }
+
+    fn removes_dependency_of(&self, other: &FlowPatchOperation) -> bool {
+        match self {
+            FlowPatchOperation::RemoveEntityInstance { .. } if matches!(other, FlowPatchOperation::RemoveRelationInstance { .. }) => false,
+            FlowPatchOperation::RemoveEntityInstance { entity_instance } => other.entity_ids().contains(&entity_instance.id),
+            FlowPatchOperation::RemoveRelationInstance { relation_instance } => other.relation_key() == Some(relation_instance.into()),
+            _ => false,
+        }
+    }
+
+    fn apply(&self, flow_instance: &mut FlowInstance) -> Result<(), FlowPatchError> {
+        match self {
+            FlowPatchOperation::SetName { old_value, new_value } => {
+                check(self, &flow_instance.name, old_value)?;
+                flow_instance.name = new_value.clone();
+            }
+            FlowPatchOperation::SetDescription { old_value, new_value } => {
+                check(self, &flow_instance.description, old_value)?;
+                flow_instance.description = new_value.clone();
+            }
+            FlowPatchOperation::AddEntityInstance { entity_instance } => {
+                if flow_instance.entity_instances.iter().any(|e| e.id == entity_instance.id) {
+                    return Err(FlowPatchError::EntityInstanceAlreadyExists(entity_instance.id));
+                }
+                flow_instance.entity_instances.push(entity_instance.clone());
+            }
+            FlowPatchOperation::RemoveEntityInstance { entity_instance } => {
+                let index = flow_instance
+                    .entity_instances
+                    .iter()
+                    .position(|e| e.id == entity_instance.id)
+                    .ok_or(FlowPatchError::EntityInstanceNotFound(entity_instance.id))?;
+                check(self, &flow_instance.entity_instances[index], entity_instance)?;
+                flow_instance.entity_instances.remove(index);
+            }
+            FlowPatchOperation::AddRelationInstance { relation_instance } => {
+                let key = RelationInstanceKey::from(relation_instance);
+                if find_relation_instance(flow_instance, &key).is_some() {
+                    return Err(FlowPatchError::RelationInstanceAlreadyExists(key));
+                }
+                flow_instance.relation_instances.push(relation_instance.clone());
+            }
+            FlowPatchOperation::RemoveRelationInstance { relation_instance } => {
+                let key = RelationInstanceKey::from(relation_instance);
+                let index = find_relation_instance(flow_instance, &key).ok_or(FlowPatchError::RelationInstanceNotFound(key))?;
+                check(self, &flow_instance.relation_instances[index], relation_instance)?;
+                flow_instance.relation_instances.remove(index);
+            }
+            FlowPatchOperation::SetEntityProperty {
+                id,
+                property_name,
+                old_value,
+                new_value,
+            } => {
+                let entity_instance = get_entity_instance(flow_instance, *id)?;
+                check(self, &entity_instance.properties.get(property_name).cloned(), old_value)?;
+                set_property(&mut entity_instance.properties, property_name, new_value);
+            }
+            FlowPatchOperation::SetEntityDescription { id, old_value, new_value } => {
+                let entity_instance = get_entity_instance(flow_instance, *id)?;
+                check(self, &entity_instance.description, old_value)?;
+                entity_instance.description = new_value.clone();
+            }
+            FlowPatchOperation::SetEntityExtension {
+                id,
+                extension_ty,
+                old_value,
+                new_value,
+            } => {
+                let entity_instance = get_entity_instance(flow_instance, *id)?;
+                set_extension(self, &mut entity_instance.extensions, extension_ty, old_value, new_value)?;
+            }
+            FlowPatchOperation::SetRelationProperty {
+                key,
+                property_name,
+                old_value,
+                new_value,
+            } => {
+                let relation_instance = get_relation_instance(flow_instance, key)?;
+                check(self, &relation_instance.properties.get(property_name).cloned(), old_value)?;
+                set_property(&mut relation_instance.properties, property_name, new_value);
+            }
+            FlowPatchOperation::SetRelationDescription { key, old_value, new_value } => {
+                let relation_instance = get_relation_instance(flow_instance, key)?;
+                check(self, &relation_instance.description, old_value)?;
+                relation_instance.description = new_value.clone();
+            }
+            FlowPatchOperation::SetRelationExtension {
+                key,
+                extension_ty,
+                old_value,
+                new_value,
+            } => {
+                let relation_instance = get_relation_instance(flow_instance, key)?;
+                set_extension(self, &mut relation_instance.extensions, extension_ty, old_value, new_value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_reactive(&self, flow_instance: &ReactiveFlowInstance) -> Result<(), FlowPatchError> {
+        match self {
+            FlowPatchOperation::AddEntityInstance { entity_instance } => {
+                if flow_instance.has_entity_by_id(entity_instance.id) {
+                    return Err(FlowPatchError::EntityInstanceAlreadyExists(entity_instance.id));
+                }
+                flow_instance.add_entity(Arc::new(ReactiveEntityInstance::from(entity_instance.clone())));
+            }
+            FlowPatchOperation::RemoveEntityInstance { entity_instance } => {
+                let reactive_entity_instance = flow_instance
+                    .get_entity(entity_instance.id)
+                    .ok_or(FlowPatchError::EntityInstanceNotFound(entity_instance.id))?;
+                check(self, &reactive_entity_instance.ty, &entity_instance.ty)?;
+                check(self, &reactive_property_values(&reactive_entity_instance.properties), &entity_instance.properties)?;
+                flow_instance.remove_entity(entity_instance.id);
+            }
+            FlowPatchOperation::AddRelationInstance { relation_instance } => {
+                let key = RelationInstanceKey::from(relation_instance);
+                if flow_instance.has_relation_by_key(&key.get_key()) {
+                    return Err(FlowPatchError::RelationInstanceAlreadyExists(key));
+                }
+                let outbound = flow_instance
+                    .get_entity(key.outbound_id)
+                    .ok_or(FlowPatchError::EntityInstanceNotFound(key.outbound_id))?;
+                let inbound = flow_instance
+                    .get_entity(key.inbound_id)
+                    .ok_or(FlowPatchError::EntityInstanceNotFound(key.inbound_id))?;
+                flow_instance.add_relation(Arc::new(ReactiveRelationInstance::new_from_instance(outbound, inbound, relation_instance.clone())));
+            }
+            FlowPatchOperation::RemoveRelationInstance { relation_instance } => {
+                let key = RelationInstanceKey::from(relation_instance);
+                let reactive_relation_instance = flow_instance
+                    .get_relation(&key.get_key())
+                    .ok_or_else(|| FlowPatchError::RelationInstanceNotFound(key.clone()))?;
+                check(self, &reactive_property_values(&reactive_relation_instance.properties), &relation_instance.properties)?;
+                flow_instance.remove_relation(&key.get_key());
+            }
+            FlowPatchOperation::SetEntityProperty {
+                id,
+                property_name,
+                old_value,
+                new_value,
+            } => {
+                let entity_instance = flow_instance.get_entity(*id).ok_or(FlowPatchError::EntityInstanceNotFound(*id))?;
+                check(self, &entity_instance.get(property_name), old_value)?;
+                set_reactive_property(entity_instance.as_ref(), property_name, new_value);
+            }
+            FlowPatchOperation::SetRelationProperty {
+                key,
+                property_name,
+                old_value,
+                new_value,
+            } => {
+                let relation_instance = flow_instance
+                    .get_relation(&key.get_key())
+                    .ok_or_else(|| FlowPatchError::RelationInstanceNotFound(key.clone()))?;
+                check(self, &relation_instance.get(property_name), old_value)?;
+                set_reactive_property(relation_instance.as_ref(), property_name, new_value);
+            }
+            // The name, the descriptions and the extensions are not part of the reactive model
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn check<T: PartialEq>(operation: &FlowPatchOperation, actual: &T, expected: &T) -> Result<(), FlowPatchError> {
+    if actual != expected {
+        return Err(FlowPatchError::Conflict(Box::new(operation.clone())));
+    }
+    Ok(())
+}
+
+fn find_relation_instance(flow_instance: &FlowInstance, key: &RelationInstanceKey) -> Option<usize> {
+    flow_instance
+        .relation_instances
+        .iter()
+        .position(|r| r.outbound_id == key.outbound_id && r.ty == key.ty && r.inbound_id == key.inbound_id)
+}
+
+fn get_entity_instance(flow_instance: &mut FlowInstance, id: Uuid) -> Result<&mut EntityInstance, FlowPatchError> {
+    flow_instance
+        .entity_instances
+        .iter_mut()
+        .find(|e| e.id == id)
+        .ok_or(FlowPatchError::EntityInstanceNotFound(id))
+}
+
+fn get_relation_instance<'a>(flow_instance: &'a mut FlowInstance, key: &RelationInstanceKey) -> Result<&'a mut RelationInstance, FlowPatchError> {
+    match find_relation_instance(flow_instance, key) {
+        Some(index) => Ok(&mut flow_instance.relation_instances[index]),
+        None => Err(FlowPatchError::RelationInstanceNotFound(key.clone())),
+    }
+}
+
+fn set_property(properties: &mut HashMap<String, Value>, property_name: &str, value: &Option<Value>) {
+    match value {
+        Some(value) => {
+            properties.insert(property_name.to_string(), value.clone());
+        }
+        None => {
+            properties.remove(property_name);
+        }
+    }
+}
+
+fn reactive_property_values(properties: &DashMap<String, ReactivePropertyInstance>) -> HashMap<String, Value> {
+    properties
+        .iter()
+        .map(|property_instance| (property_instance.key().clone(), property_instance.get()))
+        .collect()
+}
+
+fn set_reactive_property<T: ReactivePropertyContainer + PropertyInstanceSetter>(instance: &T, property_name: &str, value: &Option<Value>) {
+    match value {
+        Some(value) if instance.has_property(property_name) => instance.set(property_name, value.clone()),
+        Some(value) => instance.add_property(property_name, Mutable, value.clone()),
+        None => instance.remove_property(property_name),
+    }
+}
+
+fn set_extension(
+    operation: &FlowPatchOperation,
+    extensions: &mut Vec<Extension>,
+    extension_ty: &ExtensionTypeId,
+    old_value: &Option<Extension>,
+    new_value: &Option<Extension>,
+) -> Result<(), FlowPatchError> {
+    let index = extensions.iter().position(|extension| &extension.ty == extension_ty);
+    check(operation, &index.map(|index| extensions[index].clone()), old_value)?;
+    match (index, new_value) {
+        (Some(index), Some(extension)) => extensions[index] = extension.clone(),
+        (Some(index), None) => {
+            extensions.remove(index);
+        }
+        (None, Some(extension)) => extensions.push(extension.clone()),
+        (None, None) => {}
+    }
+    Ok(())
+}
+
+/// A serializable list of operations which transforms one flow instance into another.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct FlowPatch {
+    /// The operations in the order they are applied.
+    pub operations: Vec<FlowPatchOperation>,
+}
+
+impl FlowPatch {
+    pub fn new(operations: Vec<FlowPatchOperation>) -> FlowPatch {
+        FlowPatch { operations }
+    }
+
+    /// Computes the patch which transforms the flow instance `from` into the flow instance `to`.
+    ///
+    /// Instances are matched by their id respectively by their outbound id, type and inbound id.
+    /// The order of the instances and the order of the properties are irrelevant. An entity
+    /// instance whose type has changed is removed and added again.
+    ///
+    /// The operations are ordered: flow attributes, removed relation instances, removed entity
+    /// instances, added entity instances, added relation instances, changed entity instances,
+    /// changed relation instances.
+    pub fn diff(from: &FlowInstance, to: &FlowInstance) -> FlowPatch {
+        let mut operations = Vec::new();
+        if from.name != to.name {
+            operations.push(FlowPatchOperation::SetName {
+                old_value: from.name.clone(),
+                new_value: to.name.clone(),
+            });
+        }
+        if from.description != to.description {
+            operations.push(FlowPatchOperation::SetDescription {
+                old_value: from.description.clone(),
+                new_value: to.description.clone(),
+            });
+        }
+
+        let from_entities: HashMap<Uuid, &EntityInstance> = from.entity_instances.iter().map(|e| (e.id, e)).collect();
+        let to_entities: HashMap<Uuid, &EntityInstance> = to.entity_instances.iter().map(|e| (e.id, e)).collect();
+        let from_relations: HashMap<RelationInstanceKey, &RelationInstance> = from.relation_instances.iter().map(|r| (r.into(), r)).collect();
+        let to_relations: HashMap<RelationInstanceKey, &RelationInstance> = to.relation_instances.iter().map(|r| (r.into(), r)).collect();
+
+        let mut entity_ids: Vec<Uuid> = from_entities
+            .keys()
+            .chain(to_entities.keys())
+            .copied()
+            .collect::<HashSet<Uuid>>()
+            .into_iter()
+            .collect();
+        entity_ids.sort();
+        let mut relation_keys: Vec<RelationInstanceKey> = from_relations
+            .keys()
+            .chain(to_relations.keys())
+            .cloned()
+            .collect::<HashSet<RelationInstanceKey>>()
+            .into_iter()
+            .collect();
+        relation_keys.sort_by_key(|key| (key.outbound_id, key.ty.to_string(), key.inbound_id));
+
+        // Entity instances whose type has changed are replaced
+        let replaced: HashSet<Uuid> = entity_ids
+            .iter()
+            .filter(|id| match (from_entities.get(id), to_entities.get(id)) {
+                (Some(from_entity), Some(to_entity)) => from_entity.ty != to_entity.ty,
+                _ => false,
+            })
+            .copied()
+            .collect();
+        let removed_entity = |id: &Uuid| from_entities.contains_key(id) && (!to_entities.contains_key(id) || replaced.contains(id));
+        let added_entity = |id: &Uuid| to_entities.contains_key(id) && (!from_entities.contains_key(id) || replaced.contains(id));
+        // Relation instances of replaced entity instances are replaced as well
+        let replaced_relation = |key: &RelationInstanceKey| replaced.contains(&key.outbound_id) || replaced.contains(&key.inbound_id);
+        let removed_relation = |key: &RelationInstanceKey| from_relations.contains_key(key) && (!to_relations.contains_key(key) || replaced_relation(key));
+        let added_relation = |key: &RelationInstanceKey| to_relations.contains_key(key) && (!from_relations.contains_key(key) || replaced_relation(key));
+
+        for key in relation_keys.iter().filter(|key| removed_relation(key)) {
+            operations.push(FlowPatchOperation::RemoveRelationInstance {
+                relation_instance: from_relations[key].clone(),
+            });
+        }
+        for id in entity_ids.iter().filter(|id| removed_entity(id)) {
+            operations.push(FlowPatchOperation::RemoveEntityInstance {
+                entity_instance: from_entities[id].clone(),
+            });
+        }
+        for id in entity_ids.iter().filter(|id| added_entity(id)) {
+            operations.push(FlowPatchOperation::AddEntityInstance {
+                entity_instance: to_entities[id].clone(),
+            });
+        }
+        for key in relation_keys.iter().filter(|key| added_relation(key)) {
+            operations.push(FlowPatchOperation::AddRelationInstance {
+                relation_instance: to_relations[key].clone(),
+            });
+        }
+
+        for id in entity_ids.iter().filter(|id| !removed_entity(id) && !added_entity(id)) {
+            let (from_entity, to_entity) = (from_entities[id], to_entities[id]);
+            for (property_name, old_value, new_value) in diff_properties(&from_entity.properties, &to_entity.properties) {
+                operations.push(FlowPatchOperation::SetEntityProperty {
+                    id: *id,
+                    property_name,
+                    old_value,
+                    new_value,
+                });
+            }
+            if from_entity.description != to_entity.description {
+                operations.push(FlowPatchOperation::SetEntityDescription {
+                    id: *id,
+                    old_value: from_entity.description.clone(),
+                    new_value: to_entity.description.clone(),
+                });
+            }
+            for (extension_ty, old_value, new_value) in diff_extensions(&from_entity.extensions, &to_entity.extensions) {
+                operations.push(FlowPatchOperation::SetEntityExtension {
+                    id: *id,
+                    extension_ty,
+                    old_value,
+                    new_value,
+                });
+            }
+        }
+
+        for key in relation_keys.iter().filter(|key| !removed_relation(key) && !added_relation(key)) {
+            let (from_relation, to_relation) = (from_relations[key], to_relations[key]);
+            for (property_name, old_value, new_value) in diff_properties(&from_relation.properties, &to_relation.properties) {
+                operations.push(FlowPatchOperation::SetRelationProperty {
+                    key: key.clone(),
+                    property_name,
+                    old_value,
+                    new_value,
+                });
+            }
+            if from_relation.description != to_relation.description {
+                operations.push(FlowPatchOperation::SetRelationDescription {
+                    key: key.clone(),
+                    old_value: from_relation.description.clone(),
+                    new_value: to_relation.description.clone(),
+                });
+            }
+            for (extension_ty, old_value, new_value) in diff_extensions(&from_relation.extensions, &to_relation.extensions) {
+                operations.push(FlowPatchOperation::SetRelationExtension {
+                    key: key.clone(),
+                    extension_ty,
+                    old_value,
+                    new_value,
+                });
+            }
+        }
+        FlowPatch { operations }
+    }
+
+    /// Merges the changes which have been made on `ours` and on `theirs` since the common
+    /// ancestor `base`.
+    ///
+    /// Returns the merged flow instance or the list of conflicting operations. Changes which have
+    /// been made on both sides identically are not conflicting.
+    pub fn merge(base: &FlowInstance, ours: &FlowInstance, theirs: &FlowInstance) -> Result<FlowInstance, FlowMergeError> {
+        let our_patch = FlowPatch::diff(base, ours);
+        let their_patch = FlowPatch::diff(base, theirs);
+        let mut conflicts = Vec::new();
+        for our_operation in our_patch.operations.iter() {
+            for their_operation in their_patch.operations.iter() {
+                if our_operation.conflicts_with(their_operation) {
+                    conflicts.push(FlowMergeConflict {
+                        ours: our_operation.clone(),
+                        theirs: their_operation.clone(),
+                    });
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(FlowMergeError::Conflicts(conflicts));
+        }
+        // Apply their operations which haven't been made on our side as well
+        let operations = their_patch
+            .operations
+            .into_iter()
+            .filter(|operation| !our_patch.operations.contains(operation))
+            .collect();
+        let mut merged = ours.clone();
+        FlowPatch::new(operations).apply(&mut merged).map_err(FlowMergeError::Patch)?;
+        Ok(merged)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Returns the patch which reverts this patch.
+    pub fn invert(&self) -> FlowPatch {
+        FlowPatch {
+            operations: self.operations.iter().rev().map(|operation| operation.invert()).collect(),
+        }
+    }
+
+    /// Applies the patch on the given flow instance.
+    ///
+    /// The patch is applied atomically: if an operation fails, the flow instance remains
+    /// unchanged. An operation fails if the instance it modifies doesn't exist or if the old
+    /// value doesn't match the current value.
+    pub fn apply(&self, flow_instance: &mut FlowInstance) -> Result<(), FlowPatchError> {
+        let mut patched = flow_instance.clone();
+        for operation in self.operations.iter() {
+            operation.apply(&mut patched)?;
+        }
+        *flow_instance = patched;
+        Ok(())
+    }
+
+    /// Applies the patch on the given reactive flow instance.
+    ///
+    /// The name, the description and the extensions are not part of the reactive model, so these
+    /// operations are skipped. The values of the properties are set with propagation. Properties
+    /// which don't exist yet are added as mutable properties. Instances are only removed if their
+    /// properties match the removed instances of the patch.
+    ///
+    /// In contrast to `apply`, the patch is not applied atomically: if an operation fails, the
+    /// preceding operations remain applied.
+    pub fn apply_reactive(&self, flow_instance: &ReactiveFlowInstance) -> Result<(), FlowPatchError> {
+        for operation in self.operations.iter() {
+            operation.apply_reactive(flow_instance)?;
+        }
+        Ok(())
+    }
+}
+
+fn diff_properties(from: &HashMap<String, Value>, to: &HashMap<String, Value>) -> Vec<(String, Option<Value>, Option<Value>)> {
+    let mut property_names: Vec<&String> = from.keys().chain(to.keys()).collect::<HashSet<&String>>().into_iter().collect();
+    property_names.sort();
+    property_names
+        .into_iter()
+        .filter(|property_name| from.get(*property_name) != to.get(*property_name))
+        .map(|property_name| (property_name.clone(), from.get(property_name).cloned(), to.get(property_name).cloned()))
+        .collect()
+}
+
+fn diff_extensions(from: &[Extension], to: &[Extension]) -> Vec<(ExtensionTypeId, Option<Extension>, Option<Extension>)> {
+    let mut extension_tys: Vec<&ExtensionTypeId> = Vec::new();
+    for extension in from.iter().chain(to.iter()) {
+        if !extension_tys.contains(&&extension.ty) {
+            extension_tys.push(&extension.ty);
+        }
+    }
+    extension_tys.sort_by_key(|ty| ty.to_string());
+    extension_tys
+        .into_iter()
+        .filter_map(|ty| {
+            let old_value = from.iter().find(|extension| &extension.ty == ty).cloned();
+            let new_value = to.iter().find(|extension| &extension.ty == ty).cloned();
+            if old_value == new_value {
+                return None;
+            }
+            Some((ty.clone(), old_value, new_value))
+        })
+        .collect()
+}
+
+impl FlowInstance {
+    /// Computes the patch which transforms this flow instance into the given flow instance.
+    pub fn diff(&self, other: &FlowInstance) -> FlowPatch {
+        FlowPatch::diff(self, other)
+    }
+}
+
+/// An operation made on our side which is conflicting with an operation made on their side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowMergeConflict {
+    pub ours: FlowPatchOperation,
+    pub theirs: FlowPatchOperation,
+}
+
+/// The patch cannot be applied.
+#[derive(Debug, PartialEq)]
+pub enum FlowPatchError {
+    /// The entity instance to add already exists.
+    EntityInstanceAlreadyExists(Uuid),
+
+    /// The entity instance doesn't exist.
+    EntityInstanceNotFound(Uuid),
+
+    /// The relation instance to add already exists.
+    RelationInstanceAlreadyExists(RelationInstanceKey),
+
+    /// The relation instance doesn't exist.
+    RelationInstanceNotFound(RelationInstanceKey),
+
+    /// The old value of the operation doesn't match the current value.
+    Conflict(Box<FlowPatchOperation>),
+}
+
+impl Display for FlowPatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowPatchError::EntityInstanceAlreadyExists(id) => write!(f, "The entity instance {} already exists", id),
+            FlowPatchError::EntityInstanceNotFound(id) => write!(f, "The entity instance {} doesn't exist", id),
+            FlowPatchError::RelationInstanceAlreadyExists(key) => write!(f, "The relation instance {} already exists", key),
+            FlowPatchError::RelationInstanceNotFound(key) => write!(f, "The relation instance {} doesn't exist", key),
+            FlowPatchError::Conflict(operation) => write!(f, "The operation {:?} doesn't match the current state", operation),
+        }
+    }
+}
+
+/// The flow instances cannot be merged.
+#[derive(Debug, PartialEq)]
+pub enum FlowMergeError {
+    /// Both sides have made conflicting changes.
+    Conflicts(Vec<FlowMergeConflict>),
+
+    /// The changes of their side cannot be applied on our side.
+    Patch(FlowPatchError),
+}
+
+impl Display for FlowMergeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowMergeError::Conflicts(conflicts) => write!(f, "The flow instances have {} conflicting changes", conflicts.len()),
+            FlowMergeError::Patch(error) => write!(f, "The changes cannot be merged: {}", error),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use indradb::EdgeKey;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::RelationInstanceTypeId;
+use crate::TypeDefinitionGetter;
+
+/// The property values of an entity instance at the time the snapshot has been taken.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EntityInstanceSnapshot {
+    /// The id of the entity instance.
+    pub id: Uuid,
+
+    /// The property values of the entity instance.
+    pub properties: Map<String, Value>,
+}
+
+/// The property values of a relation instance at the time the snapshot has been taken.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RelationInstanceSnapshot {
+    /// The id of the outbound entity instance.
+    pub outbound_id: Uuid,
+
+    /// The type of the relation instance.
+    #[serde(flatten)]
+    pub ty: RelationInstanceTypeId,
+
+    /// The id of the inbound entity instance.
+    pub inbound_id: Uuid,
+
+    /// The property values of the relation instance.
+    pub properties: Map<String, Value>,
+}
+
+impl RelationInstanceSnapshot {
+    /// Returns the edge key of the relation instance.
+    pub fn get_key(&self) -> EdgeKey {
+        EdgeKey::new(self.outbound_id, self.ty.type_id(), self.inbound_id)
+    }
+}
+
+/// A snapshot of the property values of all entity instances and relation instances of a
+/// reactive flow instance.
+///
+/// The snapshot contains the instances which were part of the flow when the snapshot was taken.
+/// The values of the properties are read one after another, so values which are set
+/// concurrently while the snapshot is taken may or may not be contained.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FlowSnapshot {
+    /// The id of the flow.
+    pub id: Uuid,
+
+    /// The entity instances of the flow, including the wrapper entity instance.
+    pub entity_instances: Vec<EntityInstanceSnapshot>,
+
+    /// The relation instances of the flow.
+    pub relation_instances: Vec<RelationInstanceSnapshot>,
+}
+
+impl FlowSnapshot {
+    /// Returns the snapshot of the entity instance with the given id.
+    pub fn get_entity_instance(&self, id: Uuid) -> Option<&EntityInstanceSnapshot> {
+        self.entity_instances.iter().find(|e| e.id == id)
+    }
+
+    /// Returns the snapshot of the relation instance with the given edge key.
+    pub fn get_relation_instance(&self, edge_key: &EdgeKey) -> Option<&RelationInstanceSnapshot> {
+        self.relation_instances.iter().find(|r| &r.get_key() == edge_key)
+    }
+}
+
+/// The snapshot cannot be restored.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FlowSnapshotRestoreError {
+    /// The snapshot has been taken from another flow.
+    FlowMismatch { expected: Uuid, actual: Uuid },
+}
+
+impl Display for FlowSnapshotRestoreError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowSnapshotRestoreError::FlowMismatch { expected, actual } => {
+                write!(f, "The snapshot of flow {} cannot be restored into flow {}", actual, expected)
+            }
+        }
+    }
+}
@@ -1,11 +1,19 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
 use serde::Deserialize;
 use serde::Serialize;
+use serde_json::Value;
 use uuid::Uuid;
 
+use crate::DataType;
 use crate::EntityInstance;
 use crate::EntityTypeId;
 use crate::Extension;
 use crate::ExtensionTypeId;
+use crate::FlowInstance;
 use crate::FlowTypeId;
 use crate::NamespacedTypeGetter;
 use crate::PropertyType;
@@ -18,7 +26,37 @@ use crate::TypeIdType;
 #[derive(Debug)]
 pub struct FlowTypeCreationError;
 
+/// Errors which can occur during the instantiation of a flow type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FlowTypeInstantiationError {
+    /// No value has been provided for the variable with the given name.
+    MissingVariable(String),
+
+    /// A value has been provided for a variable which is not declared by the flow type.
+    UnknownVariable(String),
+
+    /// The value of the variable doesn't match the data type of the variable.
+    InvalidVariableValue { variable_name: String, expected: DataType, value: Value },
+}
+
+impl Display for FlowTypeInstantiationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowTypeInstantiationError::MissingVariable(variable_name) => write!(f, "No value has been provided for variable {}", variable_name),
+            FlowTypeInstantiationError::UnknownVariable(variable_name) => write!(f, "The variable {} is not declared by the flow type", variable_name),
+            FlowTypeInstantiationError::InvalidVariableValue {
+                variable_name,
+                expected,
+                value,
+            } => {
+                write!(f, "The value {} of variable {} is not of data type {}", value, variable_name, expected)
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct FlowType {
     /// The type definition of the entity type.
     #[serde(flatten)]
@@ -34,23 +72,23 @@ pub struct FlowType {
     /// The entity instances which are contained in this flow.
     ///
     /// By default, no relation instances are contained in this flow type.
-    #[serde(default = "Vec::new", alias = "entities")]
+    #[serde(default, alias = "entities")]
     pub entity_instances: Vec<EntityInstance>,
 
     /// The relation instances which are contained in this flow.
     ///
     /// By default, no relation instances are contained in this flow type.
-    #[serde(default = "Vec::new", alias = "relations")]
+    #[serde(default, alias = "relations")]
     pub relation_instances: Vec<RelationInstance>,
 
     /// The variables. Variables will be replaced by instantiation of a flow instance.
     ///
     /// By default, the flow type has no variables.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub variables: Vec<PropertyType>,
 
     /// Flow type specific extensions.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub extensions: Vec<Extension>,
 }
 
@@ -174,6 +212,72 @@ impl FlowType {
         self.extensions.iter().any(|extension| &extension.ty == extension_ty)
     }
 
+    /// Creates a new flow instance from the flow type.
+    ///
+    /// Each entity instance (including the wrapper entity instance) gets a new id. The outbound
+    /// and inbound ids of the relation instances are remapped to the new ids. Relation instances
+    /// which are connected to entity instances outside of the flow type keep these ids.
+    ///
+    /// Placeholders of the form `{{variable_name}}` in the property values are replaced by the
+    /// values of the variables. If a string consists only of the placeholder, the string is
+    /// replaced by the value of the variable. Otherwise the placeholder is replaced by the
+    /// textual representation of the value. Placeholders of undeclared variables are kept.
+    pub fn instantiate(&self, variables: &HashMap<String, Value>) -> Result<FlowInstance, FlowTypeInstantiationError> {
+        for variable_name in variables.keys() {
+            if !self.has_variable(variable_name) {
+                return Err(FlowTypeInstantiationError::UnknownVariable(variable_name.clone()));
+            }
+        }
+        for variable in self.variables.iter() {
+            match variables.get(&variable.name) {
+                Some(value) => {
+                    if !variable.data_type.is_valid_value(value) {
+                        return Err(FlowTypeInstantiationError::InvalidVariableValue {
+                            variable_name: variable.name.clone(),
+                            expected: variable.data_type.clone(),
+                            value: value.clone(),
+                        });
+                    }
+                }
+                None => return Err(FlowTypeInstantiationError::MissingVariable(variable.name.clone())),
+            }
+        }
+
+        let mut ids: HashMap<Uuid, Uuid> = HashMap::new();
+        ids.insert(self.wrapper_entity_instance.id, Uuid::new_v4());
+        for entity_instance in self.entity_instances.iter() {
+            ids.entry(entity_instance.id).or_insert_with(Uuid::new_v4);
+        }
+        let remap = |id: Uuid| *ids.get(&id).unwrap_or(&id);
+
+        let instantiate_entity_instance = |entity_instance: &EntityInstance| {
+            let mut entity_instance = entity_instance.clone();
+            entity_instance.id = remap(entity_instance.id);
+            for value in entity_instance.properties.values_mut() {
+                *value = replace_variables(value, variables);
+            }
+            entity_instance
+        };
+
+        let mut flow_instance = FlowInstance::from(instantiate_entity_instance(&self.wrapper_entity_instance));
+        flow_instance.description = self.description.clone();
+        for entity_instance in self.entity_instances.iter() {
+            if entity_instance.id != self.wrapper_entity_instance.id {
+                flow_instance.entity_instances.push(instantiate_entity_instance(entity_instance));
+            }
+        }
+        for relation_instance in self.relation_instances.iter() {
+            let mut relation_instance = relation_instance.clone();
+            relation_instance.outbound_id = remap(relation_instance.outbound_id);
+            relation_instance.inbound_id = remap(relation_instance.inbound_id);
+            for value in relation_instance.properties.values_mut() {
+                *value = replace_variables(value, variables);
+            }
+            flow_instance.relation_instances.push(relation_instance);
+        }
+        Ok(flow_instance)
+    }
+
     /// Adds an extension to the flow type.
     pub fn add_extension(&mut self, extension: Extension) {
         self.extensions.push(extension)
@@ -185,6 +289,45 @@ impl FlowType {
     }
 }
 
+/// Replaces the placeholders in the given value (recursively) by the values of the variables.
+fn replace_variables(value: &Value, variables: &HashMap<String, Value>) -> Value {
+    match value {
+        Value::String(s) => {
+            if let Some(variable_name) = s.strip_prefix("{{").and_then(|s| s.strip_suffix("}}")) {
+                if let Some(variable_value) = variables.get(variable_name.trim()) {
+                    return variable_value.clone();
+                }
+            }
+            Value::String(replace_variables_in_string(s, variables))
+        }
+        Value::Array(values) => Value::Array(values.iter().map(|value| replace_variables(value, variables)).collect()),
+        Value::Object(values) => Value::Object(values.iter().map(|(name, value)| (name.clone(), replace_variables(value, variables))).collect()),
+        _ => value.clone(),
+    }
+}
+
+/// Replaces the placeholders in the given string by the textual representation of the values
+/// of the variables.
+fn replace_variables_in_string(s: &str, variables: &HashMap<String, Value>) -> String {
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let placeholder = &rest[start..start + end + 2];
+        result.push_str(&rest[..start]);
+        match variables.get(placeholder[2..placeholder.len() - 2].trim()) {
+            Some(Value::String(variable_value)) => result.push_str(variable_value),
+            Some(variable_value) => result.push_str(&variable_value.to_string()),
+            None => result.push_str(placeholder),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
 impl NamespacedTypeGetter for FlowType {
     fn namespace(&self) -> String {
         self.ty.namespace()
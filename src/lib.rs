@@ -3,12 +3,15 @@
 #![register_tool(tarpaulin)]
 
 pub use behaviour_type_id::*;
+pub use change_history::*;
 pub use component::*;
 pub use component_behaviour_type_id::*;
 pub use component_container::*;
+pub use component_lookup::*;
 pub use component_or_entity_type_id::*;
 pub use component_type_id::*;
 pub use data_type::*;
+pub use effective_property::*;
 pub use entity_behaviour_type_id::*;
 pub use entity_component_type_id::*;
 pub use entity_instance::*;
@@ -17,13 +20,22 @@ pub use entity_type_id::*;
 pub use extension::*;
 pub use extension_container::*;
 pub use extension_type_id::*;
+pub use flow_change_log::*;
+pub use flow_graph::*;
 pub use flow_instance::*;
+pub use flow_patch::*;
+pub use flow_snapshot::*;
 pub use flow_type::*;
 pub use flow_type_id::*;
+pub use model_error::*;
 pub use mutability::*;
+pub use observer_subscription::*;
+pub use propagation_mode::*;
 pub use property::*;
 pub use property_identifier::*;
 pub use property_instance_accessor::*;
+#[cfg(feature = "async")]
+pub use property_stream::*;
 pub use property_type::*;
 pub use reactive_behaviour_container::*;
 pub use reactive_entity_instance::*;
@@ -38,22 +50,40 @@ pub use relation_instance::*;
 pub use relation_instance_type_id::*;
 pub use relation_type::*;
 pub use relation_type_id::*;
+pub use reserved_property::*;
+pub use serialization_error::*;
+pub use serialization_format::*;
 pub use socket_type::*;
+pub use sync_stream::*;
 pub use type_container::*;
 pub use type_definition::*;
 pub use type_definition_component::*;
 pub use type_definition_extension::*;
 pub use type_definition_property::*;
 pub use type_id_type::*;
+pub use type_migration::*;
 pub use type_namespaced_type::*;
+pub use type_registry::*;
+pub use validation::*;
+
+#[doc(hidden)]
+pub use paste;
+#[doc(hidden)]
+pub use serde_json;
+
+#[cfg(feature = "derive")]
+pub use inexor_rgf_core_model_derive::*;
 
 pub mod behaviour_type_id;
+pub mod change_history;
 pub mod component;
 pub mod component_behaviour_type_id;
 pub mod component_container;
+pub mod component_lookup;
 pub mod component_or_entity_type_id;
 pub mod component_type_id;
 pub mod data_type;
+pub mod effective_property;
 pub mod entity_behaviour_type_id;
 pub mod entity_component_type_id;
 pub mod entity_type;
@@ -63,10 +93,15 @@ pub mod extension_container;
 pub mod extension_type_id;
 pub mod flow_type;
 pub mod flow_type_id;
+pub mod model_error;
 pub mod mutability;
+pub mod observer_subscription;
+pub mod propagation_mode;
 pub mod property;
 pub mod property_identifier;
 pub mod property_instance_accessor;
+#[cfg(feature = "async")]
+pub mod property_stream;
 pub mod property_type;
 pub mod reactive_behaviour_container;
 pub mod reactive_property_container;
@@ -74,17 +109,28 @@ pub mod relation_behaviour_type_id;
 pub mod relation_component_type_id;
 pub mod relation_type;
 pub mod relation_type_id;
+pub mod reserved_property;
+pub mod serialization_error;
+pub mod serialization_format;
 pub mod socket_type;
+pub mod sync_stream;
 pub mod type_container;
 pub mod type_definition;
 pub mod type_definition_component;
 pub mod type_definition_extension;
 pub mod type_definition_property;
 pub mod type_id_type;
+pub mod type_migration;
 pub mod type_namespaced_type;
+pub mod type_registry;
+pub mod validation;
 
 pub mod entity_instance;
+pub mod flow_change_log;
+pub mod flow_graph;
 pub mod flow_instance;
+pub mod flow_patch;
+pub mod flow_snapshot;
 pub mod relation_instance;
 pub mod relation_instance_type_id;
 
@@ -0,0 +1,50 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::TypeIdType;
+use crate::TYPE_ID_TYPE_SEPARATOR;
+
+/// Errors which can occur while parsing type identifiers or converting between the model and its
+/// graph database representation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModelError {
+    /// The one letter representation of the type of a type is unknown.
+    UnknownTypeIdType(String),
+
+    /// The type of the type is not the expected one.
+    TypeIdTypeMismatch { expected: TypeIdType, actual: TypeIdType },
+
+    /// The type identifier is missing a separator between type of the type, namespace and type name.
+    MissingSeparator(String),
+
+    /// The namespace of the type identifier is empty.
+    EmptyNamespace(String),
+
+    /// The type name of the type identifier is empty.
+    EmptyTypeName(String),
+
+    /// The type identifier contains more segments than expected.
+    TrailingSegment(String),
+
+    /// A reserved vertex or edge property could not be converted into the field of an instance
+    /// it stores, for example a description which is not a string.
+    InvalidProperty { property_name: String, reason: String },
+}
+
+impl Display for ModelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelError::UnknownTypeIdType(type_id_type) => write!(f, "Unknown type of type: {}", type_id_type),
+            ModelError::TypeIdTypeMismatch { expected, actual } => write!(f, "Expected type of type {} but found {}", expected.full_name(), actual.full_name()),
+            ModelError::MissingSeparator(type_id) => write!(f, "The type id {} is missing the separator {}", type_id, TYPE_ID_TYPE_SEPARATOR),
+            ModelError::EmptyNamespace(type_id) => write!(f, "The namespace of the type id {} is empty", type_id),
+            ModelError::EmptyTypeName(type_id) => write!(f, "The type name of the type id {} is empty", type_id),
+            ModelError::TrailingSegment(type_id) => write!(f, "The type id {} contains too many segments", type_id),
+            ModelError::InvalidProperty { property_name, reason } => write!(f, "The property {} could not be converted: {}", property_name, reason),
+        }
+    }
+}
+
+impl Error for ModelError {}
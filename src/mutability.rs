@@ -7,6 +7,7 @@ use serde::Serialize;
 
 /// The mutability of a property.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Copy, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Mutability {
     /// The property is mutable.
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::sync::Weak;
+
+use inexor_rgf_core_frp::Stream;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::SyncStream;
+
+enum SubscribedStream {
+    Stream(Weak<RwLock<Stream<'static, Value>>>),
+    SyncStream(Weak<SyncStream>),
+}
+
+/// A subscription of an observer on the stream of a reactive property.
+///
+/// The observer is removed from the stream when the subscription is dropped. Use `detach` to keep
+/// the observer for the lifetime of the stream.
+#[must_use = "the observer is removed immediately if the subscription is not kept"]
+pub struct ObserverSubscription {
+    /// The stream the observer is subscribed to.
+    stream: SubscribedStream,
+
+    /// The handle of the observer.
+    handle_id: u128,
+
+    /// True, if the observer is kept when the subscription is dropped.
+    detached: bool,
+}
+
+impl ObserverSubscription {
+    /// Subscribes the given observer on the given stream using a unique handle.
+    pub fn new<F>(stream: &Arc<RwLock<Stream<'static, Value>>>, subscriber: F) -> ObserverSubscription
+    where
+        F: FnMut(&Value) + 'static,
+    {
+        let handle_id = Uuid::new_v4().as_u128();
+        stream.read().unwrap().observe_with_handle(subscriber, handle_id);
+        ObserverSubscription {
+            stream: SubscribedStream::Stream(Arc::downgrade(stream)),
+            handle_id,
+            detached: false,
+        }
+    }
+
+    /// Subscribes the given thread-safe observer on the given stream using a unique handle.
+    pub fn new_sync<F>(stream: &Arc<SyncStream>, subscriber: F) -> ObserverSubscription
+    where
+        F: FnMut(&Value) + Send + 'static,
+    {
+        let handle_id = Uuid::new_v4().as_u128();
+        stream.observe_with_handle(subscriber, handle_id);
+        ObserverSubscription {
+            stream: SubscribedStream::SyncStream(Arc::downgrade(stream)),
+            handle_id,
+            detached: false,
+        }
+    }
+
+    /// Returns the handle of the observer.
+    pub fn handle_id(&self) -> u128 {
+        self.handle_id
+    }
+
+    /// Returns true, if the stream still exists.
+    pub fn is_active(&self) -> bool {
+        match &self.stream {
+            SubscribedStream::Stream(stream) => stream.strong_count() > 0,
+            SubscribedStream::SyncStream(stream) => stream.strong_count() > 0,
+        }
+    }
+
+    /// Removes the observer from the stream.
+    pub fn unsubscribe(self) {}
+
+    /// Keeps the observer subscribed for the lifetime of the stream and returns its handle. The
+    /// observer can still be removed using the handle.
+    pub fn detach(mut self) -> u128 {
+        self.detached = true;
+        self.handle_id
+    }
+}
+
+impl Drop for ObserverSubscription {
+    fn drop(&mut self) {
+        if self.detached {
+            return;
+        }
+        match &self.stream {
+            SubscribedStream::Stream(stream) => {
+                if let Some(stream) = stream.upgrade() {
+                    stream.read().unwrap().remove(self.handle_id);
+                }
+            }
+            SubscribedStream::SyncStream(stream) => {
+                if let Some(stream) = stream.upgrade() {
+                    stream.remove(self.handle_id);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use core::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// The propagation mode of a property defines when a new value is sent down the stream.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Copy)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PropagationMode {
+    /// Every new value is sent down the stream.
+    #[default]
+    Always,
+
+    /// A new value is only sent down the stream if it differs from the last value which has been
+    /// sent down the stream.
+    OnChange,
+
+    /// A new value is only sent down the stream if it differs from the last value which has been
+    /// sent down the stream. Numbers are only considered as changed if they differ by more than
+    /// the given epsilon, so values which drift slowly are sent as soon as the accumulated
+    /// difference exceeds the epsilon.
+    OnChangeWithEpsilon(f64),
+}
+
+impl PropagationMode {
+    pub fn always() -> Self {
+        PropagationMode::Always
+    }
+
+    pub fn on_change() -> Self {
+        PropagationMode::OnChange
+    }
+
+    /// Returns true, if the new value has to be sent down the stream. The new value is compared
+    /// with the last value which has been sent down the stream.
+    pub fn should_propagate(&self, propagated_value: &Value, new_value: &Value) -> bool {
+        match self {
+            PropagationMode::Always => true,
+            PropagationMode::OnChange => propagated_value != new_value,
+            PropagationMode::OnChangeWithEpsilon(epsilon) => match (propagated_value.as_f64(), new_value.as_f64()) {
+                (Some(propagated_value), Some(new_value)) => (propagated_value - new_value).abs() > *epsilon,
+                _ => propagated_value != new_value,
+            },
+        }
+    }
+}
+
+impl Display for PropagationMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::has_reserved_prefix;
 use crate::NAMESPACE_PROPERTY_TYPE;
 use indradb::Identifier;
 use uuid::Uuid;
@@ -5,8 +6,14 @@ use uuid::Uuid;
 /// Safely constructs a property identifier.
 ///
 /// Fallback: generate a UUID v5 based on the property name and a given namespace. The generated
-/// property identifier is stable for the property name.
+/// property identifier is stable for the property name. The fallback is also used for property
+/// names which start with the prefix of the reserved properties.
 pub fn property_identifier<S: Into<String>>(property_name: S) -> Identifier {
     let property_name = property_name.into();
-    Identifier::new(&property_name).unwrap_or_else(|_| Identifier::new(Uuid::new_v5(&NAMESPACE_PROPERTY_TYPE, property_name.as_bytes()).to_string()).unwrap())
+    if !has_reserved_prefix(&property_name) {
+        if let Ok(identifier) = Identifier::new(&property_name) {
+            return identifier;
+        }
+    }
+    Identifier::new(Uuid::new_v5(&NAMESPACE_PROPERTY_TYPE, property_name.as_bytes()).to_string()).unwrap()
 }
@@ -1,7 +1,14 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::DateTime;
+use chrono::Utc;
+use serde_json::json;
 use serde_json::Map;
 use serde_json::Value;
+use uuid::Uuid;
 
 use crate::Mutability;
+use crate::PropagationMode;
 
 pub trait PropertyInstanceGetter {
     /// Returns the json value of the given property by name
@@ -28,26 +35,71 @@ pub trait PropertyInstanceGetter {
     /// Returns the string value of the given property by name
     fn as_object<S: Into<String>>(&self, property_name: S) -> Option<Map<String, Value>>;
 
+    /// Returns the uuid value of the given property by name
+    fn as_uuid<S: Into<String>>(&self, property_name: S) -> Option<Uuid> {
+        self.as_string(property_name).and_then(|v| Uuid::parse_str(&v).ok())
+    }
+
+    /// Returns the date time value (RFC 3339) of the given property by name
+    fn as_date_time<S: Into<String>>(&self, property_name: S) -> Option<DateTime<Utc>> {
+        self.as_string(property_name)
+            .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+            .map(|v| v.with_timezone(&Utc))
+    }
+
+    /// Returns the byte array value (base64) of the given property by name
+    fn as_bytes<S: Into<String>>(&self, property_name: S) -> Option<Vec<u8>> {
+        self.as_string(property_name).and_then(|v| STANDARD.decode(v).ok())
+    }
+
     // TODO: integrate with other non-primitive types
-    // as_byte_array (string BASE64 -> Vec<u8>)
     // as_date (string ISO8601 -> chrono::Date)
-    // as_date_time (string ISO8601 -> chrono::DateTime)
     // as_time (string ISO8601 -> chrono::naive::NaiveTime)
-    // as_uuid (string uuid -> Uuid)
 }
 
 pub trait MutablePropertyInstanceSetter: PropertyInstanceGetter {
     /// Sets the value of the given property by name
     fn set<S: Into<String>>(&mut self, property_name: S, value: Value);
 
-    // TODO: Typed setters
-    // set_bool(property_name, value)
-    // set_number(property_name, value)
-    // set_byte_array(property_name, u8[] -> string BASE64)
-    // set_date(property_name, chrono::Date -> string ISO8601)
-    // set_date_time(property_name, chrono::Date -> string ISO8601)
-    // as_time(property_name, chrono::naive::NaiveTime -> string ISO8601)
-    // as_uuid(property_name, Uuid -> string uuid)
+    /// Sets the uuid value of the given property by name
+    fn set_uuid<S: Into<String>>(&mut self, property_name: S, value: Uuid) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the date time value of the given property by name. The value is stored in RFC 3339 format.
+    fn set_date_time<S: Into<String>>(&mut self, property_name: S, value: DateTime<Utc>) {
+        self.set(property_name, json!(value.to_rfc3339()));
+    }
+
+    /// Sets the byte array value of the given property by name. The value is stored base64 encoded.
+    fn set_bytes<S: Into<String>>(&mut self, property_name: S, value: &[u8]) {
+        self.set(property_name, json!(STANDARD.encode(value)));
+    }
+
+    /// Sets the boolean value of the given property by name
+    fn set_bool<S: Into<String>>(&mut self, property_name: S, value: bool) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the unsigned integer value of the given property by name
+    fn set_u64<S: Into<String>>(&mut self, property_name: S, value: u64) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the signed integer value of the given property by name
+    fn set_i64<S: Into<String>>(&mut self, property_name: S, value: i64) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the floating point value of the given property by name
+    fn set_f64<S: Into<String>>(&mut self, property_name: S, value: f64) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the string value of the given property by name
+    fn set_string<S: Into<String>, V: Into<String>>(&mut self, property_name: S, value: V) {
+        self.set(property_name, json!(value.into()));
+    }
 }
 
 pub trait PropertyInstanceSetter: PropertyInstanceGetter {
@@ -64,347 +116,717 @@ pub trait PropertyInstanceSetter: PropertyInstanceGetter {
     /// Sets the value of the given property by name. Sends the value down the stream.
     fn set_no_propagate<S: Into<String>>(&self, property_name: S, value: Value);
 
+    /// Sets the boolean value of the given property by name
+    fn set_bool<S: Into<String>>(&self, property_name: S, value: bool) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the unsigned integer value of the given property by name
+    fn set_u64<S: Into<String>>(&self, property_name: S, value: u64) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the signed integer value of the given property by name
+    fn set_i64<S: Into<String>>(&self, property_name: S, value: i64) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the floating point value of the given property by name
+    fn set_f64<S: Into<String>>(&self, property_name: S, value: f64) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the string value of the given property by name
+    fn set_string<S: Into<String>, V: Into<String>>(&self, property_name: S, value: V) {
+        self.set(property_name, json!(value.into()));
+    }
+
+    /// Sets the uuid value of the given property by name
+    fn set_uuid<S: Into<String>>(&self, property_name: S, value: Uuid) {
+        self.set(property_name, json!(value));
+    }
+
+    /// Sets the date time value of the given property by name. The value is stored in RFC 3339 format.
+    fn set_date_time<S: Into<String>>(&self, property_name: S, value: DateTime<Utc>) {
+        self.set(property_name, json!(value.to_rfc3339()));
+    }
+
+    /// Sets the byte array value of the given property by name. The value is stored base64 encoded.
+    fn set_bytes<S: Into<String>>(&self, property_name: S, value: &[u8]) {
+        self.set(property_name, json!(STANDARD.encode(value)));
+    }
+
+    /// Sets the values of all given mutable properties at once.
+    ///
+    /// First all values are set internally and after that the values are sent down the streams of
+    /// the affected properties. Observers of one property never see a partially updated instance.
+    fn set_all_checked(&self, properties: Map<String, Value>);
+
+    /// Sets the values of all given properties at once.
+    ///
+    /// First all values are set internally and after that the values are sent down the streams of
+    /// the affected properties. Observers of one property never see a partially updated instance.
+    fn set_all(&self, properties: Map<String, Value>);
+
     /// Returns the mutability of the property by name.
     fn mutability<S: Into<String>>(&self, property_name: S) -> Option<Mutability>;
 
     /// Sets the mutability of the property by name.
     fn set_mutability<S: Into<String>>(&self, property_name: S, mutability: Mutability);
+
+    /// Returns the propagation mode of the property by name.
+    fn propagation<S: Into<String>>(&self, property_name: S) -> Option<PropagationMode>;
+
+    /// Sets the propagation mode of the property by name.
+    fn set_propagation<S: Into<String>>(&self, property_name: S, propagation: PropagationMode);
 }
 
+/// Generates the accessors of a property.
+///
+/// * `get x` generates the getter `x()`
+/// * `readonly x` generates the getter `get_x()`
+/// * `set x` generates the setter `set_x(v)`
+/// * `data x` generates the getter `get_x()` and the setter `set_x(v)`
+///
+/// The getters (`get`, `readonly` and `data`) also generate the constant `X` with the name of the
+/// property and the observer `on_x_changed(f)`. Setters don't, so that a property can be declared
+/// with `get x` and `set x`. Declare at most one getter per property.
+///
+/// Breaking change: `set x` generated the setter `x(v)` before and generates `set_x(v)` now.
 #[macro_export]
 macro_rules! rx_accessor {
     // Special accessors
     (trigger) => {
         fn trigger(&self) {
-            $crate::PropertyInstanceSetter::set(self, "trigger", serde_json::json!(true));
+            $crate::PropertyInstanceSetter::set(self, "trigger", $crate::serde_json::json!(true));
         }
     };
     (pub trigger) => {
         pub fn trigger(&self) {
-            $crate::PropertyInstanceSetter::set(self, "trigger", serde_json::json!(true));
+            $crate::PropertyInstanceSetter::set(self, "trigger", $crate::serde_json::json!(true));
         }
     };
     // Getters
     (get $getter_name: ident value) => {
-        fn $getter_name(&self) -> Option<serde_json::Value> {
+        fn $getter_name(&self) -> Option<$crate::serde_json::Value> {
             $crate::PropertyInstanceGetter::get(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(observe $getter_name value);
+        $crate::rx_accessor!(name $getter_name);
     };
     (pub get $getter_name: ident value) => {
-        pub fn $getter_name(&self) -> Option<serde_json::Value> {
+        pub fn $getter_name(&self) -> Option<$crate::serde_json::Value> {
             $crate::PropertyInstanceGetter::get(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(pub observe $getter_name value);
+        $crate::rx_accessor!(pub name $getter_name);
     };
     (get $getter_name: ident bool) => {
         fn $getter_name(&self) -> Option<bool> {
             $crate::PropertyInstanceGetter::as_bool(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(observe $getter_name bool);
+        $crate::rx_accessor!(name $getter_name);
     };
     (pub get $getter_name: ident bool) => {
         pub fn $getter_name(&self) -> Option<bool> {
             $crate::PropertyInstanceGetter::as_bool(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(pub observe $getter_name bool);
+        $crate::rx_accessor!(pub name $getter_name);
     };
     (get $getter_name: ident u64) => {
         fn $getter_name(&self) -> Option<u64> {
             $crate::PropertyInstanceGetter::as_u64(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(observe $getter_name u64);
+        $crate::rx_accessor!(name $getter_name);
     };
     (pub get $getter_name: ident u64) => {
         pub fn $getter_name(&self) -> Option<u64> {
             $crate::PropertyInstanceGetter::as_u64(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(pub observe $getter_name u64);
+        $crate::rx_accessor!(pub name $getter_name);
     };
     (get $getter_name: ident i64) => {
         fn $getter_name(&self) -> Option<i64> {
             $crate::PropertyInstanceGetter::as_i64(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(observe $getter_name i64);
+        $crate::rx_accessor!(name $getter_name);
     };
     (pub get $getter_name: ident i64) => {
         pub fn $getter_name(&self) -> Option<i64> {
             $crate::PropertyInstanceGetter::as_i64(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(pub observe $getter_name i64);
+        $crate::rx_accessor!(pub name $getter_name);
     };
     (get $getter_name: ident f64) => {
         fn $getter_name(&self) -> Option<f64> {
             $crate::PropertyInstanceGetter::as_f64(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(observe $getter_name f64);
+        $crate::rx_accessor!(name $getter_name);
     };
     (pub get $getter_name: ident f64) => {
         pub fn $getter_name(&self) -> Option<f64> {
             $crate::PropertyInstanceGetter::as_f64(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(pub observe $getter_name f64);
+        $crate::rx_accessor!(pub name $getter_name);
     };
     (get $getter_name: ident string) => {
         fn $getter_name(&self) -> Option<String> {
             $crate::PropertyInstanceGetter::as_string(self, stringify!($getter_name)).map(String::from)
         }
+        $crate::rx_accessor!(observe $getter_name string);
+        $crate::rx_accessor!(name $getter_name);
     };
     (pub get $getter_name: ident string) => {
         pub fn $getter_name(&self) -> Option<String> {
             $crate::PropertyInstanceGetter::as_string(self, stringify!($getter_name)).map(String::from)
         }
+        $crate::rx_accessor!(pub observe $getter_name string);
+        $crate::rx_accessor!(pub name $getter_name);
     };
     (get $getter_name: ident array) => {
-        fn $getter_name(&self) -> Option<Vec<serde_json::Value>> {
+        fn $getter_name(&self) -> Option<Vec<$crate::serde_json::Value>> {
             $crate::PropertyInstanceGetter::as_array(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(observe $getter_name array);
+        $crate::rx_accessor!(name $getter_name);
     };
     (pub get $getter_name: ident array) => {
-        pub fn $getter_name(&self) -> Option<Vec<serde_json::Value>> {
+        pub fn $getter_name(&self) -> Option<Vec<$crate::serde_json::Value>> {
             $crate::PropertyInstanceGetter::as_array(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(pub observe $getter_name array);
+        $crate::rx_accessor!(pub name $getter_name);
     };
     (get $getter_name: ident object) => {
-        fn $getter_name(&self) -> Option<serde_json::Map<String, serde_json::Value>> {
+        fn $getter_name(&self) -> Option<$crate::serde_json::Map<String, $crate::serde_json::Value>> {
             $crate::PropertyInstanceGetter::as_object(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(observe $getter_name object);
+        $crate::rx_accessor!(name $getter_name);
     };
     (pub get $getter_name: ident object) => {
-        pub fn $getter_name(&self) -> Option<serde_json::Map<String, serde_json::Value>> {
+        pub fn $getter_name(&self) -> Option<$crate::serde_json::Map<String, $crate::serde_json::Value>> {
             $crate::PropertyInstanceGetter::as_object(self, stringify!($getter_name))
         }
+        $crate::rx_accessor!(pub observe $getter_name object);
+        $crate::rx_accessor!(pub name $getter_name);
     };
     // Setters
-    (set $setter_name: ident value) => {
-        fn $setter_name(&self, v: serde_json::Value) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), v);
+    (set $property_name: ident value) => {
+        $crate::paste::paste! {
+            fn [<set_ $property_name>](&self, v: $crate::serde_json::Value) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), v);
+            }
+        }
+    };
+    (pub set $property_name: ident value) => {
+        $crate::paste::paste! {
+            pub fn [<set_ $property_name>](&self, v: $crate::serde_json::Value) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), v);
+            }
+        }
+    };
+    (set $property_name: ident bool) => {
+        $crate::paste::paste! {
+            fn [<set_ $property_name>](&self, v: bool) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (pub set $setter_name: ident value) => {
-        pub fn $setter_name(&self, v: serde_json::Value) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), v);
+    (pub set $property_name: ident bool) => {
+        $crate::paste::paste! {
+            pub fn [<set_ $property_name>](&self, v: bool) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
+        }
+    };
+    (set $property_name: ident u64) => {
+        $crate::paste::paste! {
+            fn [<set_ $property_name>](&self, v: u64) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (set $setter_name: ident bool) => {
-        fn $setter_name(&self, v: bool) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (pub set $property_name: ident u64) => {
+        $crate::paste::paste! {
+            pub fn [<set_ $property_name>](&self, v: u64) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (pub set $setter_name: ident bool) => {
-        pub fn $setter_name(&self, v: bool) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (set $property_name: ident i64) => {
+        $crate::paste::paste! {
+            fn [<set_ $property_name>](&self, v: i64) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (set $setter_name: ident u64) => {
-        fn $setter_name(&self, v: u64) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (pub set $property_name: ident i64) => {
+        $crate::paste::paste! {
+            pub fn [<set_ $property_name>](&self, v: i64) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (pub set $setter_name: ident u64) => {
-        pub fn $setter_name(&self, v: u64) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (set $property_name: ident f64) => {
+        $crate::paste::paste! {
+            fn [<set_ $property_name>](&self, v: f64) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (set $setter_name: ident i64) => {
-        fn $setter_name(&self, v: i64) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (pub set $property_name: ident f64) => {
+        $crate::paste::paste! {
+            pub fn [<set_ $property_name>](&self, v: f64) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (pub set $setter_name: ident i64) => {
-        pub fn $setter_name(&self, v: i64) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (set $property_name: ident string) => {
+        $crate::paste::paste! {
+            fn [<set_ $property_name>]<S: Into<String>>(&self, v: S) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v.into()));
+            }
         }
     };
-    (set $setter_name: ident f64) => {
-        fn $setter_name(&self, v: f64) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (pub set $property_name: ident string) => {
+        $crate::paste::paste! {
+            pub fn [<set_ $property_name>]<S: Into<String>>(&self, v: S) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v.into()));
+            }
         }
     };
-    (pub set $setter_name: ident f64) => {
-        pub fn $setter_name(&self, v: f64) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (set $property_name: ident array) => {
+        $crate::paste::paste! {
+            fn [<set_ $property_name>](&self, v: Vec<$crate::serde_json::Value>) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (set $setter_name: ident string) => {
-        fn $setter_name<S: Into<String>>(&self, v: S) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v.into()));
+    (pub set $property_name: ident array) => {
+        $crate::paste::paste! {
+            pub fn [<set_ $property_name>](&self, v: Vec<$crate::serde_json::Value>) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (pub set $setter_name: ident string) => {
-        pub fn $setter_name<S: Into<String>>(&self, v: S) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v.into()));
+    (set $property_name: ident object) => {
+        $crate::paste::paste! {
+            fn [<set_ $property_name>](&self, v: $crate::serde_json::Map<String, $crate::serde_json::Value>) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (set $setter_name: ident array) => {
-        fn $setter_name(&self, v: Vec<serde_json::Value>) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (pub set $property_name: ident object) => {
+        $crate::paste::paste! {
+            pub fn [<set_ $property_name>](&self, v: $crate::serde_json::Map<String, $crate::serde_json::Value>) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
         }
     };
-    (pub set $setter_name: ident array) => {
-        pub fn $setter_name(&self, v: Vec<serde_json::Value>) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    // Read only (getter with the prefix get_)
+    (readonly $property_name: ident value) => {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<$crate::serde_json::Value> {
+                $crate::PropertyInstanceGetter::get(self, stringify!($property_name))
+            }
         }
+        $crate::rx_accessor!(observe $property_name value);
+        $crate::rx_accessor!(name $property_name);
     };
-    (set $setter_name: ident object) => {
-        fn $setter_name(&self, v: serde_json::Map<String, serde_json::Value>) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (pub readonly $property_name: ident value) => {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<$crate::serde_json::Value> {
+                $crate::PropertyInstanceGetter::get(self, stringify!($property_name))
+            }
         }
+        $crate::rx_accessor!(pub observe $property_name value);
+        $crate::rx_accessor!(pub name $property_name);
     };
-    (pub set $setter_name: ident object) => {
-        pub fn $setter_name(&self, v: serde_json::Map<String, serde_json::Value>) {
-            $crate::PropertyInstanceSetter::set(self, stringify!($setter_name), serde_json::json!(v));
+    (readonly $property_name: ident bool) => {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<bool> {
+                $crate::PropertyInstanceGetter::as_bool(self, stringify!($property_name))
+            }
         }
+        $crate::rx_accessor!(observe $property_name bool);
+        $crate::rx_accessor!(name $property_name);
+    };
+    (pub readonly $property_name: ident bool) => {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<bool> {
+                $crate::PropertyInstanceGetter::as_bool(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(pub observe $property_name bool);
+        $crate::rx_accessor!(pub name $property_name);
+    };
+    (readonly $property_name: ident u64) => {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<u64> {
+                $crate::PropertyInstanceGetter::as_u64(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(observe $property_name u64);
+        $crate::rx_accessor!(name $property_name);
+    };
+    (pub readonly $property_name: ident u64) => {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<u64> {
+                $crate::PropertyInstanceGetter::as_u64(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(pub observe $property_name u64);
+        $crate::rx_accessor!(pub name $property_name);
+    };
+    (readonly $property_name: ident i64) => {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<i64> {
+                $crate::PropertyInstanceGetter::as_i64(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(observe $property_name i64);
+        $crate::rx_accessor!(name $property_name);
+    };
+    (pub readonly $property_name: ident i64) => {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<i64> {
+                $crate::PropertyInstanceGetter::as_i64(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(pub observe $property_name i64);
+        $crate::rx_accessor!(pub name $property_name);
+    };
+    (readonly $property_name: ident f64) => {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<f64> {
+                $crate::PropertyInstanceGetter::as_f64(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(observe $property_name f64);
+        $crate::rx_accessor!(name $property_name);
+    };
+    (pub readonly $property_name: ident f64) => {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<f64> {
+                $crate::PropertyInstanceGetter::as_f64(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(pub observe $property_name f64);
+        $crate::rx_accessor!(pub name $property_name);
+    };
+    (readonly $property_name: ident string) => {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<String> {
+                $crate::PropertyInstanceGetter::as_string(self, stringify!($property_name)).map(String::from)
+            }
+        }
+        $crate::rx_accessor!(observe $property_name string);
+        $crate::rx_accessor!(name $property_name);
+    };
+    (pub readonly $property_name: ident string) => {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<String> {
+                $crate::PropertyInstanceGetter::as_string(self, stringify!($property_name)).map(String::from)
+            }
+        }
+        $crate::rx_accessor!(pub observe $property_name string);
+        $crate::rx_accessor!(pub name $property_name);
+    };
+    (readonly $property_name: ident array) => {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<Vec<$crate::serde_json::Value>> {
+                $crate::PropertyInstanceGetter::as_array(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(observe $property_name array);
+        $crate::rx_accessor!(name $property_name);
+    };
+    (pub readonly $property_name: ident array) => {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<Vec<$crate::serde_json::Value>> {
+                $crate::PropertyInstanceGetter::as_array(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(pub observe $property_name array);
+        $crate::rx_accessor!(pub name $property_name);
+    };
+    (readonly $property_name: ident object) => {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<$crate::serde_json::Map<String, $crate::serde_json::Value>> {
+                $crate::PropertyInstanceGetter::as_object(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(observe $property_name object);
+        $crate::rx_accessor!(name $property_name);
+    };
+    (pub readonly $property_name: ident object) => {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<$crate::serde_json::Map<String, $crate::serde_json::Value>> {
+                $crate::PropertyInstanceGetter::as_object(self, stringify!($property_name))
+            }
+        }
+        $crate::rx_accessor!(pub observe $property_name object);
+        $crate::rx_accessor!(pub name $property_name);
     };
     // data (getter + setter)
     (data $property_name: ident value) => {
-        paste::paste! {
-            fn [<get_ $property_name>](&self) -> Option<serde_json::Value> {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<$crate::serde_json::Value> {
                 $crate::PropertyInstanceGetter::get(self, stringify!($property_name))
             }
-            fn [<set_ $property_name>](&self, v: serde_json::Value) {
+            fn [<set_ $property_name>](&self, v: $crate::serde_json::Value) {
                 $crate::PropertyInstanceSetter::set(self, stringify!($property_name), v);
             }
         }
+        $crate::rx_accessor!(observe $property_name value);
+        $crate::rx_accessor!(name $property_name);
     };
     (pub data $property_name: ident value) => {
-        paste::paste! {
-            pub fn [<get_ $property_name>](&self) -> Option<serde_json::Value> {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<$crate::serde_json::Value> {
                 $crate::PropertyInstanceGetter::get(self, stringify!($property_name))
             }
-            pub fn [<set_ $property_name>](&self, v: serde_json::Value) {
+            pub fn [<set_ $property_name>](&self, v: $crate::serde_json::Value) {
                 $crate::PropertyInstanceSetter::set(self, stringify!($property_name), v);
             }
         }
+        $crate::rx_accessor!(pub observe $property_name value);
+        $crate::rx_accessor!(pub name $property_name);
     };
     (data $property_name: ident bool) => {
-        paste::paste! {
+        $crate::paste::paste! {
             fn [<get_ $property_name>](&self) -> Option<bool> {
                 $crate::PropertyInstanceGetter::as_bool(self, stringify!($property_name))
             }
             fn [<set_ $property_name>](&self, v: bool) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(observe $property_name bool);
+        $crate::rx_accessor!(name $property_name);
     };
     (pub data $property_name: ident bool) => {
-        paste::paste! {
+        $crate::paste::paste! {
             pub fn [<get_ $property_name>](&self) -> Option<bool> {
                 $crate::PropertyInstanceGetter::as_bool(self, stringify!($property_name))
             }
             pub fn [<set_ $property_name>](&self, v: bool) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(pub observe $property_name bool);
+        $crate::rx_accessor!(pub name $property_name);
     };
     (data $property_name: ident u64) => {
-        paste::paste! {
+        $crate::paste::paste! {
             fn [<get_ $property_name>](&self) -> Option<u64> {
                 $crate::PropertyInstanceGetter::as_u64(self, stringify!($property_name))
             }
             fn [<set_ $property_name>](&self, v: u64) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(observe $property_name u64);
+        $crate::rx_accessor!(name $property_name);
     };
     (pub data $property_name: ident u64) => {
-        paste::paste! {
+        $crate::paste::paste! {
             pub fn [<get_ $property_name>](&self) -> Option<u64> {
                 $crate::PropertyInstanceGetter::as_u64(self, stringify!($property_name))
             }
             pub fn [<set_ $property_name>](&self, v: u64) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(pub observe $property_name u64);
+        $crate::rx_accessor!(pub name $property_name);
     };
     (data $property_name: ident i64) => {
-        paste::paste! {
+        $crate::paste::paste! {
             fn [<get_ $property_name>](&self) -> Option<i64> {
                 $crate::PropertyInstanceGetter::as_i64(self, stringify!($property_name))
             }
             fn [<set_ $property_name>](&self, v: i64) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(observe $property_name i64);
+        $crate::rx_accessor!(name $property_name);
     };
     (pub data $property_name: ident i64) => {
-        paste::paste! {
+        $crate::paste::paste! {
             pub fn [<get_ $property_name>](&self) -> Option<i64> {
                 $crate::PropertyInstanceGetter::as_i64(self, stringify!($property_name))
             }
             pub fn [<set_ $property_name>](&self, v: i64) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(pub observe $property_name i64);
+        $crate::rx_accessor!(pub name $property_name);
     };
     (data $property_name: ident f64) => {
-        paste::paste! {
+        $crate::paste::paste! {
             fn [<get_ $property_name>](&self) -> Option<f64> {
                 $crate::PropertyInstanceGetter::as_f64(self, stringify!($property_name))
             }
             fn [<set_ $property_name>](&self, v: f64) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(observe $property_name f64);
+        $crate::rx_accessor!(name $property_name);
     };
     (pub data $property_name: ident f64) => {
-        paste::paste! {
+        $crate::paste::paste! {
             pub fn [<get_ $property_name>](&self) -> Option<f64> {
                 $crate::PropertyInstanceGetter::as_f64(self, stringify!($property_name))
             }
             pub fn [<set_ $property_name>](&self, v: f64) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(pub observe $property_name f64);
+        $crate::rx_accessor!(pub name $property_name);
     };
     (data $property_name: ident string) => {
-        paste::paste! {
+        $crate::paste::paste! {
             fn [<get_ $property_name>](&self) -> Option<String> {
                 $crate::PropertyInstanceGetter::as_string(self, stringify!($property_name)).map(String::from)
             }
             fn [<set_ $property_name>]<S: Into<String>>(&self, v: S) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v.into()));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v.into()));
             }
         }
+        $crate::rx_accessor!(observe $property_name string);
+        $crate::rx_accessor!(name $property_name);
     };
     (pub data $property_name: ident string) => {
-        paste::paste! {
+        $crate::paste::paste! {
             pub fn [<get_ $property_name>](&self) -> Option<String> {
                 $crate::PropertyInstanceGetter::as_string(self, stringify!($property_name)).map(String::from)
             }
             pub fn [<set_ $property_name>]<S: Into<String>>(&self, v: S) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v.into()));
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v.into()));
             }
         }
+        $crate::rx_accessor!(pub observe $property_name string);
+        $crate::rx_accessor!(pub name $property_name);
     };
     (data $property_name: ident array) => {
-        paste::paste! {
-            fn [<get_ $property_name>](&self) -> Option<Vec<serde_json::Value>> {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<Vec<$crate::serde_json::Value>> {
                 $crate::PropertyInstanceGetter::as_array(self, stringify!($property_name))
             }
-            fn [<set_ $property_name>](&self, v: Vec<serde_json::Value>) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+            fn [<set_ $property_name>](&self, v: Vec<$crate::serde_json::Value>) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(observe $property_name array);
+        $crate::rx_accessor!(name $property_name);
     };
     (pub data $property_name: ident array) => {
-        paste::paste! {
-            pub fn [<get_ $property_name>](&self) -> Option<Vec<serde_json::Value>> {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<Vec<$crate::serde_json::Value>> {
                 $crate::PropertyInstanceGetter::as_array(self, stringify!($property_name))
             }
-            pub fn [<set_ $property_name>](&self, v: Vec<serde_json::Value>) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+            pub fn [<set_ $property_name>](&self, v: Vec<$crate::serde_json::Value>) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(pub observe $property_name array);
+        $crate::rx_accessor!(pub name $property_name);
     };
     (data $property_name: ident object) => {
-        paste::paste! {
-            fn [<get_ $property_name>](&self) -> Option<serde_json::Map<String, serde_json::Value>> {
+        $crate::paste::paste! {
+            fn [<get_ $property_name>](&self) -> Option<$crate::serde_json::Map<String, $crate::serde_json::Value>> {
                 $crate::PropertyInstanceGetter::as_object(self, stringify!($property_name))
             }
-            fn [<set_ $property_name>](&self, v: serde_json::Map<String, serde_json::Value>) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
+            fn [<set_ $property_name>](&self, v: $crate::serde_json::Map<String, $crate::serde_json::Value>) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
             }
         }
+        $crate::rx_accessor!(observe $property_name object);
+        $crate::rx_accessor!(name $property_name);
     };
     (pub data $property_name: ident object) => {
-        paste::paste! {
-            pub fn [<get_ $property_name>](&self) -> Option<serde_json::Map<String, serde_json::Value>> {
+        $crate::paste::paste! {
+            pub fn [<get_ $property_name>](&self) -> Option<$crate::serde_json::Map<String, $crate::serde_json::Value>> {
                 $crate::PropertyInstanceGetter::as_object(self, stringify!($property_name))
             }
-            pub fn [<set_ $property_name>](&self, v: serde_json::Map<String, serde_json::Value>) {
-                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), serde_json::json!(v));
-            }
-        }
-    };
+            pub fn [<set_ $property_name>](&self, v: $crate::serde_json::Map<String, $crate::serde_json::Value>) {
+                $crate::PropertyInstanceSetter::set(self, stringify!($property_name), $crate::serde_json::json!(v));
+            }
+        }
+        $crate::rx_accessor!(pub observe $property_name object);
+        $crate::rx_accessor!(pub name $property_name);
+    };
+    // Observers
+    (observe $property_name: ident $data_type: tt) => {
+        $crate::paste::paste! {
+            fn [<on_ $property_name _changed>]<F>(&self, mut f: F) -> Option<$crate::ObserverSubscription>
+            where
+                Self: $crate::ReactivePropertyContainer,
+                F: FnMut($crate::rx_accessor!(@type $data_type)) + 'static,
+            {
+                $crate::ReactivePropertyContainer::subscribe(self, stringify!($property_name), move |v: &$crate::serde_json::Value| {
+                    if let Some(v) = $crate::rx_accessor!(@from_value v $data_type) {
+                        f(v);
+                    }
+                })
+            }
+        }
+    };
+    (pub observe $property_name: ident $data_type: tt) => {
+        $crate::paste::paste! {
+            pub fn [<on_ $property_name _changed>]<F>(&self, mut f: F) -> Option<$crate::ObserverSubscription>
+            where
+                F: FnMut($crate::rx_accessor!(@type $data_type)) + 'static,
+            {
+                $crate::ReactivePropertyContainer::subscribe(self, stringify!($property_name), move |v: &$crate::serde_json::Value| {
+                    if let Some(v) = $crate::rx_accessor!(@from_value v $data_type) {
+                        f(v);
+                    }
+                })
+            }
+        }
+    };
+    // Property names
+    (name $property_name: ident) => {
+        $crate::paste::paste! {
+            const [<$property_name:upper>]: &'static str = stringify!($property_name);
+        }
+    };
+    (pub name $property_name: ident) => {
+        $crate::paste::paste! {
+            pub const [<$property_name:upper>]: &'static str = stringify!($property_name);
+        }
+    };
+    // Internal: the rust type of a data type
+    (@type value) => { $crate::serde_json::Value };
+    (@type bool) => { bool };
+    (@type u64) => { u64 };
+    (@type i64) => { i64 };
+    (@type f64) => { f64 };
+    (@type string) => { String };
+    (@type array) => { Vec<$crate::serde_json::Value> };
+    (@type object) => { $crate::serde_json::Map<String, $crate::serde_json::Value> };
+    // Internal: converts a json value into the rust type of a data type
+    (@from_value $v: ident value) => { Some($v.clone()) };
+    (@from_value $v: ident bool) => { $v.as_bool() };
+    (@from_value $v: ident u64) => { $v.as_u64() };
+    (@from_value $v: ident i64) => { $v.as_i64() };
+    (@from_value $v: ident f64) => { $v.as_f64() };
+    (@from_value $v: ident string) => { $v.as_str().map(String::from) };
+    (@from_value $v: ident array) => { $v.as_array().cloned() };
+    (@from_value $v: ident object) => { $v.as_object().cloned() };
 }
@@ -0,0 +1,199 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::Weak;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use futures::Stream;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::SyncStream;
+
+/// Specifies what happens if a value is sent while the buffer of a property stream is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// The oldest buffered value is dropped.
+    #[default]
+    DropOldest,
+
+    /// The new value is dropped.
+    DropNewest,
+
+    /// The thread which sets the property is blocked until the consumer has taken a value from
+    /// the buffer. The consumer must not run on the same thread.
+    ///
+    /// The blocked thread doesn't hold the lock of the value, so the consumer may read the
+    /// property. Other threads which set the same property are blocked as well, because values
+    /// are sent in the order in which they have been set. Locks which are held by the caller of
+    /// the setter, for example by a behaviour, are held until the consumer has taken a value.
+    Block,
+}
+
+impl Display for OverflowPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OverflowPolicy::DropOldest => write!(f, "DropOldest"),
+            OverflowPolicy::DropNewest => write!(f, "DropNewest"),
+            OverflowPolicy::Block => write!(f, "Block"),
+        }
+    }
+}
+
+struct Buffer {
+    values: VecDeque<Value>,
+    dropped: u64,
+    waker: Option<Waker>,
+    producer_closed: bool,
+    consumer_closed: bool,
+}
+
+struct Shared {
+    buffer: Mutex<Buffer>,
+    space_available: Condvar,
+    capacity: usize,
+    overflow: OverflowPolicy,
+}
+
+impl Shared {
+    fn push(&self, value: &Value) {
+        let mut buffer = self.buffer.lock().unwrap();
+        if buffer.consumer_closed {
+            return;
+        }
+        if buffer.values.len() >= self.capacity {
+            match self.overflow {
+                OverflowPolicy::DropOldest => {
+                    buffer.values.pop_front();
+                    buffer.dropped += 1;
+                }
+                OverflowPolicy::DropNewest => {
+                    buffer.dropped += 1;
+                    return;
+                }
+                OverflowPolicy::Block => {
+                    buffer = self
+                        .space_available
+                        .wait_while(buffer, |buffer| buffer.values.len() >= self.capacity && !buffer.consumer_closed)
+                        .unwrap();
+                    if buffer.consumer_closed {
+                        return;
+                    }
+                }
+            }
+        }
+        buffer.values.push_back(value.clone());
+        if let Some(waker) = buffer.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Owned by the observer. Closes the property stream as soon as the observer is removed from
+/// the stream of the property, for example because the property has been removed.
+struct Producer {
+    shared: Arc<Shared>,
+}
+
+impl Drop for Producer {
+    fn drop(&mut self) {
+        let mut buffer = self.shared.buffer.lock().unwrap();
+        buffer.producer_closed = true;
+        if let Some(waker) = buffer.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// An asynchronous stream of the values of a reactive property.
+///
+/// The values are buffered in a bounded buffer. If the buffer is full, the overflow policy
+/// decides which value gets lost. The stream ends when the property is removed. Dropping the
+/// stream removes the observer from the property.
+pub struct PropertyStream {
+    sync_stream: Weak<SyncStream>,
+    handle_id: u128,
+    shared: Arc<Shared>,
+}
+
+impl PropertyStream {
+    /// Creates an asynchronous stream of the values sent down the given stream. The buffer holds
+    /// at least one value.
+    pub fn new(sync_stream: &Arc<SyncStream>, capacity: usize, overflow: OverflowPolicy) -> PropertyStream {
+        let capacity = capacity.max(1);
+        let shared = Arc::new(Shared {
+            buffer: Mutex::new(Buffer {
+                values: VecDeque::with_capacity(capacity),
+                dropped: 0,
+                waker: None,
+                producer_closed: false,
+                consumer_closed: false,
+            }),
+            space_available: Condvar::new(),
+            capacity,
+            overflow,
+        });
+        let producer = Producer { shared: shared.clone() };
+        let handle_id = Uuid::new_v4().as_u128();
+        sync_stream.observe_with_handle(move |value| producer.shared.push(value), handle_id);
+        PropertyStream {
+            sync_stream: Arc::downgrade(sync_stream),
+            handle_id,
+            shared,
+        }
+    }
+
+    /// Returns the capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    /// Returns the overflow policy.
+    pub fn overflow(&self) -> OverflowPolicy {
+        self.shared.overflow
+    }
+
+    /// Returns the number of buffered values.
+    pub fn buffered_len(&self) -> usize {
+        self.shared.buffer.lock().unwrap().values.len()
+    }
+
+    /// Returns the number of values which have been dropped because the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        self.shared.buffer.lock().unwrap().dropped
+    }
+}
+
+impl Stream for PropertyStream {
+    type Item = Value;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut buffer = self.shared.buffer.lock().unwrap();
+        if let Some(value) = buffer.values.pop_front() {
+            self.shared.space_available.notify_one();
+            return Poll::Ready(Some(value));
+        }
+        if buffer.producer_closed {
+            return Poll::Ready(None);
+        }
+        buffer.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for PropertyStream {
+    fn drop(&mut self) {
+        self.shared.buffer.lock().unwrap().consumer_closed = true;
+        self.shared.space_available.notify_all();
+        if let Some(sync_stream) = self.sync_stream.upgrade() {
+            sync_stream.remove(self.handle_id);
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::extension::Extension;
 use crate::DataType;
 use crate::ExtensionTypeId;
 use crate::Mutability;
+use crate::PropagationMode;
 use crate::SocketType;
 
 pub static NAMESPACE_PROPERTY_TYPE: Uuid = Uuid::from_u128(0x1ab7c8109dcd11c180b400d02fd540c7);
@@ -15,6 +16,7 @@ pub static NAMESPACE_PROPERTY_TYPE: Uuid = Uuid::from_u128(0x1ab7c8109dcd11c180b
 /// the name of the property, the data type and the socket
 /// type.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PropertyType {
     /// The name of the property
     pub name: String,
@@ -33,8 +35,12 @@ pub struct PropertyType {
     #[serde(default = "Mutability::mutable")]
     pub mutability: Mutability,
 
+    /// Specifies when a new value of the property is sent down the stream.
+    #[serde(default = "PropagationMode::always")]
+    pub propagation: PropagationMode,
+
     /// Property specific extensions
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub extensions: Vec<Extension>,
 }
 
@@ -46,6 +52,7 @@ impl PropertyType {
             data_type,
             socket_type: SocketType::None,
             mutability: Mutability::Mutable,
+            propagation: PropagationMode::Always,
             extensions: Vec::new(),
         }
     }
@@ -57,6 +64,7 @@ impl PropertyType {
             data_type,
             socket_type,
             mutability: Mutability::Mutable,
+            propagation: PropagationMode::Always,
             extensions: Vec::new(),
         }
     }
@@ -68,6 +76,7 @@ impl PropertyType {
             data_type,
             socket_type: SocketType::Input,
             mutability: Mutability::Mutable,
+            propagation: PropagationMode::Always,
             extensions: Vec::new(),
         }
     }
@@ -79,6 +88,7 @@ impl PropertyType {
             data_type,
             socket_type: SocketType::Output,
             mutability: Mutability::Immutable,
+            propagation: PropagationMode::Always,
             extensions: Vec::new(),
         }
     }
@@ -97,6 +107,7 @@ impl PropertyType {
             data_type,
             socket_type,
             mutability,
+            propagation: PropagationMode::Always,
             extensions,
         }
     }
@@ -4,22 +4,33 @@ use std::sync::Arc;
 
 use dashmap::DashMap;
 use dashmap::DashSet;
+use indradb::Vertex;
 use indradb::VertexProperties;
 use serde_json::Map;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::reserved_property::named_properties;
+use crate::reserved_property::reactive_reserved_properties;
+use crate::reserved_property::split_named_properties;
 use crate::BehaviourTypeId;
 use crate::Component;
 use crate::ComponentContainer;
 use crate::ComponentTypeId;
 use crate::EntityInstance;
 use crate::EntityTypeId;
+use crate::ModelError;
 use crate::Mutability;
 use crate::Mutability::Mutable;
 use crate::NamespacedTypeGetter;
+use crate::ObserverSubscription;
+#[cfg(feature = "async")]
+use crate::OverflowPolicy;
+use crate::PropagationMode;
 use crate::PropertyInstanceGetter;
 use crate::PropertyInstanceSetter;
+#[cfg(feature = "async")]
+use crate::PropertyStream;
 use crate::PropertyType;
 use crate::ReactiveBehaviourContainer;
 use crate::ReactiveInstance;
@@ -27,6 +38,10 @@ use crate::ReactivePropertyContainer;
 use crate::ReactivePropertyInstance;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
+use crate::BEHAVIOURS_PROPERTY;
+use crate::COMPONENTS_PROPERTY;
+use crate::DESCRIPTION_PROPERTY;
+use crate::VERSION_PROPERTY;
 
 pub struct ReactiveEntityInstance {
     /// The type definition of the entity type.
@@ -35,6 +50,11 @@ pub struct ReactiveEntityInstance {
     /// The unique identifier of the entity instance.
     pub id: Uuid,
 
+    /// The version of the entity type which the properties of the entity instance conform to.
+    /// A missing version is version 0: the instance has been saved before the type had a version,
+    /// so all migrations of the type are applied to it.
+    pub version: Option<u32>,
+
     /// An optional description of the entity instance.
     pub description: String,
 
@@ -48,7 +68,16 @@ pub struct ReactiveEntityInstance {
     pub behaviours: DashSet<BehaviourTypeId>,
 }
 
-impl ReactiveEntityInstance {}
+impl ReactiveEntityInstance {
+    /// Returns an asynchronous stream of the values of the property with the given name. Returns
+    /// None, if no property with the given name exists.
+    #[cfg(feature = "async")]
+    pub fn property_stream(&self, name: &str, capacity: usize, overflow: OverflowPolicy) -> Option<PropertyStream> {
+        self.properties
+            .get(name)
+            .map(|property_instance| property_instance.property_stream(capacity, overflow))
+    }
+}
 
 impl ReactivePropertyContainer for ReactiveEntityInstance {
     fn tick_checked(&self) {
@@ -76,7 +105,8 @@ impl ReactivePropertyContainer for ReactiveEntityInstance {
     }
 
     fn add_property_by_type(&self, property: &PropertyType) {
-        let property_instance = ReactivePropertyInstance::new(self.id, &property.name, property.mutability, property.data_type.default_value());
+        let mut property_instance = ReactivePropertyInstance::new(self.id, &property.name, property.mutability, property.data_type.default_value());
+        property_instance.set_propagation(property.propagation);
         self.properties.insert(property.name.clone(), property_instance);
     }
 
@@ -85,6 +115,20 @@ impl ReactivePropertyContainer for ReactiveEntityInstance {
         self.properties.retain(|property_name, _| property_name != &name);
     }
 
+    fn subscribe<F>(&self, name: &str, subscriber: F) -> Option<ObserverSubscription>
+    where
+        F: FnMut(&Value) + 'static,
+    {
+        self.properties.get(name).map(|property_instance| property_instance.subscribe(subscriber))
+    }
+
+    fn subscribe_sync<F>(&self, name: &str, subscriber: F) -> Option<ObserverSubscription>
+    where
+        F: FnMut(&Value) + Send + 'static,
+    {
+        self.properties.get(name).map(|property_instance| property_instance.subscribe_sync(subscriber))
+    }
+
     fn observe_with_handle<F>(&self, name: &str, subscriber: F, handle_id: u128)
     where
         F: FnMut(&Value) + 'static,
@@ -147,28 +191,26 @@ impl ReactiveBehaviourContainer for ReactiveEntityInstance {
 }
 
 impl TryFrom<VertexProperties> for ReactiveEntityInstance {
-    type Error = ();
+    type Error = ModelError;
 
     fn try_from(properties: VertexProperties) -> Result<Self, Self::Error> {
         let ty = EntityTypeId::try_from(&properties.vertex.t)?;
         let id = properties.vertex.id;
-        let instance_properties = properties
-            .props
-            .iter()
-            .map(|named_property| {
-                (
-                    named_property.name.to_string(),
-                    ReactivePropertyInstance::new(id, named_property.name.to_string(), Mutable, named_property.value.clone()),
-                )
-            })
+        let (instance_properties, mut reserved) = split_named_properties(properties.props)?;
+        let instance_properties = instance_properties
+            .into_iter()
+            .map(|(name, value)| (name.clone(), ReactivePropertyInstance::new(id, name, Mutable, value)))
             .collect();
+        let components: Vec<ComponentTypeId> = reserved.take(COMPONENTS_PROPERTY)?.unwrap_or_default();
+        let behaviours: Vec<BehaviourTypeId> = reserved.take(BEHAVIOURS_PROPERTY)?.unwrap_or_default();
         Ok(ReactiveEntityInstance {
             ty,
             id,
-            description: String::new(),
+            version: reserved.take(VERSION_PROPERTY)?,
+            description: reserved.take(DESCRIPTION_PROPERTY)?.unwrap_or_default(),
             properties: instance_properties,
-            components: DashSet::new(),
-            behaviours: DashSet::new(),
+            components: components.into_iter().collect(),
+            behaviours: behaviours.into_iter().collect(),
         })
     }
 }
@@ -183,6 +225,7 @@ impl From<EntityInstance> for ReactiveEntityInstance {
         ReactiveEntityInstance {
             ty: instance.ty.clone(),
             id: instance.id,
+            version: instance.version,
             description: instance.description,
             properties,
             components: DashSet::new(),
@@ -201,6 +244,7 @@ impl From<Arc<ReactiveEntityInstance>> for EntityInstance {
         EntityInstance {
             ty: instance.ty.clone(),
             id: instance.id,
+            version: instance.version,
             description: instance.description.clone(),
             properties,
             extensions: Vec::new(),
@@ -208,6 +252,25 @@ impl From<Arc<ReactiveEntityInstance>> for EntityInstance {
     }
 }
 
+impl From<&ReactiveEntityInstance> for Vertex {
+    fn from(instance: &ReactiveEntityInstance) -> Self {
+        Vertex::with_id(instance.id, instance.type_id())
+    }
+}
+
+impl From<&ReactiveEntityInstance> for VertexProperties {
+    fn from(instance: &ReactiveEntityInstance) -> Self {
+        let props = named_properties(
+            instance
+                .properties
+                .iter()
+                .map(|property_instance| (property_instance.key().clone(), property_instance.get())),
+            reactive_reserved_properties(instance.version, &instance.description, &instance.components, &instance.behaviours),
+        );
+        VertexProperties::new(instance.into(), props)
+    }
+}
+
 impl PropertyInstanceGetter for ReactiveEntityInstance {
     fn get<S: Into<String>>(&self, property_name: S) -> Option<Value> {
         self.properties.get(&property_name.into()).map(|p| p.get())
@@ -267,6 +330,38 @@ impl PropertyInstanceSetter for ReactiveEntityInstance {
         }
     }
 
+    fn set_all_checked(&self, properties: Map<String, Value>) {
+        let mut affected_properties = Vec::new();
+        for (property_name, value) in properties {
+            if let Some(instance) = self.properties.get(&property_name) {
+                if instance.mutability == Mutable && instance.set_no_propagate_changed(value) {
+                    affected_properties.push(property_name);
+                }
+            }
+        }
+        for property_name in affected_properties {
+            if let Some(instance) = self.properties.get(&property_name) {
+                instance.tick();
+            }
+        }
+    }
+
+    fn set_all(&self, properties: Map<String, Value>) {
+        let mut affected_properties = Vec::new();
+        for (property_name, value) in properties {
+            if let Some(instance) = self.properties.get(&property_name) {
+                if instance.set_no_propagate_changed(value) {
+                    affected_properties.push(property_name);
+                }
+            }
+        }
+        for property_name in affected_properties {
+            if let Some(instance) = self.properties.get(&property_name) {
+                instance.tick();
+            }
+        }
+    }
+
     fn mutability<S: Into<String>>(&self, property_name: S) -> Option<Mutability> {
         self.properties.get(&property_name.into()).map(|p| p.value().mutability)
     }
@@ -277,8 +372,15 @@ impl PropertyInstanceSetter for ReactiveEntityInstance {
         }
     }
 
-    // TODO: fn set(&self, Map<String, Value>
-    // TODO: Set values transactional: first set all values internally, then send all affected streams
+    fn propagation<S: Into<String>>(&self, property_name: S) -> Option<PropagationMode> {
+        self.properties.get(&property_name.into()).map(|p| p.value().propagation)
+    }
+
+    fn set_propagation<S: Into<String>>(&self, property_name: S, propagation: PropagationMode) {
+        if let Some(mut property_instance) = self.properties.get_mut(&property_name.into()) {
+            property_instance.set_propagation(propagation);
+        }
+    }
 }
 
 impl NamespacedTypeGetter for ReactiveEntityInstance {
@@ -343,7 +445,7 @@ macro_rules! entity_model {
         }
 
         impl $crate::PropertyInstanceGetter for $ident {
-            fn get<S: Into<String>>(&self, property_name: S) -> Option<serde_json::Value> {
+            fn get<S: Into<String>>(&self, property_name: S) -> Option<$crate::serde_json::Value> {
                 self.i.get(property_name)
             }
 
@@ -367,32 +469,40 @@ macro_rules! entity_model {
                 self.i.as_string(property_name)
             }
 
-            fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<serde_json::Value>> {
+            fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<$crate::serde_json::Value>> {
                 self.i.as_array(property_name)
             }
 
-            fn as_object<S: Into<String>>(&self, property_name: S) -> Option<serde_json::Map<String, serde_json::Value>> {
+            fn as_object<S: Into<String>>(&self, property_name: S) -> Option<$crate::serde_json::Map<String, $crate::serde_json::Value>> {
                 self.i.as_object(property_name)
             }
         }
 
         impl $crate::PropertyInstanceSetter for $ident {
-            fn set_checked<S: Into<String>>(&self, property_name: S, value: serde_json::Value) {
+            fn set_checked<S: Into<String>>(&self, property_name: S, value: $crate::serde_json::Value) {
                 self.i.set_checked(property_name, value);
             }
 
-            fn set<S: Into<String>>(&self, property_name: S, value: serde_json::Value) {
+            fn set<S: Into<String>>(&self, property_name: S, value: $crate::serde_json::Value) {
                 self.i.set(property_name, value);
             }
 
-            fn set_no_propagate_checked<S: Into<String>>(&self, property_name: S, value: serde_json::Value) {
+            fn set_no_propagate_checked<S: Into<String>>(&self, property_name: S, value: $crate::serde_json::Value) {
                 self.i.set_no_propagate_checked(property_name, value);
             }
 
-            fn set_no_propagate<S: Into<String>>(&self, property_name: S, value: serde_json::Value) {
+            fn set_no_propagate<S: Into<String>>(&self, property_name: S, value: $crate::serde_json::Value) {
                 self.i.set_no_propagate(property_name, value);
             }
 
+            fn set_all_checked(&self, properties: $crate::serde_json::Map<String, $crate::serde_json::Value>) {
+                self.i.set_all_checked(properties);
+            }
+
+            fn set_all(&self, properties: $crate::serde_json::Map<String, $crate::serde_json::Value>) {
+                self.i.set_all(properties);
+            }
+
             fn mutability<S: Into<String>>(&self, property_name: S) -> Option<$crate::Mutability> {
                 self.i.mutability(property_name)
             }
@@ -400,6 +510,65 @@ macro_rules! entity_model {
             fn set_mutability<S: Into<String>>(&self, property_name: S, mutability: $crate::Mutability) {
                 self.i.set_mutability(property_name, mutability);
             }
+
+            fn propagation<S: Into<String>>(&self, property_name: S) -> Option<$crate::PropagationMode> {
+                self.i.propagation(property_name)
+            }
+
+            fn set_propagation<S: Into<String>>(&self, property_name: S, propagation: $crate::PropagationMode) {
+                self.i.set_propagation(property_name, propagation);
+            }
+        }
+
+        impl $crate::ReactivePropertyContainer for $ident {
+            fn tick_checked(&self) {
+                $crate::ReactivePropertyContainer::tick_checked(self.i.as_ref());
+            }
+
+            fn tick(&self) {
+                $crate::ReactivePropertyContainer::tick(self.i.as_ref());
+            }
+
+            fn has_property(&self, name: &str) -> bool {
+                $crate::ReactivePropertyContainer::has_property(self.i.as_ref(), name)
+            }
+
+            fn add_property<S: Into<String>>(&self, name: S, mutability: $crate::Mutability, value: $crate::serde_json::Value) {
+                $crate::ReactivePropertyContainer::add_property(self.i.as_ref(), name, mutability, value);
+            }
+
+            fn add_property_by_type(&self, property: &$crate::PropertyType) {
+                $crate::ReactivePropertyContainer::add_property_by_type(self.i.as_ref(), property);
+            }
+
+            fn remove_property<S: Into<String>>(&self, name: S) {
+                $crate::ReactivePropertyContainer::remove_property(self.i.as_ref(), name);
+            }
+
+            fn subscribe<F>(&self, name: &str, subscriber: F) -> Option<$crate::ObserverSubscription>
+            where
+                F: FnMut(&$crate::serde_json::Value) + 'static,
+            {
+                $crate::ReactivePropertyContainer::subscribe(self.i.as_ref(), name, subscriber)
+            }
+
+            fn subscribe_sync<F>(&self, name: &str, subscriber: F) -> Option<$crate::ObserverSubscription>
+            where
+                F: FnMut(&$crate::serde_json::Value) + Send + 'static,
+            {
+                $crate::ReactivePropertyContainer::subscribe_sync(self.i.as_ref(), name, subscriber)
+            }
+
+            fn observe_with_handle<F>(&self, name: &str, subscriber: F, handle_id: u128)
+            where
+                F: FnMut(&$crate::serde_json::Value) + 'static,
+            {
+                $crate::ReactivePropertyContainer::observe_with_handle(self.i.as_ref(), name, subscriber, handle_id);
+            }
+
+            fn remove_observer(&self, name: &str, handle_id: u128) {
+                $crate::ReactivePropertyContainer::remove_observer(self.i.as_ref(), name, handle_id);
+            }
         }
 
         impl $crate::NamespacedTypeGetter for $ident {
@@ -6,14 +6,26 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 use std::sync::RwLock;
 
+use crate::EntityInstanceSnapshot;
 use crate::EntityTypeId;
+use crate::FlowChangeEvent;
+use crate::FlowChangeLog;
+use crate::FlowSnapshot;
+use crate::FlowSnapshotRestoreError;
+use crate::ModelError;
 use crate::Mutability;
 use crate::NamespacedTypeGetter;
+use crate::ObserverSubscription;
+use crate::PropagationMode;
 use crate::ReactivePropertyContainer;
+use crate::RelationInstanceSnapshot;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
 
+use indradb::BulkInsertItem;
 use indradb::EdgeKey;
+use indradb::EdgeProperties;
+use indradb::VertexProperties;
 use serde_json::Map;
 use serde_json::Value;
 use uuid::Uuid;
@@ -29,17 +41,19 @@ pub enum ReactiveFlowInstanceConstructionError {
     MissingWrapperInstance,
     MissingOutboundEntityInstance(Uuid),
     MissingInboundEntityInstance(Uuid),
+    InvalidType(ModelError),
 }
 
 impl fmt::Display for ReactiveFlowInstanceConstructionError {
     #[cfg(not(tarpaulin_include))]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
+        match self {
             ReactiveFlowInstanceConstructionError::MissingWrapperInstance => {
                 write!(f, "Missing the wrapper entity instance. Check if an entity instance exists with the same id as the flow id")
             }
             ReactiveFlowInstanceConstructionError::MissingOutboundEntityInstance(id) => write!(f, "The outbound entity instance {} cannot be found", id),
             ReactiveFlowInstanceConstructionError::MissingInboundEntityInstance(id) => write!(f, "The inbound entity instance {} cannot be found", id),
+            ReactiveFlowInstanceConstructionError::InvalidType(error) => write!(f, "The type of an instance is invalid: {}", error),
         }
     }
 }
@@ -59,17 +73,8 @@ pub struct ReactiveFlowInstance {
     /// be contained in other flows.
     pub relation_instances: RwLock<HashMap<EdgeKey, Arc<ReactiveRelationInstance>>>,
 
-    /// List of entities that has been added since creation of the flow.
-    pub entities_added: RwLock<Vec<Uuid>>,
-
-    /// List of entities that has been removed since creation of the flow.
-    pub entities_removed: RwLock<Vec<Uuid>>,
-
-    /// List of relations that has been added since creation of the flow.
-    pub relations_added: RwLock<Vec<EdgeKey>>,
-
-    /// List of relations that has been removed since creation of the flow.
-    pub relations_removed: RwLock<Vec<EdgeKey>>,
+    /// The entities and relations which have been added or removed since creation of the flow.
+    pub change_log: FlowChangeLog,
 }
 
 impl ReactiveFlowInstance {
@@ -81,10 +86,7 @@ impl ReactiveFlowInstance {
             ty: wrapper_entity_instance.ty.clone(),
             entity_instances: RwLock::new(entity_instances),
             relation_instances: RwLock::new(HashMap::new()),
-            entities_added: RwLock::new(Vec::new()),
-            entities_removed: RwLock::new(Vec::new()),
-            relations_added: RwLock::new(Vec::new()),
-            relations_removed: RwLock::new(Vec::new()),
+            change_log: FlowChangeLog::new(),
         }
     }
 
@@ -105,17 +107,35 @@ impl ReactiveFlowInstance {
         self.get_entity(self.id)
     }
 
+    /// Observes the property with the given name of the wrapper entity instance. The observer is
+    /// removed when the returned subscription is dropped.
+    pub fn subscribe<F>(&self, name: &str, subscriber: F) -> Option<ObserverSubscription>
+    where
+        F: FnMut(&Value) + 'static,
+    {
+        self.get_wrapper_entity_instance().and_then(|e| e.subscribe(name, subscriber))
+    }
+
+    /// Observes the property with the given name of the wrapper entity instance using a
+    /// thread-safe observer. The observer is removed when the returned subscription is dropped.
+    pub fn subscribe_sync<F>(&self, name: &str, subscriber: F) -> Option<ObserverSubscription>
+    where
+        F: FnMut(&Value) + Send + 'static,
+    {
+        self.get_wrapper_entity_instance().and_then(|e| e.subscribe_sync(name, subscriber))
+    }
+
     pub fn add_entity(&self, entity_instance: Arc<ReactiveEntityInstance>) {
         if !self.has_entity_by_id(entity_instance.id) {
             self.entity_instances.write().unwrap().insert(entity_instance.id, entity_instance.clone());
-            self.entities_added.write().unwrap().push(entity_instance.id);
-            // self.entities_removed.write().unwrap().remove(entity_instance.id);
+            self.change_log.record(FlowChangeEvent::EntityAdded(entity_instance.id));
         }
     }
 
     pub fn remove_entity(&self, id: Uuid) {
-        self.entity_instances.write().unwrap().remove(&id);
-        self.entities_removed.write().unwrap().push(id);
+        if self.entity_instances.write().unwrap().remove(&id).is_some() {
+            self.change_log.record(FlowChangeEvent::EntityRemoved(id));
+        }
     }
 
     pub fn has_relation(&self, relation_instance: Arc<ReactiveRelationInstance>) -> bool {
@@ -136,13 +156,14 @@ impl ReactiveFlowInstance {
         let edge_key = relation_instance.get_key();
         if !self.has_relation_by_key(&edge_key) {
             self.relation_instances.write().unwrap().insert(edge_key.clone(), relation_instance);
-            self.relations_added.write().unwrap().push(edge_key);
+            self.change_log.record(FlowChangeEvent::RelationAdded(edge_key));
         }
     }
 
     pub fn remove_relation(&self, edge_key: &EdgeKey) {
-        self.relation_instances.write().unwrap().remove(edge_key);
-        self.relations_removed.write().unwrap().push(edge_key.clone());
+        if self.relation_instances.write().unwrap().remove(edge_key).is_some() {
+            self.change_log.record(FlowChangeEvent::RelationRemoved(edge_key.clone()));
+        }
     }
 
     pub fn tick(&self) {
@@ -151,6 +172,202 @@ impl ReactiveFlowInstance {
             entity_instance.tick();
         }
     }
+
+    /// Takes a snapshot of the property values of all entity instances and relation instances of
+    /// the flow.
+    pub fn snapshot(&self) -> FlowSnapshot {
+        let mut entity_instances: Vec<EntityInstanceSnapshot> = self
+            .entity_instances
+            .read()
+            .unwrap()
+            .values()
+            .map(|entity_instance| EntityInstanceSnapshot {
+                id: entity_instance.id,
+                properties: entity_instance.properties.iter().map(|p| (p.key().clone(), p.get())).collect(),
+            })
+            .collect();
+        entity_instances.sort_by_key(|e| e.id);
+        let mut relation_instances: Vec<RelationInstanceSnapshot> = self
+            .relation_instances
+            .read()
+            .unwrap()
+            .values()
+            .map(|relation_instance| RelationInstanceSnapshot {
+                outbound_id: relation_instance.outbound.id,
+                ty: relation_instance.ty.clone(),
+                inbound_id: relation_instance.inbound.id,
+                properties: relation_instance.properties.iter().map(|p| (p.key().clone(), p.get())).collect(),
+            })
+            .collect();
+        relation_instances.sort_by_key(|r| (r.outbound_id, r.ty.to_string(), r.inbound_id));
+        FlowSnapshot {
+            id: self.id,
+            entity_instances,
+            relation_instances,
+        }
+    }
+
+    /// Restores the property values of the given snapshot.
+    ///
+    /// Instances and properties which are not part of the flow anymore are skipped. Instances
+    /// and properties which have been added after the snapshot has been taken are not changed.
+    ///
+    /// If propagate is true, all values are restored first and sent down the streams afterwards
+    /// according to the propagation mode of the properties. Otherwise the values are restored
+    /// silently.
+    pub fn restore(&self, snapshot: &FlowSnapshot, propagate: bool) -> Result<(), FlowSnapshotRestoreError> {
+        if snapshot.id != self.id {
+            return Err(FlowSnapshotRestoreError::FlowMismatch {
+                expected: self.id,
+                actual: snapshot.id,
+            });
+        }
+        // Don't hold the locks of the flow while the observers are called
+        let entity_instances: Vec<(Arc<ReactiveEntityInstance>, &EntityInstanceSnapshot)> = {
+            let reader = self.entity_instances.read().unwrap();
+            snapshot
+                .entity_instances
+                .iter()
+                .filter_map(|e| reader.get(&e.id).map(|entity_instance| (entity_instance.clone(), e)))
+                .collect()
+        };
+        let relation_instances: Vec<(Arc<ReactiveRelationInstance>, &RelationInstanceSnapshot)> = {
+            let reader = self.relation_instances.read().unwrap();
+            snapshot
+                .relation_instances
+                .iter()
+                .filter_map(|r| reader.get(&r.get_key()).map(|relation_instance| (relation_instance.clone(), r)))
+                .collect()
+        };
+        let mut changed_entity_properties = Vec::new();
+        for (entity_instance, entity_snapshot) in entity_instances.iter() {
+            for (name, value) in entity_snapshot.properties.iter() {
+                if let Some(property_instance) = entity_instance.properties.get(name) {
+                    if !propagate {
+                        property_instance.set_no_propagate(value.clone());
+                    } else if property_instance.set_no_propagate_changed(value.clone()) {
+                        changed_entity_properties.push((entity_instance, name));
+                    }
+                }
+            }
+        }
+        let mut changed_relation_properties = Vec::new();
+        for (relation_instance, relation_snapshot) in relation_instances.iter() {
+            for (name, value) in relation_snapshot.properties.iter() {
+                if let Some(property_instance) = relation_instance.properties.get(name) {
+                    if !propagate {
+                        property_instance.set_no_propagate(value.clone());
+                    } else if property_instance.set_no_propagate_changed(value.clone()) {
+                        changed_relation_properties.push((relation_instance, name));
+                    }
+                }
+            }
+        }
+        for (entity_instance, name) in changed_entity_properties {
+            if let Some(property_instance) = entity_instance.properties.get(name) {
+                property_instance.tick();
+            }
+        }
+        for (relation_instance, name) in changed_relation_properties {
+            if let Some(property_instance) = relation_instance.properties.get(name) {
+                property_instance.tick();
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the entity instances of the flow as indradb vertices with properties.
+    ///
+    /// Besides the properties, the description, the components and the behaviours of the entity
+    /// instances are stored as reserved properties (see [`crate::RESERVED_PROPERTY_PREFIX`]).
+    /// Properties whose names are no valid identifiers are stored with a generated identifier and
+    /// their names are stored as reserved property.
+    ///
+    /// Not stored are the mutability and the propagation mode of the properties, because they are
+    /// defined by the types, and the name of the flow, because a reactive flow has no name.
+    pub fn vertex_properties(&self) -> Vec<VertexProperties> {
+        let reader = self.entity_instances.read().unwrap();
+        reader
+            .values()
+            .map(|entity_instance| VertexProperties::from(entity_instance.as_ref()))
+            .collect()
+    }
+
+    /// Returns the relation instances of the flow as indradb edges with properties. The fields of
+    /// the relation instances are stored like the fields of the entity instances, see
+    /// [`ReactiveFlowInstance::vertex_properties`].
+    pub fn edge_properties(&self) -> Vec<EdgeProperties> {
+        let reader = self.relation_instances.read().unwrap();
+        reader
+            .values()
+            .map(|relation_instance| EdgeProperties::from(relation_instance.as_ref()))
+            .collect()
+    }
+
+    /// Returns the items which persist the flow into an indradb datastore using a bulk insert.
+    ///
+    /// All vertices are inserted before the edges and the properties are inserted last.
+    pub fn bulk_insert_items(&self) -> Vec<BulkInsertItem> {
+        let vertex_properties = self.vertex_properties();
+        let edge_properties = self.edge_properties();
+        let mut items: Vec<BulkInsertItem> = vertex_properties.iter().map(|v| BulkInsertItem::Vertex(v.vertex.clone())).collect();
+        items.extend(edge_properties.iter().map(|e| BulkInsertItem::Edge(e.edge.key.clone())));
+        for v in vertex_properties {
+            let id = v.vertex.id;
+            items.extend(v.props.into_iter().map(|p| BulkInsertItem::VertexProperty(id, p.name, p.value)));
+        }
+        for e in edge_properties {
+            let key = e.edge.key;
+            items.extend(e.props.into_iter().map(|p| BulkInsertItem::EdgeProperty(key.clone(), p.name, p.value)));
+        }
+        items
+    }
+
+    /// Constructs the flow with the given id from the vertices and edges loaded from an indradb
+    /// datastore. The vertex with the id of the flow is the wrapper entity instance.
+    ///
+    /// The entity instances and relation instances are restored with the properties, descriptions,
+    /// components and behaviours which have been stored by [`ReactiveFlowInstance::vertex_properties`]
+    /// and [`ReactiveFlowInstance::edge_properties`]. The description of the flow is the description
+    /// of the wrapper entity instance. The restored properties are mutable and are always
+    /// propagated.
+    pub fn try_from_properties(
+        id: Uuid,
+        vertex_properties: Vec<VertexProperties>,
+        edge_properties: Vec<EdgeProperties>,
+    ) -> Result<ReactiveFlowInstance, ReactiveFlowInstanceConstructionError> {
+        let mut entity_instances = HashMap::new();
+        for vertex_properties in vertex_properties {
+            let entity_instance = ReactiveEntityInstance::try_from(vertex_properties).map_err(ReactiveFlowInstanceConstructionError::InvalidType)?;
+            entity_instances.insert(entity_instance.id, Arc::new(entity_instance));
+        }
+        let ty = entity_instances
+            .get(&id)
+            .map(|entity_instance| entity_instance.ty.clone())
+            .ok_or(ReactiveFlowInstanceConstructionError::MissingWrapperInstance)?;
+        let mut relation_instances = HashMap::new();
+        for edge_properties in edge_properties {
+            let key = edge_properties.edge.key.clone();
+            let outbound = entity_instances
+                .get(&key.outbound_id)
+                .cloned()
+                .ok_or(ReactiveFlowInstanceConstructionError::MissingOutboundEntityInstance(key.outbound_id))?;
+            let inbound = entity_instances
+                .get(&key.inbound_id)
+                .cloned()
+                .ok_or(ReactiveFlowInstanceConstructionError::MissingInboundEntityInstance(key.inbound_id))?;
+            let relation_instance = ReactiveRelationInstance::new_from_properties(outbound, inbound, edge_properties)
+                .map_err(ReactiveFlowInstanceConstructionError::InvalidType)?;
+            relation_instances.insert(key, Arc::new(relation_instance));
+        }
+        Ok(ReactiveFlowInstance {
+            id,
+            ty,
+            entity_instances: RwLock::new(entity_instances),
+            relation_instances: RwLock::new(relation_instances),
+            change_log: FlowChangeLog::new(),
+        })
+    }
 }
 
 impl From<Arc<ReactiveEntityInstance>> for ReactiveFlowInstance {
@@ -201,10 +418,7 @@ impl TryFrom<FlowInstance> for ReactiveFlowInstance {
             entity_instances: RwLock::new(entity_instances),
             relation_instances: RwLock::new(relation_instances),
             // wrapper: wrapper.unwrap(),
-            entities_added: RwLock::new(Vec::new()),
-            entities_removed: RwLock::new(Vec::new()),
-            relations_added: RwLock::new(Vec::new()),
-            relations_removed: RwLock::new(Vec::new()),
+            change_log: FlowChangeLog::new(),
         })
     }
 }
@@ -283,6 +497,18 @@ impl PropertyInstanceSetter for ReactiveFlowInstance {
         }
     }
 
+    fn set_all_checked(&self, properties: Map<String, Value>) {
+        if let Some(instance) = self.get_entity(self.id) {
+            instance.set_all_checked(properties);
+        }
+    }
+
+    fn set_all(&self, properties: Map<String, Value>) {
+        if let Some(instance) = self.get_entity(self.id) {
+            instance.set_all(properties);
+        }
+    }
+
     fn mutability<S: Into<String>>(&self, property_name: S) -> Option<Mutability> {
         self.get_entity(self.id)
             .and_then(|instance| instance.properties.get(&property_name.into()).map(|p| p.value().mutability))
@@ -296,8 +522,18 @@ impl PropertyInstanceSetter for ReactiveFlowInstance {
         }
     }
 
-    // TODO: fn set(&self, Map<String, Value>
-    // TODO: Set values transactional: first set all values internally, then send all affected streams
+    fn propagation<S: Into<String>>(&self, property_name: S) -> Option<PropagationMode> {
+        self.get_entity(self.id)
+            .and_then(|instance| instance.properties.get(&property_name.into()).map(|p| p.value().propagation))
+    }
+
+    fn set_propagation<S: Into<String>>(&self, property_name: S, propagation: PropagationMode) {
+        if let Some(instance) = self.get_entity(self.id) {
+            if let Some(mut property_instance) = instance.properties.get_mut(&property_name.into()) {
+                property_instance.set_propagation(propagation);
+            }
+        }
+    }
 }
 
 impl NamespacedTypeGetter for ReactiveFlowInstance {
@@ -1,6 +1,7 @@
 use serde_json::Value;
 
 use crate::Mutability;
+use crate::ObserverSubscription;
 use crate::PropertyType;
 
 pub trait ReactivePropertyContainer {
@@ -22,8 +23,26 @@ pub trait ReactivePropertyContainer {
     /// Removes the reactive property with the given name.
     fn remove_property<S: Into<String>>(&self, name: S);
 
+    /// Observe the stream output flowing out of the stream of the property with the given
+    /// name. The observer is removed when the returned subscription is dropped. Returns None, if
+    /// no property with the given name exists.
+    fn subscribe<F>(&self, name: &str, subscriber: F) -> Option<ObserverSubscription>
+    where
+        F: FnMut(&Value) + 'static;
+
+    /// Observe the stream output flowing out of the stream of the property with the given
+    /// name using a thread-safe observer. The observer is removed when the returned subscription
+    /// is dropped. Returns None, if no property with the given name exists.
+    ///
+    /// The observer is called on the thread which sets or ticks the property.
+    fn subscribe_sync<F>(&self, name: &str, subscriber: F) -> Option<ObserverSubscription>
+    where
+        F: FnMut(&Value) + Send + 'static;
+
     /// Observe the stream output flowing out of the stream of the property with the given
     /// name. The handle_id allows to remove the observer again.
+    ///
+    /// Prefer `subscribe` which removes the observer automatically.
     fn observe_with_handle<F>(&self, name: &str, subscriber: F, handle_id: u128)
     where
         F: FnMut(&Value) + 'static;
@@ -1,6 +1,9 @@
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
 use std::sync::RwLock;
 
 use inexor_rgf_core_frp::Stream;
@@ -8,8 +11,16 @@ use serde_json::Map;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::ChangeHistory;
 use crate::Mutability;
 use crate::Mutability::Mutable;
+use crate::ObserverSubscription;
+#[cfg(feature = "async")]
+use crate::OverflowPolicy;
+use crate::PropagationMode;
+#[cfg(feature = "async")]
+use crate::PropertyStream;
+use crate::SyncStream;
 
 pub struct ReactivePropertyInstance {
     /// Vertex uuid
@@ -21,11 +32,29 @@ pub struct ReactivePropertyInstance {
     /// The property instance is mutable or immutable.
     pub mutability: Mutability,
 
+    /// Specifies when a new value is sent down the stream.
+    pub propagation: PropagationMode,
+
     /// The reactive stream
     pub stream: Arc<RwLock<Stream<'static, Value>>>,
 
+    /// The stream of the thread-safe observers. Values are sent down this stream after they have
+    /// been sent down the reactive stream.
+    pub sync_stream: Arc<SyncStream>,
+
     /// Store the current value
     pub value: RwLock<Value>,
+
+    /// The last value which has been sent down the stream. The propagation mode compares new
+    /// values with this value. Initially, this is the initial value.
+    pub propagated_value: RwLock<Value>,
+
+    /// The history which records the changes of the value.
+    pub history: RwLock<Option<Arc<ChangeHistory>>>,
+
+    /// Serializes setting and sending values, so that values are recorded, stored and sent in the
+    /// same order and the propagation mode compares with the value which has been sent last.
+    pub send_lock: Mutex<()>,
 }
 
 impl ReactivePropertyInstance {
@@ -34,8 +63,13 @@ impl ReactivePropertyInstance {
             id,
             name: name.into(),
             mutability,
+            propagation: PropagationMode::Always,
             stream: Arc::new(RwLock::new(Stream::new())),
+            sync_stream: Arc::new(SyncStream::new()),
+            propagated_value: RwLock::new(value.clone()),
             value: RwLock::new(value),
+            history: RwLock::new(None),
+            send_lock: Mutex::new(()),
         }
     }
 
@@ -49,10 +83,21 @@ impl ReactivePropertyInstance {
         }
     }
 
+    /// Sets the value and sends it down the stream according to the propagation mode.
+    ///
+    /// The value lock is released before the value is sent, so that subscribers and consumers of
+    /// property streams can read the value while the value is being sent. Concurrent setters of
+    /// the property wait until the value has been sent.
     pub fn set(&self, value: Value) {
+        let _send_guard = self.lock_send();
         let mut writer = self.value.write().unwrap();
+        let propagate = self.should_propagate(&value);
+        self.record(writer.deref(), &value, true);
         *writer.deref_mut() = value.clone();
-        self.stream.read().unwrap().send(&value);
+        drop(writer);
+        if propagate {
+            self.propagate(&value);
+        }
     }
 
     pub fn set_no_propagate_checked(&self, value: Value) {
@@ -63,12 +108,53 @@ impl ReactivePropertyInstance {
 
     pub fn set_no_propagate(&self, value: Value) {
         let mut writer = self.value.write().unwrap();
+        self.record(writer.deref(), &value, false);
         *writer.deref_mut() = value;
     }
 
+    /// Sets the value but doesn't send the value down the stream. Returns true, if the value has
+    /// to be sent down the stream later on according to the propagation mode.
+    pub fn set_no_propagate_changed(&self, value: Value) -> bool {
+        let _send_guard = self.lock_send();
+        let mut writer = self.value.write().unwrap();
+        let propagate = self.should_propagate(&value);
+        self.record(writer.deref(), &value, true);
+        *writer.deref_mut() = value;
+        propagate
+    }
+
     /// Send a value down the stream, but does not change the current value
     pub fn send(&self, signal: &Value) {
-        self.stream.read().unwrap().send(signal);
+        let _send_guard = self.lock_send();
+        self.propagate(signal);
+    }
+
+    /// Observes the stream of the property instance. The observer is removed when the returned
+    /// subscription is dropped.
+    pub fn subscribe<F>(&self, subscriber: F) -> ObserverSubscription
+    where
+        F: FnMut(&Value) + 'static,
+    {
+        ObserverSubscription::new(&self.stream, subscriber)
+    }
+
+    /// Observes the stream of the property instance with a thread-safe observer. The observer is
+    /// removed when the returned subscription is dropped.
+    ///
+    /// The observer is called on the thread which sets or ticks the property. See `SyncStream`
+    /// for the guarantees.
+    pub fn subscribe_sync<F>(&self, subscriber: F) -> ObserverSubscription
+    where
+        F: FnMut(&Value) + Send + 'static,
+    {
+        ObserverSubscription::new_sync(&self.sync_stream, subscriber)
+    }
+
+    /// Returns an asynchronous stream of the values of the property instance. The values are
+    /// buffered in a buffer with the given capacity.
+    #[cfg(feature = "async")]
+    pub fn property_stream(&self, capacity: usize, overflow: OverflowPolicy) -> PropertyStream {
+        PropertyStream::new(&self.sync_stream, capacity, overflow)
     }
 
     /// Resend the current value manually if mutable
@@ -81,14 +167,48 @@ impl ReactivePropertyInstance {
     /// Resend the current value manually
     pub fn tick(&self) {
         // println!("tick {}::{}", self.id, self.name);
+        let _send_guard = self.lock_send();
         let value = self.value.read().unwrap().deref().clone();
-        self.stream.read().unwrap().send(&value);
+        self.propagate(&value);
+    }
+
+    /// Sets the history which records the changes of the value. The history is removed if None
+    /// is given.
+    pub fn set_history(&self, history: Option<Arc<ChangeHistory>>) {
+        *self.history.write().unwrap() = history;
+    }
+
+    /// Acquires the send lock. The send lock doesn't guard any data, so the property can still be
+    /// set after an observer has panicked while a value was sent.
+    fn lock_send(&self) -> MutexGuard<'_, ()> {
+        self.send_lock.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sends the value down the streams. The caller holds the send lock.
+    fn propagate(&self, value: &Value) {
+        *self.propagated_value.write().unwrap() = value.clone();
+        self.stream.read().unwrap().send(value);
+        self.sync_stream.send(value);
+    }
+
+    fn should_propagate(&self, value: &Value) -> bool {
+        self.propagation.should_propagate(self.propagated_value.read().unwrap().deref(), value)
+    }
+
+    fn record(&self, old_value: &Value, new_value: &Value, propagate: bool) {
+        if let Some(history) = self.history.read().unwrap().as_ref() {
+            history.record(self.id, &self.name, old_value, new_value, propagate);
+        }
     }
 
     pub fn set_mutability(&mut self, mutability: Mutability) {
         self.mutability = mutability;
     }
 
+    pub fn set_propagation(&mut self, propagation: PropagationMode) {
+        self.propagation = propagation;
+    }
+
     pub fn as_bool(&self) -> Option<bool> {
         self.get().as_bool()
     }
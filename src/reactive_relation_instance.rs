@@ -5,21 +5,32 @@ use std::sync::Arc;
 
 use dashmap::DashMap;
 use dashmap::DashSet;
+use indradb::Edge;
 use indradb::EdgeKey;
 use indradb::EdgeProperties;
 use serde_json::Map;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::reserved_property::named_properties;
+use crate::reserved_property::reactive_reserved_properties;
+use crate::reserved_property::split_named_properties;
 use crate::BehaviourTypeId;
 use crate::Component;
 use crate::ComponentContainer;
 use crate::ComponentTypeId;
+use crate::ModelError;
 use crate::Mutability;
 use crate::Mutability::Mutable;
 use crate::NamespacedTypeGetter;
+use crate::ObserverSubscription;
+#[cfg(feature = "async")]
+use crate::OverflowPolicy;
+use crate::PropagationMode;
 use crate::PropertyInstanceGetter;
 use crate::PropertyInstanceSetter;
+#[cfg(feature = "async")]
+use crate::PropertyStream;
 use crate::PropertyType;
 use crate::ReactiveBehaviourContainer;
 use crate::ReactiveEntityInstance;
@@ -31,6 +42,10 @@ use crate::RelationInstanceTypeId;
 use crate::RelationTypeId;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
+use crate::BEHAVIOURS_PROPERTY;
+use crate::COMPONENTS_PROPERTY;
+use crate::DESCRIPTION_PROPERTY;
+use crate::VERSION_PROPERTY;
 
 /// Reactive instance of a relation in the directed property graph.
 ///
@@ -64,6 +79,11 @@ pub struct ReactiveRelationInstance {
     /// The outbound entity instance.
     pub inbound: Arc<ReactiveEntityInstance>,
 
+    /// The version of the relation type which the properties of the relation instance conform to.
+    /// A missing version is version 0: the instance has been saved before the type had a version,
+    /// so all migrations of the type are applied to it.
+    pub version: Option<u32>,
+
     /// An optional description of the relation instance.
     pub description: String,
 
@@ -77,37 +97,39 @@ pub struct ReactiveRelationInstance {
     pub behaviours: DashSet<BehaviourTypeId>,
 }
 
-#[allow(clippy::result_unit_err)]
 impl ReactiveRelationInstance {
     pub fn new_from_properties(
         outbound: Arc<ReactiveEntityInstance>,
         inbound: Arc<ReactiveEntityInstance>,
         properties: EdgeProperties,
-    ) -> Result<ReactiveRelationInstance, ()> {
+    ) -> Result<ReactiveRelationInstance, ModelError> {
         let ty = RelationInstanceTypeId::try_from(&properties.edge.key.t)?;
+        let (properties, mut reserved) = split_named_properties(properties.props)?;
         let properties = properties
-            .props
-            .iter()
-            .map(|named_property| {
+            .into_iter()
+            .map(|(name, value)| {
                 (
-                    named_property.name.to_string(),
+                    name.clone(),
                     ReactivePropertyInstance::new(
                         Uuid::new_v4(), // or generate a combined uuid from "outbound_id + type + inbound_id"
-                        named_property.name.to_string(),
+                        name,
                         Mutable,
-                        named_property.value.clone(),
+                        value,
                     ),
                 )
             })
             .collect();
+        let components: Vec<ComponentTypeId> = reserved.take(COMPONENTS_PROPERTY)?.unwrap_or_default();
+        let behaviours: Vec<BehaviourTypeId> = reserved.take(BEHAVIOURS_PROPERTY)?.unwrap_or_default();
         Ok(ReactiveRelationInstance {
             outbound,
             ty,
             inbound,
-            description: String::new(),
+            version: reserved.take(VERSION_PROPERTY)?,
+            description: reserved.take(DESCRIPTION_PROPERTY)?.unwrap_or_default(),
             properties,
-            components: DashSet::new(),
-            behaviours: DashSet::new(),
+            components: components.into_iter().collect(),
+            behaviours: behaviours.into_iter().collect(),
         })
     }
 
@@ -126,6 +148,7 @@ impl ReactiveRelationInstance {
             outbound,
             ty: instance.ty,
             inbound,
+            version: instance.version,
             description: instance.description,
             properties,
             components: DashSet::new(),
@@ -160,6 +183,7 @@ impl ReactiveRelationInstance {
             outbound,
             ty,
             inbound,
+            version: None,
             description: String::new(),
             properties,
             components: DashSet::new(),
@@ -180,6 +204,13 @@ impl ReactiveRelationInstance {
     pub fn get_key(&self) -> EdgeKey {
         EdgeKey::new(self.outbound.id, self.type_id(), self.inbound.id)
     }
+
+    /// Returns an asynchronous stream of the values of the property with the given name. Returns
+    /// None, if no property with the given name exists.
+    #[cfg(feature = "async")]
+    pub fn property_stream(&self, name: &str, capacity: usize, overflow: OverflowPolicy) -> Option<PropertyStream> {
+        self.properties.get(name).map(|property| property.property_stream(capacity, overflow))
+    }
 }
 
 impl ReactivePropertyContainer for ReactiveRelationInstance {
@@ -208,7 +239,8 @@ impl ReactivePropertyContainer for ReactiveRelationInstance {
     }
 
     fn add_property_by_type(&self, property: &PropertyType) {
-        let property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), &property.name, property.mutability, property.data_type.default_value());
+        let mut property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), &property.name, property.mutability, property.data_type.default_value());
+        property_instance.set_propagation(property.propagation);
         self.properties.insert(property.name.clone(), property_instance);
     }
 
@@ -217,6 +249,20 @@ impl ReactivePropertyContainer for ReactiveRelationInstance {
         self.properties.retain(|property_name, _| property_name != &name);
     }
 
+    fn subscribe<F>(&self, name: &str, subscriber: F) -> Option<ObserverSubscription>
+    where
+        F: FnMut(&Value) + 'static,
+    {
+        self.properties.get(name).map(|property| property.subscribe(subscriber))
+    }
+
+    fn subscribe_sync<F>(&self, name: &str, subscriber: F) -> Option<ObserverSubscription>
+    where
+        F: FnMut(&Value) + Send + 'static,
+    {
+        self.properties.get(name).map(|property| property.subscribe_sync(subscriber))
+    }
+
     fn observe_with_handle<F>(&self, name: &str, subscriber: F, handle_id: u128)
     where
         F: FnMut(&Value) + 'static,
@@ -289,6 +335,7 @@ impl From<Arc<ReactiveRelationInstance>> for RelationInstance {
             outbound_id: instance.outbound.id,
             ty: instance.ty.clone(),
             inbound_id: instance.inbound.id,
+            version: instance.version,
             description: instance.description.clone(),
             properties,
             extensions: Vec::new(),
@@ -296,6 +343,25 @@ impl From<Arc<ReactiveRelationInstance>> for RelationInstance {
     }
 }
 
+impl From<&ReactiveRelationInstance> for EdgeKey {
+    fn from(instance: &ReactiveRelationInstance) -> Self {
+        instance.get_key()
+    }
+}
+
+impl From<&ReactiveRelationInstance> for EdgeProperties {
+    fn from(instance: &ReactiveRelationInstance) -> Self {
+        let props = named_properties(
+            instance
+                .properties
+                .iter()
+                .map(|property_instance| (property_instance.key().clone(), property_instance.get())),
+            reactive_reserved_properties(instance.version, &instance.description, &instance.components, &instance.behaviours),
+        );
+        EdgeProperties::new(Edge::new_with_current_datetime(instance.get_key()), props)
+    }
+}
+
 impl PropertyInstanceGetter for ReactiveRelationInstance {
     fn get<S: Into<String>>(&self, property_name: S) -> Option<Value> {
         self.properties.get(&property_name.into()).map(|p| p.get())
@@ -355,6 +421,38 @@ impl PropertyInstanceSetter for ReactiveRelationInstance {
         }
     }
 
+    fn set_all_checked(&self, properties: Map<String, Value>) {
+        let mut affected_properties = Vec::new();
+        for (property_name, value) in properties {
+            if let Some(instance) = self.properties.get(&property_name) {
+                if instance.mutability == Mutable && instance.set_no_propagate_changed(value) {
+                    affected_properties.push(property_name);
+                }
+            }
+        }
+        for property_name in affected_properties {
+            if let Some(instance) = self.properties.get(&property_name) {
+                instance.tick();
+            }
+        }
+    }
+
+    fn set_all(&self, properties: Map<String, Value>) {
+        let mut affected_properties = Vec::new();
+        for (property_name, value) in properties {
+            if let Some(instance) = self.properties.get(&property_name) {
+                if instance.set_no_propagate_changed(value) {
+                    affected_properties.push(property_name);
+                }
+            }
+        }
+        for property_name in affected_properties {
+            if let Some(instance) = self.properties.get(&property_name) {
+                instance.tick();
+            }
+        }
+    }
+
     fn mutability<S: Into<String>>(&self, property_name: S) -> Option<Mutability> {
         self.properties.get(&property_name.into()).map(|p| p.value().mutability)
     }
@@ -364,6 +462,16 @@ impl PropertyInstanceSetter for ReactiveRelationInstance {
             property_instance.set_mutability(mutability);
         }
     }
+
+    fn propagation<S: Into<String>>(&self, property_name: S) -> Option<PropagationMode> {
+        self.properties.get(&property_name.into()).map(|p| p.value().propagation)
+    }
+
+    fn set_propagation<S: Into<String>>(&self, property_name: S, propagation: PropagationMode) {
+        if let Some(mut property_instance) = self.properties.get_mut(&property_name.into()) {
+            property_instance.set_propagation(propagation);
+        }
+    }
 }
 
 impl NamespacedTypeGetter for ReactiveRelationInstance {
@@ -428,7 +536,7 @@ macro_rules! relation_model {
         }
 
         impl $crate::PropertyInstanceGetter for $ident {
-            fn get<S: Into<String>>(&self, property_name: S) -> Option<serde_json::Value> {
+            fn get<S: Into<String>>(&self, property_name: S) -> Option<$crate::serde_json::Value> {
                 self.i.get(property_name)
             }
 
@@ -452,32 +560,40 @@ macro_rules! relation_model {
                 self.i.as_string(property_name)
             }
 
-            fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<serde_json::Value>> {
+            fn as_array<S: Into<String>>(&self, property_name: S) -> Option<Vec<$crate::serde_json::Value>> {
                 self.i.as_array(property_name)
             }
 
-            fn as_object<S: Into<String>>(&self, property_name: S) -> Option<serde_json::Map<String, serde_json::Value>> {
+            fn as_object<S: Into<String>>(&self, property_name: S) -> Option<$crate::serde_json::Map<String, $crate::serde_json::Value>> {
                 self.i.as_object(property_name)
             }
         }
 
         impl $crate::PropertyInstanceSetter for $ident {
-            fn set_checked<S: Into<String>>(&self, property_name: S, value: serde_json::Value) {
+            fn set_checked<S: Into<String>>(&self, property_name: S, value: $crate::serde_json::Value) {
                 self.i.set_checked(property_name, value);
             }
 
-            fn set<S: Into<String>>(&self, property_name: S, value: serde_json::Value) {
+            fn set<S: Into<String>>(&self, property_name: S, value: $crate::serde_json::Value) {
                 self.i.set(property_name, value);
             }
 
-            fn set_no_propagate_checked<S: Into<String>>(&self, property_name: S, value: serde_json::Value) {
+            fn set_no_propagate_checked<S: Into<String>>(&self, property_name: S, value: $crate::serde_json::Value) {
                 self.i.set_no_propagate_checked(property_name, value);
             }
 
-            fn set_no_propagate<S: Into<String>>(&self, property_name: S, value: serde_json::Value) {
+            fn set_no_propagate<S: Into<String>>(&self, property_name: S, value: $crate::serde_json::Value) {
                 self.i.set_no_propagate(property_name, value);
             }
 
+            fn set_all_checked(&self, properties: $crate::serde_json::Map<String, $crate::serde_json::Value>) {
+                self.i.set_all_checked(properties);
+            }
+
+            fn set_all(&self, properties: $crate::serde_json::Map<String, $crate::serde_json::Value>) {
+                self.i.set_all(properties);
+            }
+
             fn mutability<S: Into<String>>(&self, property_name: S) -> Option<$crate::Mutability> {
                 self.i.mutability(property_name)
             }
@@ -485,6 +601,65 @@ macro_rules! relation_model {
             fn set_mutability<S: Into<String>>(&self, property_name: S, mutability: $crate::Mutability) {
                 self.i.set_mutability(property_name, mutability);
             }
+
+            fn propagation<S: Into<String>>(&self, property_name: S) -> Option<$crate::PropagationMode> {
+                self.i.propagation(property_name)
+            }
+
+            fn set_propagation<S: Into<String>>(&self, property_name: S, propagation: $crate::PropagationMode) {
+                self.i.set_propagation(property_name, propagation);
+            }
+        }
+
+        impl $crate::ReactivePropertyContainer for $ident {
+            fn tick_checked(&self) {
+                $crate::ReactivePropertyContainer::tick_checked(self.i.as_ref());
+            }
+
+            fn tick(&self) {
+                $crate::ReactivePropertyContainer::tick(self.i.as_ref());
+            }
+
+            fn has_property(&self, name: &str) -> bool {
+                $crate::ReactivePropertyContainer::has_property(self.i.as_ref(), name)
+            }
+
+            fn add_property<S: Into<String>>(&self, name: S, mutability: $crate::Mutability, value: $crate::serde_json::Value) {
+                $crate::ReactivePropertyContainer::add_property(self.i.as_ref(), name, mutability, value);
+            }
+
+            fn add_property_by_type(&self, property: &$crate::PropertyType) {
+                $crate::ReactivePropertyContainer::add_property_by_type(self.i.as_ref(), property);
+            }
+
+            fn remove_property<S: Into<String>>(&self, name: S) {
+                $crate::ReactivePropertyContainer::remove_property(self.i.as_ref(), name);
+            }
+
+            fn subscribe<F>(&self, name: &str, subscriber: F) -> Option<$crate::ObserverSubscription>
+            where
+                F: FnMut(&$crate::serde_json::Value) + 'static,
+            {
+                $crate::ReactivePropertyContainer::subscribe(self.i.as_ref(), name, subscriber)
+            }
+
+            fn subscribe_sync<F>(&self, name: &str, subscriber: F) -> Option<$crate::ObserverSubscription>
+            where
+                F: FnMut(&$crate::serde_json::Value) + Send + 'static,
+            {
+                $crate::ReactivePropertyContainer::subscribe_sync(self.i.as_ref(), name, subscriber)
+            }
+
+            fn observe_with_handle<F>(&self, name: &str, subscriber: F, handle_id: u128)
+            where
+                F: FnMut(&$crate::serde_json::Value) + 'static,
+            {
+                $crate::ReactivePropertyContainer::observe_with_handle(self.i.as_ref(), name, subscriber, handle_id);
+            }
+
+            fn remove_observer(&self, name: &str, handle_id: u128) {
+                $crate::ReactivePropertyContainer::remove_observer(self.i.as_ref(), name, handle_id);
+            }
         }
 
         impl $crate::NamespacedTypeGetter for $ident {
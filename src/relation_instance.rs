@@ -2,24 +2,38 @@ use std::collections::HashMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use indradb::Edge;
 use indradb::EdgeKey;
 use indradb::EdgeProperties;
 use serde::Deserialize;
 use serde::Serialize;
+use serde_json::json;
 use serde_json::Map;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::reserved_property::named_properties;
+use crate::reserved_property::split_named_properties;
+use crate::type_migration::migrate_properties;
+use crate::validation::validate_properties;
+use crate::ComponentLookup;
 use crate::Extension;
 use crate::ExtensionContainer;
 use crate::ExtensionTypeId;
+use crate::MigrationError;
+use crate::ModelError;
 use crate::MutablePropertyInstanceSetter;
 use crate::NamespacedTypeGetter;
 use crate::PropertyInstanceGetter;
 use crate::RelationInstanceTypeId;
+use crate::RelationType;
 use crate::RelationTypeId;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
+use crate::ValidationViolation;
+use crate::DESCRIPTION_PROPERTY;
+use crate::EXTENSIONS_PROPERTY;
+use crate::VERSION_PROPERTY;
 
 /// Relation instances are edges from an outbound entity instance to an
 /// inbound entity instance.
@@ -31,7 +45,8 @@ use crate::TypeDefinitionGetter;
 ///
 /// In contrast to the relation type, the relation instance stores values/
 /// documents in it's properties.
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RelationInstance {
     /// The id of the outbound vertex.
     pub outbound_id: Uuid,
@@ -43,6 +58,12 @@ pub struct RelationInstance {
     /// The id of the inbound vertex.
     pub inbound_id: Uuid,
 
+    /// The version of the relation type which the properties of the relation instance conform to.
+    /// A missing version is version 0: the instance has been saved before the type had a version,
+    /// so all migrations of the type are applied to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+
     /// Textual description of the relation instance.
     #[serde(default = "String::new")]
     pub description: String,
@@ -53,11 +74,11 @@ pub struct RelationInstance {
     /// a representation of a JSON. Therefore the value can be boolean, number, string,
     /// array or an object. For more information about the data types please look at
     /// https://docs.serde.rs/serde_json/value/enum.Value.html
-    #[serde(default = "HashMap::new")]
+    #[serde(default)]
     pub properties: HashMap<String, Value>,
 
     /// Relation instance specific extensions.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub extensions: Vec<Extension>,
 }
 
@@ -68,6 +89,7 @@ impl RelationInstance {
             outbound_id,
             ty: ty.into(),
             inbound_id,
+            version: None,
             description: String::new(),
             properties,
             extensions: Vec::new(),
@@ -86,6 +108,7 @@ impl RelationInstance {
             outbound_id,
             ty: RelationInstanceTypeId::new_from_type_unique_id(namespace, type_name),
             inbound_id,
+            version: None,
             description: String::new(),
             properties,
             extensions: Vec::new(),
@@ -105,6 +128,7 @@ impl RelationInstance {
             outbound_id,
             ty: RelationInstanceTypeId::new_from_type_unique_for_instance_id(namespace, type_name, instance_id),
             inbound_id,
+            version: None,
             description: String::new(),
             properties,
             extensions: Vec::new(),
@@ -123,6 +147,7 @@ impl RelationInstance {
             outbound_id,
             ty: RelationInstanceTypeId::new_from_type_with_random_instance_id(namespace, type_name),
             inbound_id,
+            version: None,
             description: String::new(),
             properties,
             extensions: Vec::new(),
@@ -135,6 +160,7 @@ impl RelationInstance {
             outbound_id,
             ty: ty.into(),
             inbound_id,
+            version: None,
             description: String::new(),
             properties: HashMap::new(),
             extensions: Vec::new(),
@@ -155,24 +181,117 @@ impl RelationInstance {
     pub fn get_key(&self) -> EdgeKey {
         EdgeKey::new(self.outbound_id, self.type_id(), self.inbound_id)
     }
+
+    /// Validates the relation instance against the given relation type.
+    ///
+    /// The components of the relation type are resolved using the given component lookup. Returns
+    /// all violations of the schema of the relation type. The values of immutable outputs are not
+    /// checked because they may have been computed by behaviours, see
+    /// [`validate_import`](Self::validate_import).
+    pub fn validate<L: ComponentLookup + ?Sized>(&self, relation_type: &RelationType, components: &L) -> Result<(), Vec<ValidationViolation>> {
+        self.validate_with(relation_type, components, false)
+    }
+
+    /// Validates the relation instance against the given relation type like [`validate`](Self::validate)
+    /// and additionally reports immutable outputs which contain another value than the default
+    /// value of their data type.
+    ///
+    /// Use this for instances which haven't been processed by behaviours yet, like imported
+    /// instances. The values of immutable outputs are computed by behaviours, so imports have to
+    /// contain the default values of immutable outputs.
+    pub fn validate_import<L: ComponentLookup + ?Sized>(&self, relation_type: &RelationType, components: &L) -> Result<(), Vec<ValidationViolation>> {
+        self.validate_with(relation_type, components, true)
+    }
+
+    fn validate_with<L: ComponentLookup + ?Sized>(
+        &self,
+        relation_type: &RelationType,
+        components: &L,
+        check_immutable_outputs: bool,
+    ) -> Result<(), Vec<ValidationViolation>> {
+        let mut violations = Vec::new();
+        if self.relation_type_id() != relation_type.ty {
+            violations.push(ValidationViolation::TypeMismatch {
+                expected: relation_type.type_definition(),
+                actual: self.relation_type_id().type_definition(),
+            });
+        }
+        violations.append(&mut validate_properties(
+            &relation_type.properties,
+            &relation_type.components,
+            components,
+            &self.properties,
+            check_immutable_outputs,
+        ));
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Migrates the properties of the relation instance from its version to the version of the
+    /// given relation type. The components of the relation type are resolved using the given
+    /// component lookup. The relation instance is only changed if all migrations succeed.
+    pub fn migrate<L: ComponentLookup + ?Sized>(&mut self, relation_type: &RelationType, components: &L) -> Result<(), MigrationError> {
+        if self.relation_type_id() != relation_type.ty {
+            return Err(MigrationError::TypeMismatch {
+                expected: relation_type.type_definition(),
+                actual: self.relation_type_id().type_definition(),
+            });
+        }
+        migrate_properties(
+            relation_type.version,
+            &relation_type.migrations,
+            &relation_type.properties,
+            &relation_type.components,
+            components,
+            self.version.unwrap_or_default(),
+            &mut self.properties,
+        )?;
+        self.version = Some(relation_type.version);
+        Ok(())
+    }
 }
 
 impl TryFrom<EdgeProperties> for RelationInstance {
-    type Error = ();
+    type Error = ModelError;
 
     fn try_from(properties: EdgeProperties) -> Result<Self, Self::Error> {
         let ty = RelationInstanceTypeId::try_from(&properties.edge.key.t)?;
+        let (instance_properties, mut reserved) = split_named_properties(properties.props)?;
         Ok(RelationInstance {
             outbound_id: properties.edge.key.outbound_id,
             ty,
             inbound_id: properties.edge.key.inbound_id,
-            description: String::new(),
-            properties: properties.props.iter().map(|p| (p.name.to_string(), p.value.clone())).collect(),
-            extensions: Vec::new(),
+            version: reserved.take(VERSION_PROPERTY)?,
+            description: reserved.take(DESCRIPTION_PROPERTY)?.unwrap_or_default(),
+            properties: instance_properties,
+            extensions: reserved.take(EXTENSIONS_PROPERTY)?.unwrap_or_default(),
         })
     }
 }
 
+impl From<&RelationInstance> for EdgeKey {
+    fn from(instance: &RelationInstance) -> Self {
+        instance.get_key()
+    }
+}
+
+impl From<&RelationInstance> for EdgeProperties {
+    fn from(instance: &RelationInstance) -> Self {
+        let props = named_properties(
+            instance.properties.iter().map(|(name, value)| (name.clone(), value.clone())),
+            vec![
+                (VERSION_PROPERTY, instance.version.map(|version| json!(version))),
+                (DESCRIPTION_PROPERTY, (!instance.description.is_empty()).then(|| json!(instance.description))),
+                (EXTENSIONS_PROPERTY, (!instance.extensions.is_empty()).then(|| json!(instance.extensions))),
+            ],
+        );
+        EdgeProperties::new(Edge::new_with_current_datetime(instance.get_key()), props)
+    }
+}
+
 impl PropertyInstanceGetter for RelationInstance {
     fn get<S: Into<String>>(&self, property_name: S) -> Option<Value> {
         self.properties.get(&property_name.into()).cloned()
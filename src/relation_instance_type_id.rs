@@ -5,6 +5,7 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use uuid::Uuid;
 
+use crate::ModelError;
 use crate::NamespacedType;
 use crate::NamespacedTypeGetter;
 use crate::RelationTypeId;
@@ -15,6 +16,7 @@ use crate::TYPE_ID_TYPE_SEPARATOR;
 
 /// Type identifier of a relation instance.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RelationInstanceTypeId {
     /// The type definition of the relation type.
     #[serde(flatten)]
@@ -127,55 +129,39 @@ impl From<&RelationInstanceTypeId> for NamespacedType {
 }
 
 impl TryFrom<&Identifier> for RelationInstanceTypeId {
-    type Error = ();
+    type Error = ModelError;
 
     fn try_from(t: &Identifier) -> Result<Self, Self::Error> {
-        let s = t.to_string();
-        let mut s = s.splitn(4, &TYPE_ID_TYPE_SEPARATOR);
-        let type_id_type = s.next().ok_or(())?.try_into()?;
-        if TypeIdType::RelationType == type_id_type {
-            let namespace = s.next().ok_or(())?;
-            if namespace.is_empty() {
-                return Err(());
-            }
-            let type_name = s.next().ok_or(())?;
-            if type_name.is_empty() {
-                return Err(());
-            }
-            let rty = RelationTypeId::new_from_type(namespace, type_name);
-            let ty = match s.next() {
-                Some(instance_id) => RelationInstanceTypeId::new_unique_for_instance_id(rty, instance_id),
-                None => RelationInstanceTypeId::new_unique_id(rty),
-            };
-            return Ok(ty);
-        }
-        Err(())
+        RelationInstanceTypeId::try_from(&t.to_string())
     }
 }
 
 impl TryFrom<&String> for RelationInstanceTypeId {
-    type Error = ();
-
-    fn try_from(s: &String) -> Result<Self, Self::Error> {
-        let mut s = s.splitn(4, &TYPE_ID_TYPE_SEPARATOR);
-        let type_id_type = s.next().ok_or(())?.try_into()?;
-        if TypeIdType::RelationType == type_id_type {
-            let namespace = s.next().ok_or(())?;
-            if namespace.is_empty() {
-                return Err(());
-            }
-            let type_name = s.next().ok_or(())?;
-            if type_name.is_empty() {
-                return Err(());
-            }
-            let rty = RelationTypeId::new_from_type(namespace, type_name);
-            let ty = match s.next() {
-                Some(instance_id) => RelationInstanceTypeId::new_unique_for_instance_id(rty, instance_id),
-                None => RelationInstanceTypeId::new_unique_id(rty),
-            };
-            return Ok(ty);
+    type Error = ModelError;
+
+    fn try_from(type_id: &String) -> Result<Self, Self::Error> {
+        let mut s = type_id.splitn(4, &TYPE_ID_TYPE_SEPARATOR);
+        let type_id_type: TypeIdType = s.next().unwrap_or_default().try_into()?;
+        if TypeIdType::RelationType != type_id_type {
+            return Err(ModelError::TypeIdTypeMismatch {
+                expected: TypeIdType::RelationType,
+                actual: type_id_type,
+            });
+        }
+        let namespace = s.next().ok_or_else(|| ModelError::MissingSeparator(type_id.clone()))?;
+        if namespace.is_empty() {
+            return Err(ModelError::EmptyNamespace(type_id.clone()));
+        }
+        let type_name = s.next().ok_or_else(|| ModelError::MissingSeparator(type_id.clone()))?;
+        if type_name.is_empty() {
+            return Err(ModelError::EmptyTypeName(type_id.clone()));
         }
-        Err(())
+        let rty = RelationTypeId::new_from_type(namespace, type_name);
+        let ty = match s.next() {
+            Some(instance_id) => RelationInstanceTypeId::new_unique_for_instance_id(rty, instance_id),
+            None => RelationInstanceTypeId::new_unique_id(rty),
+        };
+        Ok(ty)
     }
 }
 
@@ -2,8 +2,11 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::extension::Extension;
+use crate::resolve_effective_properties;
+use crate::ComponentLookup;
 use crate::ComponentOrEntityTypeId;
 use crate::ComponentTypeId;
+use crate::EffectiveProperties;
 use crate::ExtensionContainer;
 use crate::ExtensionTypeId;
 use crate::NamespacedTypeGetter;
@@ -13,12 +16,14 @@ use crate::TypeContainer;
 use crate::TypeDefinition;
 use crate::TypeDefinitionGetter;
 use crate::TypeIdType;
+use crate::TypeMigration;
 
 /// A relation type defines the type of an relation instance.
 ///
 /// The relation type defines the entity types of the outbound and inbound entity instances.
 /// Also the relation type defines the properties of the relation instance.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RelationType {
     /// The outbound component or entity type.
     #[serde(rename = "outbound", alias = "outbound")]
@@ -32,20 +37,28 @@ pub struct RelationType {
     #[serde(rename = "inbound", alias = "inbound")]
     pub inbound_type: ComponentOrEntityTypeId,
 
+    /// The version of the relation type. The version is increased when the relation type changes.
+    #[serde(default)]
+    pub version: u32,
+
     /// Textual description of the relation type.
     #[serde(default = "String::new")]
     pub description: String,
 
     /// The names of the components of the relation type.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub components: Vec<ComponentTypeId>,
 
     /// The properties which are defined by the relation type.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub properties: Vec<PropertyType>,
 
+    /// The migrations of instances which have been saved against older versions of the relation type.
+    #[serde(default)]
+    pub migrations: Vec<TypeMigration>,
+
     /// Relation type specific extensions.
-    #[serde(default = "Vec::new")]
+    #[serde(default)]
     pub extensions: Vec<Extension>,
 }
 
@@ -65,12 +78,20 @@ impl RelationType {
             outbound_type: outbound_type.into(),
             ty,
             inbound_type: inbound_type.into(),
+            version: 0,
             description: description.into(),
             components,
             properties,
+            migrations: Vec::new(),
             extensions,
         }
     }
+
+    /// Returns the effective properties of the relation type: the own properties and the properties of
+    /// the components which are resolved using the given component lookup.
+    pub fn effective_properties<L: ComponentLookup + ?Sized>(&self, components: &L) -> EffectiveProperties {
+        resolve_effective_properties(&self.properties, &self.components, components)
+    }
 }
 
 impl TypeContainer for RelationType {
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use dashmap::DashSet;
+use indradb::Identifier;
+use indradb::NamedProperty;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::property_identifier;
+use crate::BehaviourTypeId;
+use crate::ComponentTypeId;
+use crate::ModelError;
+
+/// The prefix of the names of the reserved vertex and edge properties.
+///
+/// Reserved properties store the fields of instances which are not properties of the instances.
+/// Properties of instances whose names start with the prefix are stored with a generated
+/// identifier (see [`property_identifier`]), so they cannot collide with reserved properties.
+pub const RESERVED_PROPERTY_PREFIX: &str = "__";
+
+/// The reserved property which stores the version of the type which the properties of an instance
+/// conform to.
+pub const VERSION_PROPERTY: &str = "__version";
+
+/// The reserved property which stores the description of an instance.
+pub const DESCRIPTION_PROPERTY: &str = "__description";
+
+/// The reserved property which stores the extensions of an instance.
+pub const EXTENSIONS_PROPERTY: &str = "__extensions";
+
+/// The reserved property which stores the components of a reactive instance.
+pub const COMPONENTS_PROPERTY: &str = "__components";
+
+/// The reserved property which stores the behaviours of a reactive instance.
+pub const BEHAVIOURS_PROPERTY: &str = "__behaviours";
+
+/// The reserved property which maps generated property identifiers to the names of the
+/// properties.
+pub const PROPERTY_NAMES_PROPERTY: &str = "__property_names";
+
+/// Returns true, if the given property name starts with the prefix of the reserved properties.
+pub fn has_reserved_prefix(property_name: &str) -> bool {
+    property_name.starts_with(RESERVED_PROPERTY_PREFIX)
+}
+
+/// Converts the properties of an instance and the given reserved properties into vertex or edge
+/// properties. The names of properties which are stored with a generated identifier are stored in
+/// a reserved property. Reserved properties without value are skipped.
+pub(crate) fn named_properties<I: IntoIterator<Item = (String, Value)>>(properties: I, reserved: Vec<(&str, Option<Value>)>) -> Vec<NamedProperty> {
+    let mut property_names = Map::new();
+    let mut named_properties: Vec<NamedProperty> = properties
+        .into_iter()
+        .map(|(name, value)| {
+            let identifier = property_identifier(&name);
+            if identifier.as_str() != name {
+                property_names.insert(identifier.to_string(), Value::String(name));
+            }
+            NamedProperty::new(identifier, value)
+        })
+        .collect();
+    if !property_names.is_empty() {
+        named_properties.push(NamedProperty::new(reserved_identifier(PROPERTY_NAMES_PROPERTY), Value::Object(property_names)));
+    }
+    for (name, value) in reserved {
+        if let Some(value) = value {
+            named_properties.push(NamedProperty::new(reserved_identifier(name), value));
+        }
+    }
+    named_properties
+}
+
+/// Returns the reserved properties of a reactive instance: its version, its description, its
+/// components and its behaviours.
+pub(crate) fn reactive_reserved_properties(
+    version: Option<u32>,
+    description: &str,
+    components: &DashSet<ComponentTypeId>,
+    behaviours: &DashSet<BehaviourTypeId>,
+) -> Vec<(&'static str, Option<Value>)> {
+    let mut components: Vec<ComponentTypeId> = components.iter().map(|ty| ty.key().clone()).collect();
+    components.sort_by_key(|ty| ty.to_string());
+    let mut behaviours: Vec<BehaviourTypeId> = behaviours.iter().map(|ty| ty.key().clone()).collect();
+    behaviours.sort_by_key(|ty| ty.to_string());
+    vec![
+        (VERSION_PROPERTY, version.map(|version| json!(version))),
+        (DESCRIPTION_PROPERTY, (!description.is_empty()).then(|| Value::String(description.to_string()))),
+        (COMPONENTS_PROPERTY, (!components.is_empty()).then(|| json!(components))),
+        (BEHAVIOURS_PROPERTY, (!behaviours.is_empty()).then(|| json!(behaviours))),
+    ]
+}
+
+/// Separates the given vertex or edge properties into the properties of an instance and the
+/// reserved properties. The names of properties which have been stored with a generated
+/// identifier are restored.
+pub(crate) fn split_named_properties(named_properties: Vec<NamedProperty>) -> Result<(HashMap<String, Value>, ReservedProperties), ModelError> {
+    let mut properties = HashMap::new();
+    let mut reserved = ReservedProperties(HashMap::new());
+    for named_property in named_properties {
+        let name = named_property.name.to_string();
+        if has_reserved_prefix(&name) {
+            reserved.0.insert(name, named_property.value);
+        } else {
+            properties.insert(name, named_property.value);
+        }
+    }
+    let property_names: HashMap<String, String> = reserved.take(PROPERTY_NAMES_PROPERTY)?.unwrap_or_default();
+    for (identifier, name) in property_names {
+        if let Some(value) = properties.remove(&identifier) {
+            properties.insert(name, value);
+        }
+    }
+    Ok((properties, reserved))
+}
+
+/// The reserved properties of a vertex or an edge.
+pub(crate) struct ReservedProperties(HashMap<String, Value>);
+
+impl ReservedProperties {
+    /// Removes the reserved property with the given name and deserializes its value.
+    pub(crate) fn take<T: DeserializeOwned>(&mut self, property_name: &str) -> Result<Option<T>, ModelError> {
+        match self.0.remove(property_name) {
+            Some(value) => serde_json::from_value(value).map(Some).map_err(|e| ModelError::InvalidProperty {
+                property_name: property_name.to_string(),
+                reason: e.to_string(),
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Returns the identifier of a reserved property.
+fn reserved_identifier(property_name: &str) -> Identifier {
+    Identifier::new(property_name).unwrap()
+}
@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io;
+use std::path::PathBuf;
+
+use crate::SerializationFormat;
+
+/// Errors which can occur while loading or saving types and instances.
+#[derive(Debug)]
+pub enum SerializationError {
+    /// The file cannot be read or written.
+    Io { path: PathBuf, error: io::Error },
+
+    /// The format of the file cannot be determined by its extension or the format is not enabled.
+    UnknownFormat(PathBuf),
+
+    /// The value at the given JSON pointer cannot be represented in the format.
+    Unsupported {
+        format: SerializationFormat,
+        pointer: String,
+        reason: String,
+    },
+
+    /// The value cannot be serialized.
+    Serialize { format: SerializationFormat, message: String },
+
+    /// The value cannot be deserialized.
+    Deserialize { format: SerializationFormat, message: String },
+}
+
+impl Display for SerializationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializationError::Io { path, error } => write!(f, "Failed to access {}: {}", path.display(), error),
+            SerializationError::UnknownFormat(path) => write!(f, "The format of {} is unknown", path.display()),
+            SerializationError::Unsupported { format, pointer, reason } => {
+                write!(f, "The value at {:?} cannot be represented in {}: {}", pointer, format, reason)
+            }
+            SerializationError::Serialize { format, message } => write!(f, "Failed to serialize {}: {}", format, message),
+            SerializationError::Deserialize { format, message } => write!(f, "Failed to deserialize {}: {}", format, message),
+        }
+    }
+}
+
+impl Error for SerializationError {}
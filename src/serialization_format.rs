@@ -0,0 +1,288 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::SerializationError;
+
+/// The formats in which types and instances can be loaded and saved.
+///
+/// Types and instances are converted into a [`Value`] first, which is then written in the format.
+/// Loading reads a [`Value`] from the format and converts it into the type or instance. So every
+/// format stores the same JSON document and property values and extensions (which are JSON values
+/// anyway) are mapped exactly like the fields of the structs:
+///
+/// * Numbers keep their representation: unsigned and signed integers stay integers and floats stay
+///   floats, even if they have no fractional part (`1.0`). Non-finite floats don't exist in JSON
+///   and are loaded as `null`.
+/// * Object keys are always strings. Keys of other types (integers, booleans, byte strings, ...)
+///   are rejected when loading, except in YAML, where plain scalar keys are read as their text.
+/// * Byte strings don't exist in JSON and are rejected when loading. The data type
+///   [`Bytes`](crate::DataType::Bytes) is stored as base64 encoded string in every format.
+/// * The order of object keys is not preserved.
+///
+/// Values which cannot be represented in a format are rejected when saving instead of being
+/// changed silently, so every value which has been saved loads as the same value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SerializationFormat {
+    /// JSON (`.json`).
+    Json,
+
+    /// YAML (`.yaml`, `.yml`). `null` is written as `null`. Strings which would be read as another
+    /// scalar (`"true"`, `"1"`, `"~"`) are quoted.
+    #[cfg(feature = "yaml")]
+    Yaml,
+
+    /// TOML (`.toml`). TOML has no null, so `null` values are rejected. Integers are limited to
+    /// the range of `i64`, so larger unsigned integers are rejected. The document must be an
+    /// object. Date-times are loaded as strings as they are written in TOML: offset date-times in
+    /// RFC 3339 format (`1979-05-27T07:32:00Z`), local date-times, local dates (`1979-05-27`) and
+    /// local times (`07:32:00`) without an offset.
+    #[cfg(feature = "toml")]
+    Toml,
+
+    /// RON (`.ron`). `null` is written as unit `()` and `Some(value)` is loaded as the value.
+    #[cfg(feature = "ron")]
+    Ron,
+
+    /// MessagePack (`.msgpack`, `.mpk`). `null` is written as nil, integers in their smallest
+    /// representation and floats as 64 bit floats.
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+
+    /// CBOR (`.cbor`). `null` is written as null, integers in their smallest representation and
+    /// floats in the smallest representation which keeps their value. Tags are ignored when loading.
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl SerializationFormat {
+    /// Returns the format of the given file extension, if the format is enabled.
+    pub fn from_extension(extension: &str) -> Option<SerializationFormat> {
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(SerializationFormat::Json),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(SerializationFormat::Yaml),
+            #[cfg(feature = "toml")]
+            "toml" => Some(SerializationFormat::Toml),
+            #[cfg(feature = "ron")]
+            "ron" => Some(SerializationFormat::Ron),
+            #[cfg(feature = "msgpack")]
+            "msgpack" | "mpk" => Some(SerializationFormat::MessagePack),
+            #[cfg(feature = "cbor")]
+            "cbor" => Some(SerializationFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Returns the format of the given path by its extension, if the format is enabled.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<SerializationFormat> {
+        path.as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(SerializationFormat::from_extension)
+    }
+
+    /// Returns the default file extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SerializationFormat::Json => "json",
+            #[cfg(feature = "yaml")]
+            SerializationFormat::Yaml => "yaml",
+            #[cfg(feature = "toml")]
+            SerializationFormat::Toml => "toml",
+            #[cfg(feature = "ron")]
+            SerializationFormat::Ron => "ron",
+            #[cfg(feature = "msgpack")]
+            SerializationFormat::MessagePack => "msgpack",
+            #[cfg(feature = "cbor")]
+            SerializationFormat::Cbor => "cbor",
+        }
+    }
+
+    /// Returns true, if the format is a binary format.
+    pub fn is_binary(&self) -> bool {
+        match self {
+            #[cfg(feature = "msgpack")]
+            SerializationFormat::MessagePack => true,
+            #[cfg(feature = "cbor")]
+            SerializationFormat::Cbor => true,
+            _ => false,
+        }
+    }
+
+    /// Serializes the given type or instance.
+    pub fn to_vec<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, SerializationError> {
+        let value = serde_json::to_value(value).map_err(|e| self.serialize_error(e))?;
+        self.value_to_vec(&value)
+    }
+
+    /// Deserializes a type or instance.
+    pub fn from_slice<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, SerializationError> {
+        let value = self.value_from_slice(bytes)?;
+        serde_json::from_value(value).map_err(|e| self.deserialize_error(e))
+    }
+
+    /// Serializes the given JSON value.
+    pub fn value_to_vec(&self, value: &Value) -> Result<Vec<u8>, SerializationError> {
+        match self {
+            SerializationFormat::Json => serde_json::to_vec_pretty(value).map_err(|e| self.serialize_error(e)),
+            #[cfg(feature = "yaml")]
+            SerializationFormat::Yaml => serde_yaml::to_string(value).map(String::into_bytes).map_err(|e| self.serialize_error(e)),
+            #[cfg(feature = "toml")]
+            SerializationFormat::Toml => {
+                if !value.is_object() {
+                    return Err(self.unsupported("", "The document must be an object"));
+                }
+                self.check_toml_value(value, &mut String::new())?;
+                toml::to_string_pretty(value).map(String::into_bytes).map_err(|e| self.serialize_error(e))
+            }
+            #[cfg(feature = "ron")]
+            SerializationFormat::Ron => ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+                .map(String::into_bytes)
+                .map_err(|e| self.serialize_error(e)),
+            #[cfg(feature = "msgpack")]
+            SerializationFormat::MessagePack => rmp_serde::to_vec(value).map_err(|e| self.serialize_error(e)),
+            #[cfg(feature = "cbor")]
+            SerializationFormat::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::ser::into_writer(value, &mut bytes).map_err(|e| self.serialize_error(e))?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// Deserializes a JSON value.
+    pub fn value_from_slice(&self, bytes: &[u8]) -> Result<Value, SerializationError> {
+        match self {
+            SerializationFormat::Json => serde_json::from_slice(bytes).map_err(|e| self.deserialize_error(e)),
+            #[cfg(feature = "yaml")]
+            SerializationFormat::Yaml => serde_yaml::from_slice(bytes).map_err(|e| self.deserialize_error(e)),
+            #[cfg(feature = "toml")]
+            SerializationFormat::Toml => {
+                let document = std::str::from_utf8(bytes).map_err(|e| self.deserialize_error(e))?;
+                let table: toml::Table = toml::from_str(document).map_err(|e| self.deserialize_error(e))?;
+                Ok(toml_to_json(toml::Value::Table(table)))
+            }
+            #[cfg(feature = "ron")]
+            SerializationFormat::Ron => {
+                let document = std::str::from_utf8(bytes).map_err(|e| self.deserialize_error(e))?;
+                ron::from_str(document).map_err(|e| self.deserialize_error(e))
+            }
+            #[cfg(feature = "msgpack")]
+            SerializationFormat::MessagePack => rmp_serde::from_slice(bytes).map_err(|e| self.deserialize_error(e)),
+            #[cfg(feature = "cbor")]
+            SerializationFormat::Cbor => ciborium::de::from_reader(bytes).map_err(|e| self.deserialize_error(e)),
+        }
+    }
+
+    /// Rejects the values which TOML cannot represent: `null` and integers which are larger than
+    /// `i64::MAX`.
+    #[cfg(feature = "toml")]
+    fn check_toml_value(&self, value: &Value, pointer: &mut String) -> Result<(), SerializationError> {
+        match value {
+            Value::Null => Err(self.unsupported(pointer, "TOML has no null")),
+            Value::Number(number) if number.is_u64() && number.as_i64().is_none() => Err(self.unsupported(pointer, "TOML integers are limited to i64")),
+            Value::Array(values) => values.iter().enumerate().try_for_each(|(index, value)| {
+                let length = pointer.len();
+                pointer.push_str(&format!("/{}", index));
+                let result = self.check_toml_value(value, pointer);
+                pointer.truncate(length);
+                result
+            }),
+            Value::Object(values) => values.iter().try_for_each(|(key, value)| {
+                let length = pointer.len();
+                pointer.push_str(&format!("/{}", key.replace('~', "~0").replace('/', "~1")));
+                let result = self.check_toml_value(value, pointer);
+                pointer.truncate(length);
+                result
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    fn unsupported(&self, pointer: &str, reason: &str) -> SerializationError {
+        SerializationError::Unsupported {
+            format: *self,
+            pointer: pointer.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    fn serialize_error<E: Display>(&self, error: E) -> SerializationError {
+        SerializationError::Serialize {
+            format: *self,
+            message: error.to_string(),
+        }
+    }
+
+    fn deserialize_error<E: Display>(&self, error: E) -> SerializationError {
+        SerializationError::Deserialize {
+            format: *self,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl Display for SerializationFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializationFormat::Json => write!(f, "JSON"),
+            #[cfg(feature = "yaml")]
+            SerializationFormat::Yaml => write!(f, "YAML"),
+            #[cfg(feature = "toml")]
+            SerializationFormat::Toml => write!(f, "TOML"),
+            #[cfg(feature = "ron")]
+            SerializationFormat::Ron => write!(f, "RON"),
+            #[cfg(feature = "msgpack")]
+            SerializationFormat::MessagePack => write!(f, "MessagePack"),
+            #[cfg(feature = "cbor")]
+            SerializationFormat::Cbor => write!(f, "CBOR"),
+        }
+    }
+}
+
+/// Converts a TOML value into a JSON value. Date-times are converted into strings.
+#[cfg(feature = "toml")]
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(value) => Value::String(value),
+        toml::Value::Integer(value) => Value::from(value),
+        toml::Value::Float(value) => serde_json::Number::from_f64(value).map_or(Value::Null, Value::Number),
+        toml::Value::Boolean(value) => Value::Bool(value),
+        toml::Value::Datetime(value) => Value::String(value.to_string()),
+        toml::Value::Array(values) => Value::Array(values.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(values) => Value::Object(values.into_iter().map(|(key, value)| (key, toml_to_json(value))).collect()),
+    }
+}
+
+/// Loads a type or an instance from the given file. The format is determined by the extension of
+/// the file.
+pub fn load_from_file<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, SerializationError> {
+    let path = path.as_ref();
+    let format = SerializationFormat::from_path(path).ok_or_else(|| SerializationError::UnknownFormat(path.to_path_buf()))?;
+    let bytes = fs::read(path).map_err(|error| SerializationError::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    format.from_slice(&bytes)
+}
+
+/// Saves a type or an instance into the given file. The format is determined by the extension of
+/// the file.
+pub fn save_to_file<T: Serialize, P: AsRef<Path>>(path: P, value: &T) -> Result<(), SerializationError> {
+    let path = path.as_ref();
+    let format = SerializationFormat::from_path(path).ok_or_else(|| SerializationError::UnknownFormat(path.to_path_buf()))?;
+    let bytes = format.to_vec(value)?;
+    fs::write(path, bytes).map_err(|error| SerializationError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
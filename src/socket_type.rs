@@ -8,6 +8,7 @@ use serde::Serialize;
 /// The socket type defines if the property acts as an input or output socket
 /// or is an hidden property
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Copy, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum SocketType {
     /// The property doesn't act as input or output socket.
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::RwLock;
+
+use serde_json::Value;
+
+type SyncObserver = Arc<Mutex<Box<dyn FnMut(&Value) + Send>>>;
+
+/// A stream whose observers can be shared between threads.
+///
+/// Guarantees:
+///
+/// - An observer is called synchronously on the thread which sends the value, i.e. the thread
+///   which sets or ticks the property. No thread is spawned.
+/// - An observer is never called concurrently with itself. Different observers may be called
+///   concurrently if values are sent from multiple threads.
+/// - An observer may subscribe or remove observers while being called. It must not send a value
+///   down the same stream synchronously, because this would call itself again.
+#[derive(Default)]
+pub struct SyncStream {
+    observers: RwLock<HashMap<u128, SyncObserver>>,
+}
+
+impl SyncStream {
+    pub fn new() -> SyncStream {
+        SyncStream::default()
+    }
+
+    /// Adds the given observer with the given handle. An existing observer with the same handle
+    /// is replaced.
+    pub fn observe_with_handle<F>(&self, observer: F, handle_id: u128)
+    where
+        F: FnMut(&Value) + Send + 'static,
+    {
+        self.observers.write().unwrap().insert(handle_id, Arc::new(Mutex::new(Box::new(observer))));
+    }
+
+    /// Removes the observer with the given handle.
+    pub fn remove(&self, handle_id: u128) {
+        self.observers.write().unwrap().remove(&handle_id);
+    }
+
+    /// Removes all observers.
+    pub fn clear(&self) {
+        self.observers.write().unwrap().clear();
+    }
+
+    /// Returns the number of observers.
+    pub fn len(&self) -> usize {
+        self.observers.read().unwrap().len()
+    }
+
+    /// Returns true, if the stream has no observers.
+    pub fn is_empty(&self) -> bool {
+        self.observers.read().unwrap().is_empty()
+    }
+
+    /// Calls all observers with the given value on the current thread.
+    pub fn send(&self, value: &Value) {
+        // Release the lock on the observers before calling them
+        let observers: Vec<SyncObserver> = self.observers.read().unwrap().values().cloned().collect();
+        for observer in observers {
+            let mut observer = observer.lock().unwrap_or_else(|e| e.into_inner());
+            observer(value);
+        }
+    }
+}
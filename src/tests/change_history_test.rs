@@ -0,0 +1,175 @@
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::thread;
+
+use serde_json::json;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::create_random_entity_instance_with_type;
+use crate::tests::utils::r_string;
+use crate::ChangeHistory;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+use crate::ReactiveFlowInstance;
+use crate::ReactivePropertyContainer;
+use crate::ReactiveRelationInstance;
+
+#[test]
+fn change_history_undo_redo_test() {
+    let property_name = r_string();
+    let entity_instance = Arc::new(create_random_entity_instance(&property_name));
+    let initial_value = entity_instance.get(&property_name).unwrap();
+    let history = Arc::new(ChangeHistory::new());
+    history.attach_entity(&entity_instance);
+    assert!(!history.can_undo());
+
+    entity_instance.set(&property_name, json!(1));
+    entity_instance.set(&property_name, json!(2));
+    entity_instance.set_no_propagate(&property_name, json!(3));
+    let changes = history.changes();
+    assert_eq!(3, changes.len());
+    assert!(changes.windows(2).all(|c| c[0].sequence < c[1].sequence));
+    assert_eq!(initial_value, changes[0].old_value);
+    assert_eq!(json!(1), changes[0].new_value);
+    assert_eq!(entity_instance.id, changes[0].id);
+    assert!(changes[0].propagate);
+    assert!(!changes[2].propagate);
+
+    // Undo doesn't propagate changes which haven't been propagated
+    let observed = Arc::new(RwLock::new(json!(null)));
+    let inner_observed = observed.clone();
+    let _subscription = entity_instance
+        .subscribe(&property_name, move |v| *inner_observed.write().unwrap() = v.clone())
+        .unwrap();
+    assert_eq!(json!(3), history.undo().unwrap()[0].new_value);
+    assert_eq!(json!(2), entity_instance.get(&property_name).unwrap());
+    assert_eq!(json!(null), *observed.read().unwrap());
+    history.undo();
+    assert_eq!(json!(1), entity_instance.get(&property_name).unwrap());
+    assert_eq!(json!(1), *observed.read().unwrap());
+    history.undo();
+    assert_eq!(initial_value, entity_instance.get(&property_name).unwrap());
+    assert!(history.undo().is_none());
+
+    // Undo and redo are not recorded
+    assert_eq!(0, history.undo_len());
+    assert_eq!(3, history.redo_len());
+    history.redo();
+    history.redo();
+    assert_eq!(json!(2), entity_instance.get(&property_name).unwrap());
+    assert_eq!(2, history.undo_len());
+    assert_eq!(1, history.redo_len());
+
+    // A new change clears the redo stack
+    entity_instance.set(&property_name, json!(4));
+    assert!(!history.can_redo());
+    assert_eq!(3, history.undo_len());
+
+    // Detached instances are not recorded
+    history.detach_entity(&entity_instance);
+    entity_instance.set(&property_name, json!(5));
+    assert_eq!(3, history.undo_len());
+}
+
+#[test]
+fn change_history_concurrent_replay_test() {
+    let property_name = r_string();
+    let entity_instance_1 = Arc::new(create_random_entity_instance(&property_name));
+    let entity_instance_2 = Arc::new(create_random_entity_instance(&property_name));
+    let history = Arc::new(ChangeHistory::new());
+    history.attach_entity(&entity_instance_1);
+    history.attach_entity(&entity_instance_2);
+    entity_instance_2.set(&property_name, json!(1));
+    history.group(|| {
+        entity_instance_1.set(&property_name, json!(1));
+        entity_instance_1.set(&property_name, json!(2));
+    });
+
+    // While the first group is undone, another thread undoes the second group
+    let armed = Arc::new(AtomicBool::new(true));
+    let inner_history = history.clone();
+    let _subscription = entity_instance_1
+        .subscribe(&property_name, move |_| {
+            if armed.swap(false, Ordering::SeqCst) {
+                let history = inner_history.clone();
+                thread::spawn(move || history.undo()).join().unwrap();
+            }
+        })
+        .unwrap();
+    history.undo();
+
+    // The remaining changes of the first group are not recorded
+    assert_eq!(0, history.undo_len());
+    assert_eq!(2, history.redo_len());
+}
+
+#[test]
+fn change_history_replay_panic_test() {
+    let property_name = r_string();
+    let entity_instance = Arc::new(create_random_entity_instance(&property_name));
+    let history = Arc::new(ChangeHistory::new());
+    history.attach_entity(&entity_instance);
+    entity_instance.set(&property_name, json!(1));
+
+    // An observer panics while the change is undone
+    let armed = Arc::new(AtomicBool::new(true));
+    let _subscription = entity_instance.properties.get(&property_name).unwrap().subscribe_sync(move |_| {
+        if armed.swap(false, Ordering::SeqCst) {
+            panic!("observer panicked");
+        }
+    });
+    let inner_history = history.clone();
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| inner_history.undo())).is_err());
+
+    // Later changes of the thread are recorded
+    let undo_len = history.undo_len();
+    entity_instance.set(&property_name, json!(2));
+    assert_eq!(undo_len + 1, history.undo_len());
+}
+
+#[test]
+fn change_history_flow_group_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("namespace", "generic_flow", "test"));
+    let reactive_flow_instance = ReactiveFlowInstance::new(wrapper_entity_instance.clone());
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    reactive_flow_instance.add_entity(entity_instance.clone());
+    let relation_instance = Arc::new(ReactiveRelationInstance::new_from_type_with_properties(
+        r_string(),
+        wrapper_entity_instance.clone(),
+        r_string(),
+        entity_instance.clone(),
+        vec![("y".to_string(), json!(0))].into_iter().collect(),
+    ));
+    reactive_flow_instance.add_relation(relation_instance.clone());
+    let history = Arc::new(ChangeHistory::with_limit(2));
+    history.attach_flow(&reactive_flow_instance);
+
+    history.group(|| {
+        reactive_flow_instance.set("test", json!("a"));
+        entity_instance.set("x", json!("b"));
+        relation_instance.set("y", json!(1));
+    });
+    assert_eq!(1, history.undo_len());
+    assert_eq!(3, history.changes().len());
+    history.undo();
+    assert_ne!(json!("a"), reactive_flow_instance.get("test").unwrap());
+    assert_ne!(json!("b"), entity_instance.get("x").unwrap());
+    assert_eq!(json!(0), relation_instance.get("y").unwrap());
+    history.redo();
+    assert_eq!(json!("a"), reactive_flow_instance.get("test").unwrap());
+    assert_eq!(json!("b"), entity_instance.get("x").unwrap());
+    assert_eq!(json!(1), relation_instance.get("y").unwrap());
+
+    // The limit drops the oldest groups
+    relation_instance.set("y", json!(2));
+    relation_instance.set("y", json!(3));
+    assert_eq!(2, history.undo_len());
+    history.undo();
+    history.undo();
+    assert!(history.undo().is_none());
+    assert_eq!(json!(1), relation_instance.get("y").unwrap());
+}
@@ -38,8 +38,10 @@ fn component_test() {
     let ty = ComponentTypeId::new_from_type(&namespace, &component_name);
     let component = Component {
         ty,
+        version: 0,
         description: description.clone(),
         properties: property_types,
+        migrations: Vec::new(),
         extensions,
     };
 
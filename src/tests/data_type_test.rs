@@ -1,4 +1,6 @@
+use serde_json::json;
 use serde_json::Value;
+use uuid::Uuid;
 
 use crate::tests::utils::r_string;
 use crate::DataType;
@@ -8,6 +10,8 @@ fn data_type_should_be_created_using_static_method_call() {
     assert_eq!(DataType::Bool, DataType::bool());
     assert_eq!(DataType::Number, DataType::number());
     assert_eq!(DataType::String, DataType::string());
+    assert_eq!(DataType::Integer, DataType::integer());
+    assert_eq!(DataType::Float, DataType::float());
 }
 
 #[test]
@@ -26,6 +30,19 @@ fn data_type_should_return_the_correct_default_value() {
 
     assert!(DataType::Any.default_value().is_string());
     assert_eq!("", DataType::Any.default_value());
+
+    assert_eq!(0, DataType::Integer.default_value());
+    assert_eq!(0.0, DataType::Float.default_value());
+    assert_eq!("00000000-0000-0000-0000-000000000000", DataType::Uuid.default_value());
+    assert_eq!("1970-01-01T00:00:00Z", DataType::DateTime.default_value());
+    assert_eq!("", DataType::Bytes.default_value());
+    assert_eq!("a", DataType::Enum(vec!["a".to_string(), "b".to_string()]).default_value());
+    // Enums without values have no valid value
+    assert_eq!(json!(null), DataType::Enum(Vec::new()).default_value());
+
+    for data_type in [DataType::Integer, DataType::Float, DataType::Uuid, DataType::DateTime, DataType::Bytes] {
+        assert!(data_type.is_valid_value(&data_type.default_value()));
+    }
 }
 
 #[test]
@@ -54,6 +71,36 @@ fn data_type_from_str() {
     assert_eq!(DataType::Any, DataType::from("Any"));
     assert_eq!(DataType::Any, DataType::from("ANY"));
 
+    assert_eq!(DataType::Integer, DataType::from("integer"));
+    assert_eq!(DataType::Float, DataType::from("float"));
+    assert_eq!(DataType::Uuid, DataType::from("uuid"));
+    assert_eq!(DataType::DateTime, DataType::from("DateTime"));
+    assert_eq!(DataType::Bytes, DataType::from("bytes"));
+    assert_eq!(DataType::Null, DataType::from("null"));
+    assert_eq!(DataType::Enum(vec!["On".to_string(), "off".to_string()]), DataType::from(r#"enum(["On", "off"])"#));
+    assert_eq!(DataType::Enum(Vec::new()), DataType::from("enum"));
+    assert_eq!(DataType::String, DataType::from("enum(on, off)"));
+
+    // The string form of all data types can be parsed
+    for data_type in [
+        DataType::Null,
+        DataType::Bool,
+        DataType::Number,
+        DataType::String,
+        DataType::Array,
+        DataType::Object,
+        DataType::Any,
+        DataType::Integer,
+        DataType::Float,
+        DataType::Uuid,
+        DataType::DateTime,
+        DataType::Bytes,
+        DataType::Enum(vec!["on".to_string(), "off \"1\"".to_string()]),
+        DataType::Enum(Vec::new()),
+    ] {
+        assert_eq!(data_type, DataType::from(data_type.to_string().as_str()));
+    }
+
     // Fallback to String
     assert_eq!(DataType::String, DataType::from(r_string().as_str()));
 }
@@ -66,3 +113,53 @@ fn data_type_display() {
     assert_eq!("Array", format!("{}", DataType::Array));
     assert_eq!("Object", format!("{}", DataType::Object));
 }
+
+#[test]
+fn data_type_is_valid_value() {
+    assert!(DataType::Null.is_valid_value(&json!(null)));
+    assert!(!DataType::Null.is_valid_value(&json!(false)));
+    assert!(DataType::Bool.is_valid_value(&json!(true)));
+    assert!(!DataType::Bool.is_valid_value(&json!(1)));
+    assert!(DataType::Number.is_valid_value(&json!(1)));
+    assert!(DataType::Number.is_valid_value(&json!(-1.5)));
+    assert!(!DataType::Number.is_valid_value(&json!("1")));
+    assert!(DataType::String.is_valid_value(&json!(r_string())));
+    assert!(!DataType::String.is_valid_value(&json!(null)));
+    assert!(DataType::Array.is_valid_value(&json!([1, 2])));
+    assert!(!DataType::Array.is_valid_value(&json!({})));
+    assert!(DataType::Object.is_valid_value(&json!({})));
+    assert!(!DataType::Object.is_valid_value(&json!([])));
+    assert!(DataType::Any.is_valid_value(&json!(null)));
+    assert!(DataType::Any.is_valid_value(&json!({ "a": [1] })));
+}
+
+#[test]
+fn data_type_is_valid_value_extended() {
+    assert!(DataType::Integer.is_valid_value(&json!(-1)));
+    assert!(DataType::Integer.is_valid_value(&json!(u64::MAX)));
+    assert!(!DataType::Integer.is_valid_value(&json!(1.5)));
+    assert!(DataType::Float.is_valid_value(&json!(1.5)));
+    assert!(DataType::Float.is_valid_value(&json!(1)));
+    assert!(!DataType::Float.is_valid_value(&json!("1.5")));
+    assert!(DataType::Uuid.is_valid_value(&json!(Uuid::new_v4())));
+    assert!(!DataType::Uuid.is_valid_value(&json!(r_string())));
+    assert!(DataType::DateTime.is_valid_value(&json!("2022-11-06T12:30:00+01:00")));
+    assert!(!DataType::DateTime.is_valid_value(&json!("2022-11-06")));
+    assert!(!DataType::DateTime.is_valid_value(&json!(0)));
+    assert!(DataType::Bytes.is_valid_value(&json!("aGVsbG8=")));
+    assert!(!DataType::Bytes.is_valid_value(&json!("not base64!")));
+    let data_type = DataType::Enum(vec!["red".to_string(), "green".to_string()]);
+    assert!(data_type.is_valid_value(&json!("red")));
+    assert!(!data_type.is_valid_value(&json!("blue")));
+    assert!(!data_type.is_valid_value(&json!(0)));
+}
+
+#[test]
+fn data_type_serde() {
+    assert_eq!(json!("integer"), serde_json::to_value(DataType::Integer).unwrap());
+    assert_eq!(json!("datetime"), serde_json::to_value(DataType::DateTime).unwrap());
+    let data_type = DataType::Enum(vec!["red".to_string(), "green".to_string()]);
+    let value = serde_json::to_value(&data_type).unwrap();
+    assert_eq!(json!({ "enum": ["red", "green"] }), value);
+    assert_eq!(data_type, serde_json::from_value(value).unwrap());
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::r_string;
+use crate::Component;
+use crate::ComponentTypeId;
+use crate::DataType;
+use crate::EntityInstance;
+use crate::EntityType;
+use crate::EntityTypeId;
+use crate::PropertyConflict;
+use crate::PropertyDeclaration;
+use crate::PropertyOrigin;
+use crate::PropertyType;
+use crate::RelationType;
+use crate::RelationTypeId;
+use crate::SocketType;
+use crate::ValidationViolation;
+
+#[test]
+fn effective_properties_test() {
+    let namespace = r_string();
+    let component_1_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let component_2_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let component_1 = Component::new_without_extensions(
+        component_1_ty.clone(),
+        "",
+        vec![PropertyType::string("name"), PropertyType::number("x"), PropertyType::bool("shared")],
+    );
+    let component_2 = Component::new_without_extensions(
+        component_2_ty.clone(),
+        "",
+        vec![PropertyType::number("name"), PropertyType::bool("shared"), PropertyType::string("label")],
+    );
+    let components = vec![component_1, component_2];
+    let entity_type = EntityType::new(
+        EntityTypeId::new_from_type(&namespace, &r_string()),
+        "",
+        vec![component_1_ty.clone(), component_2_ty.clone()],
+        vec![PropertyType::new("x", DataType::Integer)],
+        Vec::new(),
+    );
+
+    let effective_properties = entity_type.effective_properties(&components);
+    assert_eq!(
+        vec!["x", "name", "shared", "label"],
+        effective_properties
+            .properties
+            .iter()
+            .map(|p| p.property_type.name.as_str())
+            .collect::<Vec<&str>>()
+    );
+
+    // Own properties win over the properties of the components, but differing declarations are conflicts
+    let x = effective_properties.get_property("x").unwrap();
+    assert_eq!(PropertyOrigin::Own, x.origin);
+    assert_eq!(DataType::Integer, x.property_type.data_type);
+
+    // The first component wins
+    let name = effective_properties.get_property("name").unwrap();
+    assert_eq!(PropertyOrigin::Component(component_1_ty.clone()), name.origin);
+    assert_eq!(DataType::String, name.property_type.data_type);
+    assert_eq!(PropertyOrigin::Component(component_2_ty.clone()), effective_properties.get_property("label").unwrap().origin);
+    assert!(effective_properties.has_property("shared"));
+    assert!(!effective_properties.has_property(r_string()));
+
+    // Equal declarations are no conflict
+    assert!(!effective_properties.is_ok());
+    assert!(effective_properties.missing_components.is_empty());
+    assert_eq!(
+        vec![
+            PropertyConflict {
+                property_name: "x".to_string(),
+                first: PropertyDeclaration {
+                    origin: PropertyOrigin::Own,
+                    data_type: DataType::Integer,
+                    socket_type: SocketType::None,
+                },
+                second: PropertyDeclaration {
+                    origin: PropertyOrigin::Component(component_1_ty.clone()),
+                    data_type: DataType::Number,
+                    socket_type: SocketType::None,
+                },
+            },
+            PropertyConflict {
+                property_name: "name".to_string(),
+                first: PropertyDeclaration {
+                    origin: PropertyOrigin::Component(component_1_ty.clone()),
+                    data_type: DataType::String,
+                    socket_type: SocketType::None,
+                },
+                second: PropertyDeclaration {
+                    origin: PropertyOrigin::Component(component_2_ty.clone()),
+                    data_type: DataType::Number,
+                    socket_type: SocketType::None,
+                },
+            },
+        ],
+        effective_properties.conflicts
+    );
+    assert_eq!(
+        format!(
+            "The property x is declared as None Integer by the type itself and as None Number by component {}",
+            component_1_ty
+        ),
+        effective_properties.conflicts[0].to_string()
+    );
+
+    // The conflict is a validation violation
+    let mut properties = HashMap::new();
+    properties.insert("x".to_string(), json!(1));
+    properties.insert("name".to_string(), json!(r_string()));
+    properties.insert("shared".to_string(), json!(true));
+    properties.insert("label".to_string(), json!(r_string()));
+    let entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+    let violations = entity_instance.validate(&entity_type, &components).unwrap_err();
+    assert_eq!(
+        effective_properties
+            .conflicts
+            .iter()
+            .cloned()
+            .map(ValidationViolation::PropertyConflict)
+            .collect::<Vec<ValidationViolation>>(),
+        violations
+    );
+}
+
+#[test]
+fn effective_properties_missing_component_test() {
+    let namespace = r_string();
+    let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let relation_type = RelationType::new(
+        EntityTypeId::new_from_type(&namespace, &r_string()),
+        RelationTypeId::new_from_type(&namespace, &r_string()),
+        EntityTypeId::new_from_type(&namespace, &r_string()),
+        "",
+        vec![component_ty.clone()],
+        vec![PropertyType::bool("enabled")],
+        Vec::new(),
+    );
+    let effective_properties = relation_type.effective_properties(&Vec::<Component>::new());
+    assert!(!effective_properties.is_ok());
+    assert_eq!(vec![component_ty], effective_properties.missing_components);
+    assert_eq!(1, effective_properties.property_types().len());
+}
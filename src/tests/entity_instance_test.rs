@@ -9,6 +9,7 @@ use uuid::Uuid;
 
 use crate::property_identifier;
 use crate::tests::utils::r_string;
+use crate::tests::utils::r_string_1000;
 use crate::EntityInstance;
 use crate::EntityTypeId;
 use crate::Extension;
@@ -18,6 +19,11 @@ use crate::MutablePropertyInstanceSetter;
 use crate::NamespacedTypeGetter;
 use crate::PropertyInstanceGetter;
 use crate::TypeDefinitionGetter;
+use crate::DESCRIPTION_PROPERTY;
+use crate::EXTENSIONS_PROPERTY;
+use crate::PROPERTY_NAMES_PROPERTY;
+use crate::RESERVED_PROPERTY_PREFIX;
+use crate::VERSION_PROPERTY;
 
 #[test]
 fn entity_instance_test() {
@@ -49,6 +55,7 @@ fn entity_instance_test() {
     let entity_instance = EntityInstance {
         ty: ty.clone(),
         id: uuid.clone(),
+        version: None,
         description: description.to_string(),
         properties: properties.clone(),
         extensions: extensions.clone(),
@@ -193,6 +200,7 @@ fn entity_instance_ser_test() {
     let entity_instance = EntityInstance {
         ty: ty.clone(),
         id: uuid.clone(),
+        version: None,
         description: description.to_string(),
         properties: properties.clone(),
         extensions: extensions.clone(),
@@ -237,3 +245,55 @@ fn entity_instance_de_test() {
     assert_eq!("ext_name", extension.ty.type_name());
     assert_eq!(json!("extension_value"), extension.extension);
 }
+
+#[test]
+fn entity_instance_to_vertex_properties_round_trip_test() {
+    let ty = EntityTypeId::new_from_type(r_string(), r_string());
+    let property_name = r_string();
+    let mut properties = HashMap::new();
+    properties.insert(property_name.clone(), json!(r_string()));
+    properties.insert(r_string(), json!({ "a": [1, 2, 3] }));
+    let entity_instance = EntityInstance::new(ty.clone(), Uuid::new_v4(), properties);
+
+    let vertex = Vertex::from(&entity_instance);
+    assert_eq!(entity_instance.id, vertex.id);
+    assert_eq!(ty.type_id(), vertex.t);
+
+    let vertex_properties = VertexProperties::from(&entity_instance);
+    assert_eq!(2, vertex_properties.props.len());
+    assert!(vertex_properties.props.iter().any(|p| p.name == property_identifier(&property_name)));
+
+    let entity_instance_2 = EntityInstance::try_from(vertex_properties).unwrap();
+    assert_eq!(entity_instance.ty, entity_instance_2.ty);
+    assert_eq!(entity_instance.id, entity_instance_2.id);
+    assert_eq!(None, entity_instance_2.version);
+    assert_eq!(entity_instance.properties, entity_instance_2.properties);
+}
+
+#[test]
+fn entity_instance_to_vertex_properties_reserved_properties_round_trip_test() {
+    let ty = EntityTypeId::new_from_type(r_string(), r_string());
+    let long_property_name = r_string_1000();
+    let reserved_property_name = format!("{}{}", RESERVED_PROPERTY_PREFIX, "extensions");
+    let mut properties = HashMap::new();
+    properties.insert(long_property_name.clone(), json!(r_string()));
+    properties.insert(reserved_property_name.clone(), json!(r_string()));
+    let mut entity_instance = EntityInstance::new(ty, Uuid::new_v4(), properties);
+    entity_instance.description = r_string();
+    entity_instance.version = Some(2);
+    entity_instance
+        .extensions
+        .push(Extension::new_from_type(r_string(), r_string(), r_string(), json!({ "a": 1 })));
+
+    let vertex_properties = VertexProperties::from(&entity_instance);
+    assert!(vertex_properties.props.iter().any(|p| p.name.as_str() == DESCRIPTION_PROPERTY));
+    assert!(vertex_properties.props.iter().any(|p| p.name.as_str() == EXTENSIONS_PROPERTY));
+    assert!(vertex_properties.props.iter().any(|p| p.name.as_str() == PROPERTY_NAMES_PROPERTY));
+    assert!(vertex_properties.props.iter().any(|p| p.name.as_str() == VERSION_PROPERTY));
+
+    let entity_instance_2 = EntityInstance::try_from(vertex_properties).unwrap();
+    assert_eq!(entity_instance.description, entity_instance_2.description);
+    assert_eq!(Some(2), entity_instance_2.version);
+    assert_eq!(entity_instance.extensions, entity_instance_2.extensions);
+    assert_eq!(entity_instance.properties, entity_instance_2.properties);
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::create_random_relation_instance;
+use crate::tests::utils::r_string;
+use crate::FlowChangeEvent;
+use crate::FlowChangeLog;
+use crate::FlowChangeLogError;
+use crate::ReactiveFlowInstance;
+
+#[test]
+fn flow_change_log_test() {
+    let change_log = FlowChangeLog::new();
+    let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+    assert_eq!(0, change_log.cursor());
+    assert_eq!(1, change_log.record(FlowChangeEvent::EntityAdded(a)));
+    assert_eq!(2, change_log.record(FlowChangeEvent::EntityAdded(b)));
+    assert_eq!(3, change_log.record(FlowChangeEvent::EntityRemoved(a)));
+    assert_eq!(3, change_log.cursor());
+
+    // A cursor ahead of the latest event is rejected
+    assert!(change_log.changes_since(3).unwrap().is_empty());
+    assert_eq!(Err(FlowChangeLogError::CursorAhead { cursor: 4, sequence: 3 }), change_log.changes_since(4));
+    assert_eq!(Err(FlowChangeLogError::CursorAhead { cursor: 4, sequence: 3 }), change_log.drain_since(4));
+    assert_eq!(3, change_log.len());
+
+    let changes = change_log.changes_since(1).unwrap();
+    assert_eq!(vec![2, 3], changes.iter().map(|change| change.sequence).collect::<Vec<u64>>());
+    assert_eq!(FlowChangeEvent::EntityRemoved(a), changes[1].event);
+
+    // Only the latest event of each entity instance is kept
+    change_log.compact();
+    let changes = change_log.changes_since(0).unwrap();
+    assert_eq!(2, changes.len());
+    assert_eq!(FlowChangeEvent::EntityAdded(b), changes[0].event);
+    assert_eq!(FlowChangeEvent::EntityRemoved(a), changes[1].event);
+    assert_eq!(3, changes[1].sequence);
+
+    // Drained events are not available anymore
+    assert_eq!(1, change_log.drain_since(2).unwrap().len());
+    assert!(change_log.is_empty());
+    assert_eq!(Err(FlowChangeLogError::CursorExpired { cursor: 2, discarded_until: 3 }), change_log.changes_since(2));
+    change_log.record(FlowChangeEvent::EntityAdded(a));
+    assert_eq!(1, change_log.changes_since(3).unwrap().len());
+
+    // The oldest events are discarded if compaction is not sufficient
+    let change_log = FlowChangeLog::with_limit(2);
+    change_log.record(FlowChangeEvent::EntityAdded(a));
+    change_log.record(FlowChangeEvent::EntityRemoved(a));
+    change_log.record(FlowChangeEvent::EntityAdded(b));
+    assert_eq!(2, change_log.len());
+    assert_eq!(2, change_log.changes_since(0).unwrap().len());
+    change_log.record(FlowChangeEvent::EntityAdded(Uuid::new_v4()));
+    assert_eq!(2, change_log.len());
+    assert!(change_log.changes_since(0).is_err());
+    assert_eq!(2, change_log.changes_since(2).unwrap().len());
+}
+
+#[test]
+fn reactive_flow_instance_change_log_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    let reactive_flow_instance = ReactiveFlowInstance::new(wrapper_entity_instance.clone());
+    let entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    let relation_instance = Arc::new(create_random_relation_instance(wrapper_entity_instance.clone(), entity_instance.clone(), r_string()));
+    let cursor = reactive_flow_instance.change_log.cursor();
+
+    reactive_flow_instance.add_entity(entity_instance.clone());
+    reactive_flow_instance.add_entity(entity_instance.clone());
+    reactive_flow_instance.add_relation(relation_instance.clone());
+    reactive_flow_instance.remove_relation(&relation_instance.get_key());
+    reactive_flow_instance.remove_relation(&relation_instance.get_key());
+    reactive_flow_instance.remove_entity(Uuid::new_v4());
+
+    // Only effective changes are recorded
+    let events: Vec<FlowChangeEvent> = reactive_flow_instance
+        .change_log
+        .drain_since(cursor)
+        .unwrap()
+        .into_iter()
+        .map(|change| change.event)
+        .collect();
+    assert_eq!(
+        vec![
+            FlowChangeEvent::EntityAdded(entity_instance.id),
+            FlowChangeEvent::RelationAdded(relation_instance.get_key()),
+            FlowChangeEvent::RelationRemoved(relation_instance.get_key()),
+        ],
+        events
+    );
+    assert!(reactive_flow_instance.change_log.is_empty());
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::create_entity_instance_from_type;
+use crate::tests::utils::r_string;
+use crate::FlowGraph;
+use crate::FlowInstance;
+use crate::ReactiveEntityInstance;
+use crate::ReactiveFlowInstance;
+use crate::ReactiveRelationInstance;
+use crate::RelationInstance;
+use crate::RelationInstanceTypeId;
+
+fn create_flow_instance() -> FlowInstance {
+    let namespace = r_string();
+    let mut wrapper_entity_instance = create_entity_instance_from_type(&namespace, &r_string());
+    wrapper_entity_instance.properties.insert("name".to_string(), json!("wrapper \"flow\""));
+    let mut entity_instance = create_entity_instance_from_type(&namespace, &r_string());
+    entity_instance.properties.insert("name".to_string(), json!("a < b & c"));
+    let r_ty = RelationInstanceTypeId::new_from_type_unique_id(&namespace, &r_string());
+    let relation_instance = RelationInstance::new(wrapper_entity_instance.id, r_ty, entity_instance.id, HashMap::new());
+    let mut flow_instance = FlowInstance::from(wrapper_entity_instance);
+    flow_instance.entity_instances.push(entity_instance);
+    flow_instance.relation_instances.push(relation_instance);
+    flow_instance
+}
+
+#[test]
+fn flow_graph_from_flow_instance_test() {
+    let flow_instance = create_flow_instance();
+    let flow_graph = FlowGraph::from_flow_instance(&flow_instance, &["name", "unknown"]);
+    assert_eq!(flow_instance.id, flow_graph.id);
+    assert_eq!(2, flow_graph.nodes.len());
+    assert_eq!(1, flow_graph.edges.len());
+    let wrapper = &flow_graph.nodes[0];
+    assert!(wrapper.wrapper);
+    assert_eq!(flow_instance.id, wrapper.id);
+    assert_eq!(vec![("name".to_string(), json!("wrapper \"flow\""))], wrapper.properties);
+    assert!(!flow_graph.nodes[1].wrapper);
+    assert_eq!(flow_instance.entity_instances[1].id, flow_graph.nodes[1].id);
+    assert_eq!(flow_instance.relation_instances[0].ty, flow_graph.edges[0].ty);
+
+    let flow_graph = FlowGraph::from(&flow_instance);
+    assert!(flow_graph.nodes.iter().all(|node| node.properties.is_empty()));
+}
+
+#[test]
+fn flow_graph_from_reactive_flow_instance_test() {
+    let flow_instance = create_flow_instance();
+    let wrapper = Arc::new(ReactiveEntityInstance::from(flow_instance.entity_instances[0].clone()));
+    let entity_instance = Arc::new(ReactiveEntityInstance::from(flow_instance.entity_instances[1].clone()));
+    let relation_instance = Arc::new(ReactiveRelationInstance::new_from_instance(
+        wrapper.clone(),
+        entity_instance.clone(),
+        flow_instance.relation_instances[0].clone(),
+    ));
+    let reactive_flow_instance = ReactiveFlowInstance::new(wrapper.clone());
+    reactive_flow_instance.add_entity(entity_instance.clone());
+    reactive_flow_instance.add_relation(relation_instance);
+    let flow_graph = FlowGraph::from_reactive_flow_instance(&reactive_flow_instance, &["name"]);
+    assert_eq!(FlowGraph::from_flow_instance(&flow_instance, &["name"]), flow_graph);
+}
+
+#[test]
+fn flow_graph_to_dot_test() {
+    let flow_instance = create_flow_instance();
+    let wrapper = &flow_instance.entity_instances[0];
+    let entity_instance = &flow_instance.entity_instances[1];
+    let dot = FlowGraph::from_flow_instance(&flow_instance, &["name"]).to_dot();
+    assert!(dot.starts_with(&format!("digraph \"{}\" {{\n", flow_instance.id)));
+    assert!(dot.contains(&format!(
+        "  \"{}\" [label=\"{}\\nname = \\\"wrapper \\\\\\\"flow\\\\\\\"\\\"\", style=\"bold,filled\", fillcolor=\"lightblue\"];\n",
+        wrapper.id, wrapper.ty
+    )));
+    assert!(dot.contains(&format!("  \"{}\" [label=\"{}\\nname = \\\"a < b & c\\\"\"];\n", entity_instance.id, entity_instance.ty)));
+    assert!(dot.contains(&format!(
+        "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
+        wrapper.id, entity_instance.id, flow_instance.relation_instances[0].ty
+    )));
+    assert!(dot.ends_with("}\n"));
+}
+
+#[test]
+fn flow_graph_to_mermaid_test() {
+    let flow_instance = create_flow_instance();
+    let wrapper = &flow_instance.entity_instances[0];
+    let entity_instance = &flow_instance.entity_instances[1];
+    let mermaid = FlowGraph::from_flow_instance(&flow_instance, &["name"]).to_mermaid();
+    assert!(mermaid.starts_with("flowchart LR\n"));
+    assert!(mermaid.contains(&format!(
+        "  e{}[\"{}<br/>name = #quot;a #lt; b & c#quot;\"]\n",
+        entity_instance.id.simple(),
+        entity_instance.ty
+    )));
+    assert!(mermaid.contains(&format!(
+        "  e{} -->|\"{}\"| e{}\n",
+        wrapper.id.simple(),
+        flow_instance.relation_instances[0].ty,
+        entity_instance.id.simple()
+    )));
+    assert!(mermaid.ends_with(&format!("  class e{} wrapper\n", wrapper.id.simple())));
+}
+
+#[test]
+fn flow_graph_to_graphml_test() {
+    let flow_instance = create_flow_instance();
+    let wrapper = &flow_instance.entity_instances[0];
+    let entity_instance = &flow_instance.entity_instances[1];
+    let graphml = FlowGraph::from_flow_instance(&flow_instance, &["name"]).to_graphml();
+    assert!(graphml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+    assert!(graphml.contains("  <key id=\"p0\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n"));
+    assert!(graphml.contains(&format!(
+        "    <node id=\"{}\">\n      <data key=\"type\">{}</data>\n      <data key=\"wrapper\">true</data>\n",
+        wrapper.id, wrapper.ty
+    )));
+    assert!(graphml.contains("      <data key=\"p0\">&quot;a &lt; b &amp; c&quot;</data>\n"));
+    assert!(graphml.contains(&format!("    <edge source=\"{}\" target=\"{}\">\n", wrapper.id, entity_instance.id)));
+    assert!(graphml.ends_with("  </graph>\n</graphml>\n"));
+}
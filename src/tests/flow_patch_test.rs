@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::tests::utils::create_entity_instance;
+use crate::tests::utils::r_string;
+use crate::Extension;
+use crate::FlowInstance;
+use crate::FlowMergeError;
+use crate::FlowPatch;
+use crate::FlowPatchError;
+use crate::FlowPatchOperation;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+use crate::ReactiveFlowInstance;
+use crate::RelationInstance;
+use crate::RelationInstanceKey;
+
+fn create_flow_instance() -> FlowInstance {
+    let mut flow_instance = FlowInstance::from_instance_with_name(create_entity_instance("x"), r_string());
+    let entity_instance = create_entity_instance("y");
+    let relation_instance =
+        RelationInstance::new_from_type_unique_id(r_string(), flow_instance.id, r_string(), entity_instance.id, HashMap::from([("z".to_string(), json!(0))]));
+    flow_instance.entity_instances.push(entity_instance);
+    flow_instance.relation_instances.push(relation_instance);
+    flow_instance
+}
+
+#[test]
+fn flow_patch_diff_apply_test() {
+    let base = create_flow_instance();
+    let entity_id = base.entity_instances[1].id;
+    let key = RelationInstanceKey::from(&base.relation_instances[0]);
+    assert!(base.diff(&base).is_empty());
+
+    let mut changed = base.clone();
+    changed.name = r_string();
+    changed.entity_instances[1].properties.insert("y".to_string(), json!(42));
+    changed.entity_instances[1].description = r_string();
+    changed.entity_instances[1]
+        .extensions
+        .push(Extension::new_from_type(r_string(), r_string(), r_string(), json!(true)));
+    changed.relation_instances[0].properties.remove("z");
+    // The order of the instances is irrelevant
+    changed.entity_instances.reverse();
+    let added_entity_instance = create_entity_instance("w");
+    changed.entity_instances.push(added_entity_instance.clone());
+
+    let patch = base.diff(&changed);
+    assert_eq!(6, patch.operations.len());
+    assert!(matches!(&patch.operations[0], FlowPatchOperation::SetName { .. }));
+    assert_eq!(
+        FlowPatchOperation::AddEntityInstance {
+            entity_instance: added_entity_instance
+        },
+        patch.operations[1]
+    );
+    assert_eq!(
+        FlowPatchOperation::SetEntityProperty {
+            id: entity_id,
+            property_name: "y".to_string(),
+            old_value: Some(base.entity_instances[1].properties["y"].clone()),
+            new_value: Some(json!(42)),
+        },
+        patch.operations[2]
+    );
+    assert!(matches!(&patch.operations[3], FlowPatchOperation::SetEntityDescription { .. }));
+    assert!(matches!(&patch.operations[4], FlowPatchOperation::SetEntityExtension { old_value: None, .. }));
+    assert_eq!(
+        FlowPatchOperation::SetRelationProperty {
+            key,
+            property_name: "z".to_string(),
+            old_value: Some(json!(0)),
+            new_value: None,
+        },
+        patch.operations[5]
+    );
+
+    // The patch can be stored
+    let patch_json = serde_json::to_value(&patch).unwrap();
+    assert_eq!("set_name", patch_json["operations"][0]["op"]);
+    let patch = serde_json::from_value::<FlowPatch>(patch_json).unwrap();
+
+    let mut patched = base.clone();
+    patch.apply(&mut patched).unwrap();
+    assert!(patched.diff(&changed).is_empty());
+
+    // The patch can't be applied twice and the flow instance remains unchanged
+    let mut twice = patched.clone();
+    assert!(matches!(patch.apply(&mut twice), Err(FlowPatchError::Conflict(_))));
+    assert!(twice.diff(&patched).is_empty());
+
+    // The inverted patch reverts the changes
+    patch.invert().apply(&mut patched).unwrap();
+    assert!(patched.diff(&base).is_empty());
+
+    // Removing an entity instance removes its relation instances first
+    let mut removed = base.clone();
+    removed.entity_instances.remove(1);
+    removed.relation_instances.clear();
+    let patch = base.diff(&removed);
+    assert_eq!(2, patch.operations.len());
+    assert!(matches!(&patch.operations[0], FlowPatchOperation::RemoveRelationInstance { .. }));
+    assert!(matches!(&patch.operations[1], FlowPatchOperation::RemoveEntityInstance { .. }));
+}
+
+#[test]
+fn flow_patch_merge_test() {
+    let base = create_flow_instance();
+    let entity_id = base.entity_instances[1].id;
+
+    let mut ours = base.clone();
+    ours.description = r_string();
+    ours.entity_instances[1].properties.insert("y".to_string(), json!(1));
+    let mut theirs = base.clone();
+    theirs.relation_instances[0].properties.insert("z".to_string(), json!(2));
+    theirs.entity_instances[1].properties.insert("y".to_string(), json!(1));
+
+    let merged = FlowPatch::merge(&base, &ours, &theirs).unwrap();
+    assert_eq!(ours.description, merged.description);
+    assert_eq!(json!(1), merged.entity_instances[1].properties["y"]);
+    assert_eq!(json!(2), merged.relation_instances[0].properties["z"]);
+
+    // Both sides have changed the same property differently
+    theirs.entity_instances[1].properties.insert("y".to_string(), json!(3));
+    match FlowPatch::merge(&base, &ours, &theirs) {
+        Err(FlowMergeError::Conflicts(conflicts)) => {
+            assert_eq!(1, conflicts.len());
+            assert!(matches!(&conflicts[0].theirs, FlowPatchOperation::SetEntityProperty { id, .. } if *id == entity_id));
+        }
+        _ => panic!("Expected a conflict"),
+    }
+
+    // Their side has removed the entity instance which has been changed on our side
+    let mut theirs = base.clone();
+    theirs.entity_instances.remove(1);
+    theirs.relation_instances.clear();
+    match FlowPatch::merge(&base, &ours, &theirs) {
+        Err(FlowMergeError::Conflicts(conflicts)) => {
+            assert_eq!(1, conflicts.len());
+            assert!(matches!(&conflicts[0].theirs, FlowPatchOperation::RemoveEntityInstance { .. }));
+        }
+        _ => panic!("Expected a conflict"),
+    }
+
+    // Our side has removed the relation instance and their side has removed its inbound entity instance
+    let mut ours = base.clone();
+    ours.relation_instances.clear();
+    let merged = FlowPatch::merge(&base, &ours, &theirs).unwrap();
+    assert_eq!(1, merged.entity_instances.len());
+    assert!(merged.relation_instances.is_empty());
+    let merged = FlowPatch::merge(&base, &theirs, &ours).unwrap();
+    assert_eq!(1, merged.entity_instances.len());
+    assert!(merged.relation_instances.is_empty());
+}
+
+#[test]
+fn flow_patch_apply_reactive_test() {
+    let base = create_flow_instance();
+    let entity_id = base.entity_instances[1].id;
+    let reactive_flow_instance = ReactiveFlowInstance::try_from(base.clone()).unwrap();
+
+    let mut changed = base.clone();
+    changed.entity_instances[1].properties.insert("y".to_string(), json!(42));
+    changed.entity_instances[1].properties.insert("v".to_string(), json!("new"));
+    changed.entity_instances[1].description = r_string();
+    let added_entity_instance = create_entity_instance("w");
+    changed.relation_instances.push(RelationInstance::new_from_type_unique_id(
+        r_string(),
+        entity_id,
+        r_string(),
+        added_entity_instance.id,
+        HashMap::new(),
+    ));
+    changed.entity_instances.push(added_entity_instance.clone());
+
+    let patch = base.diff(&changed);
+    patch.apply_reactive(&reactive_flow_instance).unwrap();
+    let entity_instance = reactive_flow_instance.get_entity(entity_id).unwrap();
+    assert_eq!(json!(42), entity_instance.get("y").unwrap());
+    assert_eq!(json!("new"), entity_instance.get("v").unwrap());
+    assert!(reactive_flow_instance.has_entity_by_id(added_entity_instance.id));
+    assert_eq!(2, reactive_flow_instance.relation_instances.read().unwrap().len());
+
+    // The entity instance has already been added
+    assert!(matches!(patch.apply_reactive(&reactive_flow_instance), Err(FlowPatchError::EntityInstanceAlreadyExists(_))));
+
+    // Only the name and the descriptions are not part of the reactive model
+    let patched = FlowInstance::try_from(Arc::new(reactive_flow_instance)).unwrap();
+    assert!(patched.diff(&changed).operations.iter().all(|operation| matches!(
+        operation,
+        FlowPatchOperation::SetName { .. } | FlowPatchOperation::SetDescription { .. } | FlowPatchOperation::SetEntityDescription { .. }
+    )));
+}
+
+#[test]
+fn flow_patch_apply_reactive_stale_removal_test() {
+    let base = create_flow_instance();
+    let entity_id = base.entity_instances[1].id;
+    let key = RelationInstanceKey::from(&base.relation_instances[0]);
+    let reactive_flow_instance = ReactiveFlowInstance::try_from(base.clone()).unwrap();
+
+    // The relation instance has been changed since the patch has been created
+    let mut removed_relation = base.clone();
+    removed_relation.relation_instances.clear();
+    let patch = base.diff(&removed_relation);
+    let relation_instance = reactive_flow_instance.get_relation(&key.get_key()).unwrap();
+    relation_instance.set("z", json!(1));
+    assert!(matches!(patch.apply_reactive(&reactive_flow_instance), Err(FlowPatchError::Conflict(_))));
+    assert!(reactive_flow_instance.has_relation_by_key(&key.get_key()));
+    relation_instance.set("z", json!(0));
+    patch.apply_reactive(&reactive_flow_instance).unwrap();
+    assert!(!reactive_flow_instance.has_relation_by_key(&key.get_key()));
+
+    // The entity instance has been changed since the patch has been created
+    let mut removed_entity = removed_relation.clone();
+    removed_entity.entity_instances.remove(1);
+    let patch = removed_relation.diff(&removed_entity);
+    let entity_instance = reactive_flow_instance.get_entity(entity_id).unwrap();
+    let value = entity_instance.get("y").unwrap();
+    entity_instance.set("y", json!(42));
+    assert!(matches!(patch.apply_reactive(&reactive_flow_instance), Err(FlowPatchError::Conflict(_))));
+    assert!(reactive_flow_instance.has_entity_by_id(entity_id));
+    entity_instance.set("y", value);
+    patch.apply_reactive(&reactive_flow_instance).unwrap();
+    assert!(!reactive_flow_instance.has_entity_by_id(entity_id));
+}
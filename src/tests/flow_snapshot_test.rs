@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::create_random_entity_instance_with_type;
+use crate::tests::utils::r_string;
+use crate::FlowSnapshot;
+use crate::FlowSnapshotRestoreError;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+use crate::ReactiveFlowInstance;
+use crate::ReactivePropertyContainer;
+use crate::ReactiveRelationInstance;
+
+#[test]
+fn flow_snapshot_restore_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("namespace", "generic_flow", "test"));
+    let reactive_flow_instance = ReactiveFlowInstance::new(wrapper_entity_instance.clone());
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    reactive_flow_instance.add_entity(entity_instance.clone());
+    let relation_instance = Arc::new(ReactiveRelationInstance::new_from_type_with_properties(
+        r_string(),
+        wrapper_entity_instance.clone(),
+        r_string(),
+        entity_instance.clone(),
+        vec![("y".to_string(), json!(0))].into_iter().collect(),
+    ));
+    reactive_flow_instance.add_relation(relation_instance.clone());
+    reactive_flow_instance.set("test", json!("a"));
+    entity_instance.set("x", json!("b"));
+
+    let snapshot = reactive_flow_instance.snapshot();
+    assert_eq!(reactive_flow_instance.id, snapshot.id);
+    assert_eq!(2, snapshot.entity_instances.len());
+    assert_eq!(json!("b"), snapshot.get_entity_instance(entity_instance.id).unwrap().properties["x"]);
+    assert_eq!(json!(0), snapshot.get_relation_instance(&relation_instance.get_key()).unwrap().properties["y"]);
+
+    // The snapshot can be stored
+    let snapshot_json = serde_json::to_value(&snapshot).unwrap();
+    assert_eq!(snapshot, serde_json::from_value::<FlowSnapshot>(snapshot_json).unwrap());
+
+    let observed = Arc::new(RwLock::new(Vec::new()));
+    let inner_observed = observed.clone();
+    let _subscription = entity_instance
+        .subscribe("x", move |v| inner_observed.write().unwrap().push(v.clone()))
+        .unwrap();
+
+    // Restore without propagation
+    reactive_flow_instance.set("test", json!("c"));
+    entity_instance.set("x", json!("d"));
+    relation_instance.set("y", json!(1));
+    reactive_flow_instance.restore(&snapshot, false).unwrap();
+    assert_eq!(json!("a"), reactive_flow_instance.get("test").unwrap());
+    assert_eq!(json!("b"), entity_instance.get("x").unwrap());
+    assert_eq!(json!(0), relation_instance.get("y").unwrap());
+    assert_eq!(vec![json!("d")], *observed.read().unwrap());
+
+    // Restore with propagation
+    entity_instance.set("x", json!("e"));
+    reactive_flow_instance.restore(&snapshot, true).unwrap();
+    assert_eq!(json!("b"), entity_instance.get("x").unwrap());
+    assert_eq!(vec![json!("d"), json!("e"), json!("b")], *observed.read().unwrap());
+
+    // Instances which have been added after the snapshot are not changed
+    let added_entity_instance = Arc::new(create_random_entity_instance("z"));
+    reactive_flow_instance.add_entity(added_entity_instance.clone());
+    added_entity_instance.set("z", json!(5));
+    reactive_flow_instance.restore(&snapshot, true).unwrap();
+    assert_eq!(json!(5), added_entity_instance.get("z").unwrap());
+
+    let mut foreign_snapshot = snapshot.clone();
+    foreign_snapshot.id = Uuid::new_v4();
+    assert_eq!(
+        Err(FlowSnapshotRestoreError::FlowMismatch {
+            expected: reactive_flow_instance.id,
+            actual: foreign_snapshot.id,
+        }),
+        reactive_flow_instance.restore(&foreign_snapshot, true)
+    );
+}
@@ -9,6 +9,7 @@ use crate::Extension;
 use crate::ExtensionTypeId;
 use crate::FlowType;
 use crate::FlowTypeId;
+use crate::FlowTypeInstantiationError;
 use crate::NamespacedTypeGetter;
 use crate::PropertyType;
 use crate::RelationInstance;
@@ -39,7 +40,7 @@ fn create_flow_type_test() {
     let mut variables = Vec::new();
     let variable_name = "variable_name";
     let variable_data_type = DataType::Object;
-    let variable = PropertyType::new(variable_name, variable_data_type);
+    let variable = PropertyType::new(variable_name, variable_data_type.clone());
     variables.push(variable.clone());
 
     let mut extensions = Vec::new();
@@ -156,3 +157,81 @@ fn create_flow_type_test() {
     assert_eq!(2, flow_type.extensions.len());
     assert!(!flow_type.has_extension(&extension_3_ty));
 }
+
+#[test]
+fn flow_type_instantiate_test() {
+    let namespace = r_string();
+    let wrapper_entity_instance = create_entity_instance("name");
+    let mut entity_instance_2 = create_entity_instance("name");
+    entity_instance_2.properties.insert("name".to_string(), json!("{{name}}"));
+    entity_instance_2
+        .properties
+        .insert("label".to_string(), json!("Hello {{ name }}, you are {{age}} years old"));
+    entity_instance_2
+        .properties
+        .insert("nested".to_string(), json!({ "values": ["{{age}}", "{{unknown}}"] }));
+    let external_id = Uuid::new_v4();
+    let r_ty = RelationInstanceTypeId::new_from_type_unique_id(&namespace, &r_string());
+    let relation_instance_1 = RelationInstance::new(wrapper_entity_instance.id, r_ty.clone(), entity_instance_2.id, HashMap::new());
+    let mut relation_properties = HashMap::new();
+    relation_properties.insert("weight".to_string(), json!("{{age}}"));
+    let relation_instance_2 = RelationInstance::new(entity_instance_2.id, r_ty.clone(), external_id, relation_properties);
+    let flow_type = FlowType::new(
+        FlowTypeId::new_from_type(&namespace, &r_string()),
+        r_string(),
+        wrapper_entity_instance.clone(),
+        vec![entity_instance_2.clone()],
+        vec![relation_instance_1, relation_instance_2],
+        vec![PropertyType::string("name"), PropertyType::new("age", DataType::Integer)],
+        Vec::new(),
+    );
+
+    let mut variables = HashMap::new();
+    variables.insert("name".to_string(), json!("Alice"));
+    variables.insert("age".to_string(), json!(42));
+    let flow_instance = flow_type.instantiate(&variables).unwrap();
+
+    assert_eq!(flow_type.description, flow_instance.description);
+    assert_eq!(wrapper_entity_instance.ty, flow_instance.ty);
+    assert_eq!(2, flow_instance.entity_instances.len());
+    assert_eq!(2, flow_instance.relation_instances.len());
+    let wrapper = &flow_instance.entity_instances[0];
+    let entity_instance = &flow_instance.entity_instances[1];
+    assert_eq!(flow_instance.id, wrapper.id);
+    assert_ne!(wrapper_entity_instance.id, wrapper.id);
+    assert_ne!(entity_instance_2.id, entity_instance.id);
+    assert_eq!(wrapper_entity_instance.properties, wrapper.properties);
+    assert_eq!(json!("Alice"), *entity_instance.properties.get("name").unwrap());
+    assert_eq!(json!("Hello Alice, you are 42 years old"), *entity_instance.properties.get("label").unwrap());
+    assert_eq!(json!({ "values": [42, "{{unknown}}"] }), *entity_instance.properties.get("nested").unwrap());
+
+    let relation_instance = &flow_instance.relation_instances[0];
+    assert_eq!(wrapper.id, relation_instance.outbound_id);
+    assert_eq!(entity_instance.id, relation_instance.inbound_id);
+    let relation_instance = &flow_instance.relation_instances[1];
+    assert_eq!(entity_instance.id, relation_instance.outbound_id);
+    assert_eq!(external_id, relation_instance.inbound_id);
+    assert_eq!(json!(42), *relation_instance.properties.get("weight").unwrap());
+
+    // Each instantiation creates new ids
+    let flow_instance_2 = flow_type.instantiate(&variables).unwrap();
+    assert_ne!(flow_instance.id, flow_instance_2.id);
+
+    variables.remove("age");
+    assert_eq!(FlowTypeInstantiationError::MissingVariable("age".to_string()), flow_type.instantiate(&variables).unwrap_err());
+    variables.insert("age".to_string(), json!("42"));
+    assert_eq!(
+        FlowTypeInstantiationError::InvalidVariableValue {
+            variable_name: "age".to_string(),
+            expected: DataType::Integer,
+            value: json!("42"),
+        },
+        flow_type.instantiate(&variables).unwrap_err()
+    );
+    variables.insert("age".to_string(), json!(42));
+    variables.insert("unknown".to_string(), json!(1));
+    assert_eq!(
+        FlowTypeInstantiationError::UnknownVariable("unknown".to_string()),
+        flow_type.instantiate(&variables).unwrap_err()
+    );
+}
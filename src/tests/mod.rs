@@ -2,26 +2,41 @@ mod data_type_test;
 mod socket_type_test;
 
 mod component_test;
+mod effective_property_test;
 mod entity_type_test;
 mod flow_type_test;
 mod property_type_test;
 mod relation_type_test;
+mod serialization_format_test;
+mod type_registry_test;
 
 mod entity_instance_test;
 mod relation_instance_test;
+mod type_migration_test;
+mod validation_test;
 
+mod flow_graph_test;
 mod flow_instance_test;
+mod flow_patch_test;
 
+mod change_history_test;
+mod property_instance_accessor_test;
 mod reactive_entity_instance_test;
 mod reactive_property_instance_test;
 mod reactive_relation_instance_test;
 
+mod flow_change_log_test;
+mod flow_snapshot_test;
 mod reactive_flow_instance_test;
 
+#[cfg(feature = "async")]
+mod property_stream_test;
+
 mod behaviour_type_id_test;
 mod component_type_id_test;
 mod entity_type_id_test;
 mod flow_type_id_test;
+mod model_error_test;
 mod relation_instance_type_id_test;
 mod relation_type_id_test;
 mod type_definition_test;
@@ -0,0 +1,157 @@
+use indradb::Identifier;
+use indradb::NamedProperty;
+use indradb::Vertex;
+use indradb::VertexProperties;
+use serde_json::json;
+
+use crate::tests::utils::r_string;
+use crate::ComponentOrEntityTypeId;
+use crate::ComponentTypeId;
+use crate::EntityInstance;
+use crate::EntityTypeId;
+use crate::ModelError;
+use crate::ReactiveEntityInstance;
+use crate::RelationInstanceTypeId;
+use crate::TypeDefinition;
+use crate::TypeIdType;
+use crate::BEHAVIOURS_PROPERTY;
+use crate::COMPONENTS_PROPERTY;
+use crate::DESCRIPTION_PROPERTY;
+use crate::EXTENSIONS_PROPERTY;
+use crate::PROPERTY_NAMES_PROPERTY;
+
+#[test]
+fn model_error_type_id_type_test() {
+    assert_eq!(Err(ModelError::UnknownTypeIdType("z".to_string())), TypeIdType::try_from("z"));
+    assert_eq!(Err(ModelError::UnknownTypeIdType(String::new())), TypeIdType::try_from(""));
+}
+
+#[test]
+fn model_error_type_id_test() {
+    let namespace = r_string();
+    let type_name = r_string();
+
+    let type_id = format!("x__{namespace}__{type_name}");
+    assert_eq!(
+        Err(ModelError::TypeIdTypeMismatch {
+            expected: TypeIdType::EntityType,
+            actual: TypeIdType::Extension,
+        }),
+        EntityTypeId::try_from(&type_id)
+    );
+
+    let type_id = format!("q__{namespace}__{type_name}");
+    assert_eq!(Err(ModelError::UnknownTypeIdType("q".to_string())), EntityTypeId::try_from(&type_id));
+
+    let type_id = "e".to_string();
+    assert_eq!(Err(ModelError::MissingSeparator(type_id.clone())), EntityTypeId::try_from(&type_id));
+
+    let type_id = format!("e__{namespace}");
+    assert_eq!(Err(ModelError::MissingSeparator(type_id.clone())), EntityTypeId::try_from(&type_id));
+
+    let type_id = format!("e____{type_name}");
+    assert_eq!(Err(ModelError::EmptyNamespace(type_id.clone())), EntityTypeId::try_from(&type_id));
+
+    let type_id = format!("e__{namespace}__");
+    assert_eq!(Err(ModelError::EmptyTypeName(type_id.clone())), EntityTypeId::try_from(&type_id));
+
+    let type_id = format!("e__{namespace}__{type_name}__{}", r_string());
+    assert_eq!(Err(ModelError::TrailingSegment(type_id.clone())), EntityTypeId::try_from(&type_id));
+
+    let t = Identifier::new(format!("c__{namespace}")).unwrap();
+    assert_eq!(Err(ModelError::MissingSeparator(t.to_string())), ComponentTypeId::try_from(&t));
+    assert_eq!(Err(ModelError::MissingSeparator(t.to_string())), TypeDefinition::try_from(&t));
+    let t = Identifier::new(format!("c____{type_name}")).unwrap();
+    assert_eq!(Err(ModelError::EmptyNamespace(t.to_string())), TypeDefinition::try_from(&t));
+    let t = Identifier::new(format!("c__{namespace}__")).unwrap();
+    assert_eq!(Err(ModelError::EmptyTypeName(t.to_string())), TypeDefinition::try_from(&t));
+    let t = Identifier::new(format!("c__{namespace}__{type_name}__{}", r_string())).unwrap();
+    assert_eq!(Err(ModelError::TrailingSegment(t.to_string())), TypeDefinition::try_from(&t));
+
+    let type_id = format!("r____{type_name}");
+    assert_eq!(Err(ModelError::EmptyNamespace(type_id.clone())), RelationInstanceTypeId::try_from(&type_id));
+
+    let type_definition = TypeDefinition::component(&namespace, &type_name);
+    assert_eq!(
+        Err(ModelError::TypeIdTypeMismatch {
+            expected: TypeIdType::EntityType,
+            actual: TypeIdType::Component,
+        }),
+        EntityTypeId::try_from(&type_definition)
+    );
+
+    let ty = ComponentOrEntityTypeId::Component(ComponentTypeId::new_from_type(&namespace, &type_name));
+    assert_eq!(
+        Err(ModelError::TypeIdTypeMismatch {
+            expected: TypeIdType::EntityType,
+            actual: TypeIdType::Component,
+        }),
+        EntityTypeId::try_from(ty)
+    );
+}
+
+#[test]
+fn model_error_vertex_properties_test() {
+    let t = Identifier::new(format!("c__{}__{}", r_string(), r_string())).unwrap();
+    let vertex_properties = VertexProperties::new(Vertex::new(t), Vec::new());
+    let result = EntityInstance::try_from(vertex_properties);
+    assert_eq!(
+        ModelError::TypeIdTypeMismatch {
+            expected: TypeIdType::EntityType,
+            actual: TypeIdType::Component,
+        },
+        result.unwrap_err()
+    );
+}
+
+#[test]
+fn model_error_invalid_property_test() {
+    let t = Identifier::new(format!("e__{}__{}", r_string(), r_string())).unwrap();
+    for property_name in [DESCRIPTION_PROPERTY, EXTENSIONS_PROPERTY, PROPERTY_NAMES_PROPERTY] {
+        let properties = vec![NamedProperty::new(Identifier::new(property_name).unwrap(), json!(42))];
+        let vertex_properties = VertexProperties::new(Vertex::new(t.clone()), properties);
+        assert!(matches!(
+            EntityInstance::try_from(vertex_properties),
+            Err(ModelError::InvalidProperty { property_name: name, .. }) if name == property_name
+        ));
+    }
+    for property_name in [COMPONENTS_PROPERTY, BEHAVIOURS_PROPERTY] {
+        let properties = vec![NamedProperty::new(Identifier::new(property_name).unwrap(), json!(["e__a"]))];
+        let vertex_properties = VertexProperties::new(Vertex::new(t.clone()), properties);
+        assert!(matches!(
+            ReactiveEntityInstance::try_from(vertex_properties),
+            Err(ModelError::InvalidProperty { property_name: name, .. }) if name == property_name
+        ));
+    }
+}
+
+#[test]
+fn model_error_display_test() {
+    assert_eq!("Unknown type of type: z", format!("{}", ModelError::UnknownTypeIdType("z".to_string())));
+    assert_eq!(
+        "Expected type of type Entity but found Component",
+        format!(
+            "{}",
+            ModelError::TypeIdTypeMismatch {
+                expected: TypeIdType::EntityType,
+                actual: TypeIdType::Component,
+            }
+        )
+    );
+    assert_eq!("The type id e is missing the separator __", format!("{}", ModelError::MissingSeparator("e".to_string())));
+    assert_eq!(
+        "The namespace of the type id e____a is empty",
+        format!("{}", ModelError::EmptyNamespace("e____a".to_string()))
+    );
+    assert_eq!("The type name of the type id e__a__ is empty", format!("{}", ModelError::EmptyTypeName("e__a__".to_string())));
+    assert_eq!(
+        "The property a could not be converted: b",
+        format!(
+            "{}",
+            ModelError::InvalidProperty {
+                property_name: "a".to_string(),
+                reason: "b".to_string()
+            }
+        )
+    );
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use serde_json::json;
+
+use crate::component_model;
+use crate::entity_model;
+use crate::tests::utils::create_entity_instance;
+use crate::tests::utils::create_random_entity_instance;
+use crate::Mutability::Mutable;
+use crate::MutablePropertyInstanceSetter;
+use crate::PropertyInstanceGetter;
+use crate::PropertyInstanceSetter;
+use crate::ReactiveEntityInstance;
+use crate::ReactivePropertyContainer;
+
+entity_model!(Counter, data value f64, data label string, get result u64, get step i64, set step i64, set name string, get name string, readonly total u64, trigger);
+
+component_model!(Labeled, data label string);
+
+impl Labeled for ReactiveEntityInstance {}
+
+#[test]
+fn typed_setter_test() {
+    let entity_instance = create_random_entity_instance("x");
+    entity_instance.set_bool("x", true);
+    assert_eq!(json!(true), entity_instance.get("x").unwrap());
+    entity_instance.set_u64("x", 1);
+    assert_eq!(1, entity_instance.as_u64("x").unwrap());
+    entity_instance.set_i64("x", -1);
+    assert_eq!(-1, entity_instance.as_i64("x").unwrap());
+    entity_instance.set_f64("x", 1.5);
+    assert_eq!(1.5, entity_instance.as_f64("x").unwrap());
+    entity_instance.set_string("x", "y");
+    assert_eq!("y", entity_instance.as_string("x").unwrap());
+
+    let mut entity_instance = create_entity_instance("x");
+    entity_instance.set_f64("x", 2.5);
+    assert_eq!(2.5, entity_instance.as_f64("x").unwrap());
+}
+
+#[test]
+fn entity_model_test() {
+    let entity_instance = Arc::new(create_random_entity_instance("x"));
+    entity_instance.add_property(Counter::VALUE, Mutable, json!(0.0));
+    entity_instance.add_property(Counter::LABEL, Mutable, json!(""));
+    entity_instance.add_property(Counter::RESULT, Mutable, json!(0));
+    entity_instance.add_property(Counter::STEP, Mutable, json!(0));
+    entity_instance.add_property(Counter::TOTAL, Mutable, json!(0));
+    entity_instance.add_property(Counter::NAME, Mutable, json!(""));
+    entity_instance.add_property("trigger", Mutable, json!(false));
+    let counter = Counter::from(entity_instance.clone());
+    assert_eq!("value", Counter::VALUE);
+    assert_eq!("label", Counter::LABEL);
+    assert_eq!("result", Counter::RESULT);
+    assert_eq!("step", Counter::STEP);
+    assert_eq!("total", Counter::TOTAL);
+    assert_eq!("name", Counter::NAME);
+
+    let observed = Arc::new(RwLock::new(Vec::new()));
+    let inner_observed = observed.clone();
+    let subscription = counter.on_value_changed(move |v: f64| inner_observed.write().unwrap().push(v)).unwrap();
+    counter.set_value(1.5);
+    assert_eq!(Some(1.5), counter.get_value());
+    // Values of another type are not passed to the observer
+    counter.set(Counter::VALUE, json!("not a number"));
+    counter.set_value(2.5);
+    assert_eq!(vec![1.5, 2.5], *observed.read().unwrap());
+    drop(subscription);
+
+    let observed_result = Arc::new(RwLock::new(None));
+    let inner_observed_result = observed_result.clone();
+    let _subscription = counter
+        .on_result_changed(move |v: u64| *inner_observed_result.write().unwrap() = Some(v))
+        .unwrap();
+    entity_instance.set("result", json!(42));
+    assert_eq!(Some(42), counter.result());
+    assert_eq!(Some(42), *observed_result.read().unwrap());
+
+    let observed_step = Arc::new(RwLock::new(None));
+    let inner_observed_step = observed_step.clone();
+    let _subscription = counter.on_step_changed(move |v: i64| *inner_observed_step.write().unwrap() = Some(v)).unwrap();
+    counter.set_step(-1);
+    assert_eq!(Some(-1), counter.step());
+    assert_eq!(Some(-1), *observed_step.read().unwrap());
+
+    let observed_name = Arc::new(RwLock::new(None));
+    let inner_observed_name = observed_name.clone();
+    let _subscription = counter
+        .on_name_changed(move |v: String| *inner_observed_name.write().unwrap() = Some(v))
+        .unwrap();
+    counter.set_name("counter");
+    assert_eq!(Some("counter".to_string()), counter.name());
+    assert_eq!(Some("counter".to_string()), *observed_name.read().unwrap());
+
+    let observed_total = Arc::new(RwLock::new(None));
+    let inner_observed_total = observed_total.clone();
+    let _subscription = counter
+        .on_total_changed(move |v: u64| *inner_observed_total.write().unwrap() = Some(v))
+        .unwrap();
+    entity_instance.set(Counter::TOTAL, json!(7));
+    assert_eq!(Some(7), counter.get_total());
+    assert_eq!(Some(7), *observed_total.read().unwrap());
+
+    counter.set_label("counter");
+    assert_eq!(Some("counter".to_string()), counter.get_label());
+    assert!(counter.on_label_changed(|_| {}).is_some());
+    counter.trigger();
+    assert_eq!(json!(true), entity_instance.get("trigger").unwrap());
+
+    // Observing a missing property is not possible
+    entity_instance.remove_property(Counter::VALUE);
+    assert!(counter.on_value_changed(|_| {}).is_none());
+}
+
+#[test]
+fn component_model_test() {
+    let entity_instance = create_random_entity_instance("x");
+    entity_instance.add_property(<ReactiveEntityInstance as Labeled>::LABEL, Mutable, json!(""));
+    let observed = Arc::new(RwLock::new(Vec::new()));
+    let inner_observed = observed.clone();
+    let _subscription = entity_instance
+        .on_label_changed(move |v: String| inner_observed.write().unwrap().push(v))
+        .unwrap();
+    entity_instance.set_label("a");
+    assert_eq!(Some("a".to_string()), entity_instance.get_label());
+    assert_eq!(vec!["a".to_string()], *observed.read().unwrap());
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use std::thread;
+
+use futures::executor::block_on;
+use futures::StreamExt;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::create_random_entity_instance;
+use crate::tests::utils::r_string;
+use crate::Mutability::Mutable;
+use crate::OverflowPolicy;
+use crate::PropertyInstanceSetter;
+use crate::ReactivePropertyContainer;
+use crate::ReactivePropertyInstance;
+
+#[test]
+fn property_stream_overflow_drop_oldest_test() {
+    let property_name = r_string();
+    let reactive_entity_instance = create_random_entity_instance(&property_name);
+    let mut stream = reactive_entity_instance.property_stream(&property_name, 2, OverflowPolicy::DropOldest).unwrap();
+    for i in 0..5 {
+        reactive_entity_instance.set(&property_name, json!(i));
+    }
+    assert_eq!(2, stream.buffered_len());
+    assert_eq!(3, stream.dropped());
+    assert_eq!(Some(json!(3)), block_on(stream.next()));
+    assert_eq!(Some(json!(4)), block_on(stream.next()));
+
+    // The stream ends when the property is removed
+    reactive_entity_instance.remove_property(&property_name);
+    assert_eq!(None, block_on(stream.next()));
+    assert!(reactive_entity_instance
+        .property_stream(&property_name, 2, OverflowPolicy::DropOldest)
+        .is_none());
+}
+
+#[test]
+fn property_stream_overflow_drop_newest_test() {
+    let reactive_property_instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), Mutable, json!(0));
+    let mut stream = reactive_property_instance.property_stream(2, OverflowPolicy::DropNewest);
+    assert_eq!(OverflowPolicy::DropNewest, stream.overflow());
+    for i in 0..5 {
+        reactive_property_instance.set(json!(i));
+    }
+    assert_eq!(3, stream.dropped());
+    assert_eq!(Some(json!(0)), block_on(stream.next()));
+    assert_eq!(Some(json!(1)), block_on(stream.next()));
+
+    // Dropping the stream removes the observer
+    assert_eq!(1, reactive_property_instance.sync_stream.len());
+    drop(stream);
+    assert!(reactive_property_instance.sync_stream.is_empty());
+}
+
+#[test]
+fn property_stream_overflow_block_test() {
+    let reactive_property_instance = Arc::new(ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), Mutable, json!(0)));
+    let stream = reactive_property_instance.property_stream(1, OverflowPolicy::Block);
+    let producer = reactive_property_instance.clone();
+    let handle = thread::spawn(move || {
+        for i in 0..100 {
+            producer.set(json!(i));
+        }
+    });
+    // No value gets lost
+    let values: Vec<i64> = block_on(stream.take(100).map(|v| v.as_i64().unwrap()).collect());
+    handle.join().unwrap();
+    assert_eq!((0..100).collect::<Vec<i64>>(), values);
+}
+
+#[test]
+fn property_stream_overflow_block_get_test() {
+    let reactive_property_instance = Arc::new(ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), Mutable, json!(0)));
+    let mut stream = reactive_property_instance.property_stream(1, OverflowPolicy::Block);
+    reactive_property_instance.set(json!(1));
+    let producer = reactive_property_instance.clone();
+    let handle = thread::spawn(move || producer.set(json!(2)));
+    // The blocked producer doesn't hold the lock of the value
+    while reactive_property_instance.get() != json!(2) {
+        thread::yield_now();
+    }
+    assert_eq!(Some(json!(1)), block_on(stream.next()));
+    assert_eq!(Some(json!(2)), block_on(stream.next()));
+    handle.join().unwrap();
+}
+
+#[test]
+fn property_stream_drop_unblocks_producer_test() {
+    let reactive_property_instance = Arc::new(ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), Mutable, json!(0)));
+    let stream = reactive_property_instance.property_stream(1, OverflowPolicy::Block);
+    reactive_property_instance.set(json!(1));
+    let producer = reactive_property_instance.clone();
+    let handle = thread::spawn(move || producer.set(json!(2)));
+    drop(stream);
+    handle.join().unwrap();
+    assert_eq!(json!(2), reactive_property_instance.get());
+}
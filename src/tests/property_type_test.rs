@@ -1,6 +1,7 @@
 use crate::tests::utils::r_string;
 use crate::DataType;
 use crate::Mutability;
+use crate::PropagationMode;
 use crate::PropertyType;
 use crate::SocketType;
 
@@ -14,6 +15,7 @@ fn property_type_test() {
         data_type: DataType::String,
         socket_type: SocketType::None,
         mutability: Mutability::Mutable,
+        propagation: PropagationMode::Always,
         extensions: Vec::new(),
     };
 
@@ -31,6 +33,7 @@ fn property_type_serde_test() {
         data_type: DataType::String,
         socket_type: SocketType::None,
         mutability: Mutability::Mutable,
+        propagation: PropagationMode::Always,
         extensions: Vec::new(),
     };
 
@@ -43,6 +46,19 @@ fn property_type_serde_test() {
     assert_eq!(property_name.clone(), property_type_2.name);
     assert_eq!(DataType::String, property_type_2.data_type);
     assert_eq!(SocketType::None, property_type_2.socket_type);
+    assert_eq!(PropagationMode::Always, property_type_2.propagation);
+}
+
+#[test]
+fn property_type_propagation_serde_test() {
+    let property_type: PropertyType = serde_json::from_str(r#"{"name": "x", "data_type": "number"}"#).unwrap();
+    assert_eq!(PropagationMode::Always, property_type.propagation);
+
+    let property_type: PropertyType = serde_json::from_str(r#"{"name": "x", "data_type": "number", "propagation": "on_change"}"#).unwrap();
+    assert_eq!(PropagationMode::OnChange, property_type.propagation);
+
+    let property_type: PropertyType = serde_json::from_str(r#"{"name": "x", "data_type": "number", "propagation": {"on_change_with_epsilon": 0.1}}"#).unwrap();
+    assert_eq!(PropagationMode::OnChangeWithEpsilon(0.1), property_type.propagation);
 }
 
 #[test]
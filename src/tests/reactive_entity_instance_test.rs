@@ -5,6 +5,8 @@ use std::sync::Arc;
 use std::sync::RwLock;
 use test::Bencher;
 
+use chrono::TimeZone;
+use chrono::Utc;
 use dashmap::DashMap;
 use dashmap::DashSet;
 use indradb::NamedProperty;
@@ -24,6 +26,7 @@ use crate::ComponentTypeId;
 use crate::DataType;
 use crate::EntityInstance;
 use crate::EntityTypeId;
+use crate::Mutability::Immutable;
 use crate::Mutability::Mutable;
 use crate::NamespacedTypeGetter;
 use crate::PropertyInstanceGetter;
@@ -69,6 +72,7 @@ fn reactive_entity_instance_test() {
     let reactive_entity_instance = Arc::new(ReactiveEntityInstance {
         ty: ty.clone(),
         id: uuid.clone(),
+        version: None,
         description: description.clone(),
         properties,
         components,
@@ -196,6 +200,28 @@ fn reactive_entity_instance_typed_getter_test() {
     assert_eq!(json!(s), i.get(property_name.clone()).unwrap());
 }
 
+#[test]
+fn reactive_entity_instance_typed_setter_test() {
+    let property_name = r_string();
+    let i = create_random_entity_instance(property_name.clone());
+    let uuid = Uuid::new_v4();
+    i.set_uuid(property_name.clone(), uuid);
+    assert_eq!(json!(uuid.to_string()), i.get(property_name.clone()).unwrap());
+    assert_eq!(uuid, i.as_uuid(property_name.clone()).unwrap());
+    let date_time = Utc.with_ymd_and_hms(2022, 11, 6, 12, 30, 0).unwrap();
+    i.set_date_time(property_name.clone(), date_time);
+    assert_eq!(json!("2022-11-06T12:30:00+00:00"), i.get(property_name.clone()).unwrap());
+    assert_eq!(date_time, i.as_date_time(property_name.clone()).unwrap());
+    i.set(property_name.clone(), json!("2022-11-06T13:30:00+01:00"));
+    assert_eq!(date_time, i.as_date_time(property_name.clone()).unwrap());
+    i.set_bytes(property_name.clone(), b"hello");
+    assert_eq!(json!("aGVsbG8="), i.get(property_name.clone()).unwrap());
+    assert_eq!(b"hello".to_vec(), i.as_bytes(property_name.clone()).unwrap());
+    i.set(property_name.clone(), json!(r_string()));
+    assert!(i.as_uuid(property_name.clone()).is_none());
+    assert!(i.as_date_time(property_name.clone()).is_none());
+}
+
 #[test]
 fn reactive_entity_instance_typed_eq_bool_test() {
     let property_name = r_string();
@@ -227,6 +253,7 @@ fn reactive_entity_instance_stream_test() {
     let reactive_entity_instance = Arc::new(ReactiveEntityInstance {
         ty: ty.clone(),
         id: Uuid::new_v4(),
+        version: None,
         description: r_string(),
         properties: DashMap::new(),
         components: DashSet::new(),
@@ -308,6 +335,58 @@ fn reactive_entity_instance_stream_test() {
     assert_eq!(modified_value, outer_value.read().unwrap().as_str().unwrap());
 }
 
+#[test]
+fn reactive_entity_instance_set_all_test() {
+    let reactive_entity_instance = Arc::new(ReactiveEntityInstance {
+        ty: EntityTypeId::new_from_type(r_string(), r_string()),
+        id: Uuid::new_v4(),
+        version: None,
+        description: r_string(),
+        properties: DashMap::new(),
+        components: DashSet::new(),
+        behaviours: DashSet::new(),
+    });
+    reactive_entity_instance.add_property("position", Mutable, json!(0));
+    reactive_entity_instance.add_property("velocity", Mutable, json!(0));
+    reactive_entity_instance.add_property("mass", Immutable, json!(1));
+
+    // The observer of the position reads the velocity of the same instance
+    let observed_velocity = Arc::new(RwLock::new(json!(0)));
+    let inner_observed_velocity = observed_velocity.clone();
+    let inner_reactive_entity_instance = reactive_entity_instance.clone();
+    let handle_id = Uuid::new_v4().as_u128();
+    reactive_entity_instance.observe_with_handle(
+        "position",
+        move |_| {
+            let mut writer = inner_observed_velocity.write().unwrap();
+            *writer = inner_reactive_entity_instance.get("velocity").unwrap();
+        },
+        handle_id,
+    );
+
+    // Expected: The observer of the position sees the new velocity
+    let mut properties = serde_json::Map::new();
+    properties.insert("position".to_string(), json!(10));
+    properties.insert("velocity".to_string(), json!(5));
+    properties.insert(r_string(), json!(r_string()));
+    reactive_entity_instance.set_all(properties);
+    assert_eq!(10, reactive_entity_instance.as_u64("position").unwrap());
+    assert_eq!(5, reactive_entity_instance.as_u64("velocity").unwrap());
+    assert_eq!(json!(5), *observed_velocity.read().unwrap());
+
+    // Expected: Immutable properties are not modified
+    let mut properties = serde_json::Map::new();
+    properties.insert("velocity".to_string(), json!(7));
+    properties.insert("mass".to_string(), json!(2));
+    properties.insert("position".to_string(), json!(20));
+    reactive_entity_instance.set_all_checked(properties);
+    assert_eq!(20, reactive_entity_instance.as_u64("position").unwrap());
+    assert_eq!(1, reactive_entity_instance.as_u64("mass").unwrap());
+    assert_eq!(json!(7), *observed_velocity.read().unwrap());
+
+    reactive_entity_instance.remove_observer("position", handle_id);
+}
+
 #[bench]
 fn create_reactive_entity_instance_benchmark(bencher: &mut Bencher) -> impl Termination {
     let uuid = Uuid::new_v4();
@@ -339,6 +418,7 @@ fn create_reactive_entity_instance_benchmark(bencher: &mut Bencher) -> impl Term
         let _reactive_entity_instance = Arc::new(ReactiveEntityInstance {
             ty: ty.clone(),
             id: uuid.clone(),
+            version: None,
             description: description.clone(),
             properties,
             components,
@@ -346,3 +426,69 @@ fn create_reactive_entity_instance_benchmark(bencher: &mut Bencher) -> impl Term
         });
     })
 }
+
+#[test]
+fn reactive_entity_instance_subscribe_test() {
+    let property_name = r_string();
+    let reactive_entity_instance = create_random_entity_instance(&property_name);
+    let calls = Arc::new(RwLock::new(0));
+
+    let inner_calls = calls.clone();
+    let subscription = reactive_entity_instance
+        .subscribe(&property_name, move |_| *inner_calls.write().unwrap() += 1)
+        .unwrap();
+    assert!(subscription.is_active());
+    reactive_entity_instance.set(&property_name, json!(r_string()));
+    assert_eq!(1, *calls.read().unwrap());
+
+    // Dropping the subscription removes the observer
+    drop(subscription);
+    reactive_entity_instance.set(&property_name, json!(r_string()));
+    assert_eq!(1, *calls.read().unwrap());
+
+    // A detached observer is kept until it is removed explicitly
+    let inner_calls = calls.clone();
+    let handle_id = reactive_entity_instance
+        .subscribe(&property_name, move |_| *inner_calls.write().unwrap() += 1)
+        .unwrap()
+        .detach();
+    reactive_entity_instance.set(&property_name, json!(r_string()));
+    assert_eq!(2, *calls.read().unwrap());
+    reactive_entity_instance.remove_observer(&property_name, handle_id);
+    reactive_entity_instance.set(&property_name, json!(r_string()));
+    assert_eq!(2, *calls.read().unwrap());
+
+    // Subscriptions have unique handles
+    let subscription_1 = reactive_entity_instance.subscribe(&property_name, |_| {}).unwrap();
+    let subscription_2 = reactive_entity_instance.subscribe(&property_name, |_| {}).unwrap();
+    assert_ne!(subscription_1.handle_id(), subscription_2.handle_id());
+
+    // The subscription outlives the property
+    reactive_entity_instance.remove_property(&property_name);
+    assert!(!subscription_1.is_active());
+    subscription_1.unsubscribe();
+    assert!(reactive_entity_instance.subscribe(&property_name, |_| {}).is_none());
+}
+
+#[test]
+fn reactive_entity_instance_subscribe_sync_test() {
+    let property_name = r_string();
+    let reactive_entity_instance = Arc::new(create_random_entity_instance(&property_name));
+    let value = Arc::new(RwLock::new(json!(null)));
+
+    let inner_value = value.clone();
+    let subscription = reactive_entity_instance
+        .subscribe_sync(&property_name, move |v| *inner_value.write().unwrap() = v.clone())
+        .unwrap();
+    let worker_entity_instance = reactive_entity_instance.clone();
+    let worker_property_name = property_name.clone();
+    std::thread::spawn(move || worker_entity_instance.set(worker_property_name, json!("worker")))
+        .join()
+        .unwrap();
+    assert_eq!(json!("worker"), *value.read().unwrap());
+
+    drop(subscription);
+    reactive_entity_instance.set(&property_name, json!("main"));
+    assert_eq!(json!("worker"), *value.read().unwrap());
+    assert!(reactive_entity_instance.subscribe_sync(&r_string(), |_| {}).is_none());
+}
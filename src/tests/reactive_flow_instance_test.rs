@@ -1,7 +1,9 @@
 use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::sync::Arc;
+use std::sync::RwLock;
 
+use indradb::BulkInsertItem;
 use serde_json::json;
 use uuid::Uuid;
 
@@ -9,12 +11,20 @@ use crate::tests::utils::create_random_entity_instance;
 use crate::tests::utils::create_random_entity_instance_with_type;
 use crate::tests::utils::create_random_relation_instance;
 use crate::tests::utils::r_string;
+use crate::BehaviourTypeId;
+use crate::ComponentContainer;
+use crate::ComponentTypeId;
 use crate::FlowInstance;
 use crate::FlowInstanceCreationError;
+use crate::Mutability::Mutable;
 use crate::NamespacedTypeGetter;
 use crate::PropertyInstanceGetter;
 use crate::PropertyInstanceSetter;
+use crate::ReactiveBehaviourContainer;
 use crate::ReactiveFlowInstance;
+use crate::ReactiveFlowInstanceConstructionError;
+use crate::ReactivePropertyInstance;
+use crate::RESERVED_PROPERTY_PREFIX;
 
 #[test]
 fn reactive_flow_test() {
@@ -149,3 +159,125 @@ fn reactive_flow_test_try_fail_2() {
     let result: Result<FlowInstance, FlowInstanceCreationError> = reactive_flow_instance.try_into();
     assert!(result.is_err());
 }
+
+#[test]
+fn reactive_flow_indradb_round_trip_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("namespace", "generic_flow", "test"));
+    let reactive_flow_instance = ReactiveFlowInstance::new(wrapper_entity_instance.clone());
+    let second_entity_instance = Arc::new(create_random_entity_instance(r_string()));
+    reactive_flow_instance.add_entity(second_entity_instance.clone());
+    let relation_instance = Arc::new(create_random_relation_instance(wrapper_entity_instance.clone(), second_entity_instance.clone(), r_string()));
+    reactive_flow_instance.add_relation(relation_instance.clone());
+
+    let vertex_properties = reactive_flow_instance.vertex_properties();
+    let edge_properties = reactive_flow_instance.edge_properties();
+    assert_eq!(2, vertex_properties.len());
+    assert_eq!(1, edge_properties.len());
+
+    let items = reactive_flow_instance.bulk_insert_items();
+    assert_eq!(2, items.iter().filter(|item| matches!(item, BulkInsertItem::Vertex(_))).count());
+    assert_eq!(1, items.iter().filter(|item| matches!(item, BulkInsertItem::Edge(_))).count());
+    assert_eq!(
+        vertex_properties.iter().map(|v| v.props.len()).sum::<usize>(),
+        items.iter().filter(|item| matches!(item, BulkInsertItem::VertexProperty(..))).count()
+    );
+    assert_eq!(
+        edge_properties[0].props.len(),
+        items.iter().filter(|item| matches!(item, BulkInsertItem::EdgeProperty(..))).count()
+    );
+    // Vertices and edges must exist before their properties are inserted
+    let last_structural = items
+        .iter()
+        .rposition(|item| matches!(item, BulkInsertItem::Vertex(_) | BulkInsertItem::Edge(_)))
+        .unwrap();
+    assert_eq!(2, last_structural);
+
+    let reactive_flow_instance_2 = ReactiveFlowInstance::try_from_properties(reactive_flow_instance.id, vertex_properties, edge_properties).unwrap();
+    assert_eq!(reactive_flow_instance.id, reactive_flow_instance_2.id);
+    assert_eq!(reactive_flow_instance.ty, reactive_flow_instance_2.ty);
+    assert_eq!(wrapper_entity_instance.get("test"), reactive_flow_instance_2.get("test"));
+    let second_entity_instance_2 = reactive_flow_instance_2.get_entity(second_entity_instance.id).unwrap();
+    assert_eq!(second_entity_instance.ty, second_entity_instance_2.ty);
+    for property_instance in second_entity_instance.properties.iter() {
+        assert_eq!(Some(property_instance.get()), second_entity_instance_2.get(property_instance.key()));
+    }
+    let relation_instance_2 = reactive_flow_instance_2.get_relation(&relation_instance.get_key()).unwrap();
+    assert_eq!(relation_instance.ty, relation_instance_2.ty);
+    for property_instance in relation_instance.properties.iter() {
+        assert_eq!(Some(property_instance.get()), relation_instance_2.get(property_instance.key()));
+    }
+
+    assert!(matches!(
+        ReactiveFlowInstance::try_from_properties(Uuid::new_v4(), reactive_flow_instance.vertex_properties(), Vec::new()),
+        Err(ReactiveFlowInstanceConstructionError::MissingWrapperInstance)
+    ));
+}
+
+#[test]
+fn reactive_flow_indradb_round_trip_reserved_properties_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("namespace", "generic_flow", "test"));
+    let reactive_flow_instance = ReactiveFlowInstance::new(wrapper_entity_instance.clone());
+    let mut second_entity_instance = create_random_entity_instance(r_string());
+    second_entity_instance.description = r_string();
+    second_entity_instance.version = Some(3);
+    let second_entity_instance = Arc::new(second_entity_instance);
+    let component_ty = ComponentTypeId::new_from_type(r_string(), r_string());
+    second_entity_instance.add_component(component_ty.clone());
+    let behaviour_ty = BehaviourTypeId::new_from_type(r_string(), r_string());
+    second_entity_instance.add_behaviour(behaviour_ty.clone());
+    let long_property_name = "x".repeat(300);
+    let reserved_property_name = format!("{}{}", RESERVED_PROPERTY_PREFIX, "description");
+    for property_name in [long_property_name.clone(), reserved_property_name.clone()] {
+        let property_instance = ReactivePropertyInstance::new(second_entity_instance.id, property_name.clone(), Mutable, json!(r_string()));
+        second_entity_instance.properties.insert(property_name, property_instance);
+    }
+    reactive_flow_instance.add_entity(second_entity_instance.clone());
+    let mut relation_instance = create_random_relation_instance(wrapper_entity_instance.clone(), second_entity_instance.clone(), r_string());
+    relation_instance.description = r_string();
+    relation_instance.version = Some(2);
+    let relation_instance = Arc::new(relation_instance);
+    relation_instance.add_component(component_ty.clone());
+    relation_instance.add_behaviour(behaviour_ty.clone());
+    reactive_flow_instance.add_relation(relation_instance.clone());
+
+    let reactive_flow_instance_2 = ReactiveFlowInstance::try_from_properties(
+        reactive_flow_instance.id,
+        reactive_flow_instance.vertex_properties(),
+        reactive_flow_instance.edge_properties(),
+    )
+    .unwrap();
+    let second_entity_instance_2 = reactive_flow_instance_2.get_entity(second_entity_instance.id).unwrap();
+    assert_eq!(second_entity_instance.description, second_entity_instance_2.description);
+    assert_eq!(Some(3), second_entity_instance_2.version);
+    assert!(second_entity_instance_2.is_a(&component_ty));
+    assert!(second_entity_instance_2.behaves_as(&behaviour_ty));
+    assert_eq!(second_entity_instance.properties.len(), second_entity_instance_2.properties.len());
+    for property_instance in second_entity_instance.properties.iter() {
+        assert_eq!(Some(property_instance.get()), second_entity_instance_2.get(property_instance.key()));
+    }
+    assert!(second_entity_instance_2.get(&long_property_name).is_some());
+    assert!(second_entity_instance_2.get(&reserved_property_name).is_some());
+    let relation_instance_2 = reactive_flow_instance_2.get_relation(&relation_instance.get_key()).unwrap();
+    assert_eq!(relation_instance.description, relation_instance_2.description);
+    assert_eq!(Some(2), relation_instance_2.version);
+    assert!(relation_instance_2.is_a(&component_ty));
+    assert!(relation_instance_2.behaves_as(&behaviour_ty));
+}
+
+#[test]
+fn reactive_flow_subscribe_test() {
+    let wrapper_entity_instance = Arc::new(create_random_entity_instance_with_type("namespace", "generic_flow", "test"));
+    let reactive_flow_instance = ReactiveFlowInstance::new(wrapper_entity_instance.clone());
+    let value = Arc::new(RwLock::new(json!(null)));
+
+    let inner_value = value.clone();
+    let subscription = reactive_flow_instance
+        .subscribe("test", move |v| *inner_value.write().unwrap() = v.clone())
+        .unwrap();
+    wrapper_entity_instance.set("test", json!("abc"));
+    assert_eq!(json!("abc"), *value.read().unwrap());
+    drop(subscription);
+    reactive_flow_instance.set("test", json!("def"));
+    assert_eq!(json!("abc"), *value.read().unwrap());
+    assert!(reactive_flow_instance.subscribe(&r_string(), |_| {}).is_none());
+}
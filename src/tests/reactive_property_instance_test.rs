@@ -6,6 +6,7 @@ use std::process::Termination;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::RwLock;
 use std::thread;
 use test::Bencher;
@@ -18,7 +19,9 @@ use stopwatch2::Stopwatch;
 use uuid::Uuid;
 
 use crate::tests::utils::r_string;
+use crate::PropagationMode;
 use crate::ReactivePropertyInstance;
+use crate::SyncStream;
 
 #[test]
 fn reactive_property_instance_test() {
@@ -34,8 +37,13 @@ fn reactive_property_instance_test() {
         id: uuid,
         name: property_name.clone(),
         stream: Arc::new(RwLock::new(Stream::new())),
+        sync_stream: Arc::new(SyncStream::new()),
         mutability: Mutable,
-        value: RwLock::new(initial_property_value_json),
+        propagation: PropagationMode::Always,
+        value: RwLock::new(initial_property_value_json.clone()),
+        propagated_value: RwLock::new(initial_property_value_json),
+        history: RwLock::new(None),
+        send_lock: Mutex::new(()),
     };
 
     // Check that the meta data is correct
@@ -286,6 +294,73 @@ fn reactive_property_instance_stream_test() {
     assert_eq!(1, v.load(Ordering::Relaxed));
 }
 
+#[test]
+fn reactive_property_instance_propagation_test() {
+    let mut instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), Mutable, json!(0));
+    let count = Arc::new(AtomicU64::new(0));
+    {
+        let count = count.clone();
+        instance.stream.read().unwrap().observe(move |_| {
+            count.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+
+    // Always: Equal values are propagated
+    instance.set(json!(0));
+    assert_eq!(1, count.load(Ordering::Relaxed));
+
+    // OnChange: Equal values are not propagated
+    instance.set_propagation(PropagationMode::OnChange);
+    instance.set(json!(0));
+    assert_eq!(1, count.load(Ordering::Relaxed));
+    instance.set(json!(1));
+    assert_eq!(2, count.load(Ordering::Relaxed));
+    assert!(!instance.set_no_propagate_changed(json!(1)));
+    assert!(instance.set_no_propagate_changed(json!(2)));
+    // A manual tick is always propagated
+    instance.tick();
+    assert_eq!(3, count.load(Ordering::Relaxed));
+
+    // OnChangeWithEpsilon: Numbers which differ less than epsilon are not propagated
+    instance.set_propagation(PropagationMode::OnChangeWithEpsilon(0.5));
+    instance.set(json!(2.25));
+    assert_eq!(3, count.load(Ordering::Relaxed));
+    assert_eq!(json!(2.25), instance.get());
+    instance.set(json!(3));
+    assert_eq!(4, count.load(Ordering::Relaxed));
+    instance.set(json!("3"));
+    assert_eq!(5, count.load(Ordering::Relaxed));
+    instance.set(json!("3"));
+    assert_eq!(5, count.load(Ordering::Relaxed));
+}
+
+#[test]
+fn reactive_property_instance_propagation_drift_test() {
+    let epsilon = 0.1;
+    let mut instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), Mutable, json!(0.0));
+    instance.set_propagation(PropagationMode::OnChangeWithEpsilon(epsilon));
+    let sent = Arc::new(RwLock::new(Vec::new()));
+    {
+        let sent = sent.clone();
+        instance.stream.read().unwrap().observe(move |value| {
+            sent.write().unwrap().push(value.clone());
+        });
+    }
+
+    // Each step differs by less than epsilon, but the drift from the last sent value doesn't
+    for value in [0.0, 0.6 * epsilon, 1.2 * epsilon, 1.8 * epsilon] {
+        instance.set(json!(value));
+    }
+    assert_eq!(vec![json!(1.2 * epsilon)], *sent.read().unwrap());
+    assert_eq!(json!(1.8 * epsilon), instance.get());
+
+    // Values which haven't been sent are compared with the last sent value as well
+    assert!(instance.set_no_propagate_changed(json!(2.4 * epsilon)));
+    instance.tick();
+    assert!(!instance.set_no_propagate_changed(json!(3.0 * epsilon)));
+    assert_eq!(vec![json!(1.2 * epsilon), json!(2.4 * epsilon)], *sent.read().unwrap());
+}
+
 // TODO: implement cycle loop protection!
 #[test]
 #[ignore]
@@ -434,3 +509,88 @@ fn reactive_property_instance_stream_mt_benchmark() {
         s1.elapsed()
     );
 }
+
+#[test]
+fn reactive_property_instance_subscribe_sync_test() {
+    let reactive_property_instance = Arc::new(ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), Mutable, json!(0)));
+    let calls = Arc::new(AtomicU64::new(0));
+    let threads = Arc::new(RwLock::new(Vec::new()));
+
+    let inner_calls = calls.clone();
+    let inner_threads = threads.clone();
+    let subscription = reactive_property_instance.subscribe_sync(move |_| {
+        inner_calls.fetch_add(1, Ordering::SeqCst);
+        inner_threads.write().unwrap().push(thread::current().id());
+    });
+    assert_eq!(1, reactive_property_instance.sync_stream.len());
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let reactive_property_instance = reactive_property_instance.clone();
+            thread::spawn(move || {
+                for j in 0..10 {
+                    reactive_property_instance.set(json!(i * 10 + j));
+                }
+                thread::current().id()
+            })
+        })
+        .collect();
+    let worker_threads: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert_eq!(40, calls.load(Ordering::SeqCst));
+    // The observer has been called on the worker threads
+    assert!(threads.read().unwrap().iter().all(|id| worker_threads.contains(id)));
+
+    // Thread-safe observers are called after a tick as well
+    reactive_property_instance.tick();
+    assert_eq!(41, calls.load(Ordering::SeqCst));
+
+    drop(subscription);
+    assert!(reactive_property_instance.sync_stream.is_empty());
+    reactive_property_instance.set(json!(-1));
+    assert_eq!(41, calls.load(Ordering::SeqCst));
+}
+
+#[test]
+fn reactive_property_instance_concurrent_set_order_test() {
+    let mut instance = ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), Mutable, json!(0));
+    instance.set_propagation(PropagationMode::OnChange);
+    let instance = Arc::new(instance);
+    let sent = Arc::new(RwLock::new(Vec::new()));
+    let inner_sent = sent.clone();
+    let _subscription = instance.subscribe_sync(move |value| inner_sent.write().unwrap().push(value.clone()));
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let instance = instance.clone();
+            thread::spawn(move || {
+                for j in 0..1000 {
+                    instance.set(json!((i + j) % 2));
+                }
+            })
+        })
+        .collect();
+    handles.into_iter().for_each(|handle| handle.join().unwrap());
+
+    // Values are sent in the order in which they have been set, so the value which has been sent
+    // last is the current value and equal values are never sent twice in a row
+    let sent = sent.read().unwrap();
+    assert_eq!(Some(&instance.get()), sent.last());
+    assert!(sent.windows(2).all(|values| values[0] != values[1]));
+}
+
+#[test]
+fn reactive_property_instance_observer_panic_test() {
+    let instance = Arc::new(ReactivePropertyInstance::new(Uuid::new_v4(), r_string(), Mutable, json!(0)));
+    let subscription = instance.subscribe_sync(|value| {
+        if value == &json!(1) {
+            panic!("observer panicked");
+        }
+    });
+    let inner_instance = instance.clone();
+    assert!(thread::spawn(move || inner_instance.set(json!(1))).join().is_err());
+
+    // The property can be set after an observer has panicked
+    instance.set(json!(2));
+    assert_eq!(json!(2), instance.get());
+    drop(subscription);
+}
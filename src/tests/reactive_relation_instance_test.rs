@@ -72,6 +72,7 @@ fn reactive_relation_instance_test() {
         outbound: outbound_entity.clone(),
         ty: ty.clone(),
         inbound: inbound_entity.clone(),
+        version: None,
         description: relation_description.clone(),
         properties,
         components,
@@ -153,6 +154,7 @@ fn reactive_relation_instance_from_edge_properties_test() {
     let outbound_entity = Arc::new(ReactiveEntityInstance {
         ty: outbound_ty.clone(),
         id: outbound_id.clone(),
+        version: None,
         description: outbound_description.clone(),
         properties: DashMap::new(),
         components: DashSet::new(),
@@ -166,6 +168,7 @@ fn reactive_relation_instance_from_edge_properties_test() {
     let inbound_entity = Arc::new(ReactiveEntityInstance {
         ty: inbound_ty.clone(),
         id: inbound_id.clone(),
+        version: None,
         description: inbound_description.clone(),
         properties: DashMap::new(),
         components: DashSet::new(),
@@ -179,6 +182,7 @@ fn reactive_relation_instance_from_edge_properties_test() {
         outbound: outbound_entity.clone(), // Arc::clone -> Reference Counted
         ty: relation_ty.clone(),
         inbound: inbound_entity.clone(), // Arc::clone -> Reference Counted
+        version: None,
         description: relation_description.clone(),
         properties: DashMap::new(),
         components: DashSet::new(),
@@ -367,3 +371,23 @@ fn reactive_relation_instance_stream_test() {
     // outer value must not be changed after set() because there is no observer anymore
     assert_eq!(modified_value, outer_value.read().unwrap().as_str().unwrap());
 }
+
+#[test]
+fn reactive_relation_instance_subscribe_test() {
+    let property_name = r_string();
+    let outbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let inbound_entity = Arc::new(create_random_entity_instance(r_string()));
+    let reactive_relation_instance = create_random_relation_instance_with_properties(outbound_entity, inbound_entity, property_name.clone());
+    let value = Arc::new(RwLock::new(json!(null)));
+
+    let inner_value = value.clone();
+    let subscription = reactive_relation_instance
+        .subscribe(&property_name, move |v| *inner_value.write().unwrap() = v.clone())
+        .unwrap();
+    reactive_relation_instance.set(&property_name, json!(1));
+    assert_eq!(json!(1), *value.read().unwrap());
+    drop(subscription);
+    reactive_relation_instance.set(&property_name, json!(2));
+    assert_eq!(json!(1), *value.read().unwrap());
+    assert!(reactive_relation_instance.subscribe(&r_string(), |_| {}).is_none());
+}
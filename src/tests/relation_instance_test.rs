@@ -21,6 +21,11 @@ use crate::RelationInstanceTypeId;
 use crate::RelationTypeId;
 use crate::TypeDefinitionGetter;
 use crate::TypeIdType;
+use crate::DESCRIPTION_PROPERTY;
+use crate::EXTENSIONS_PROPERTY;
+use crate::PROPERTY_NAMES_PROPERTY;
+use crate::RESERVED_PROPERTY_PREFIX;
+use crate::VERSION_PROPERTY;
 
 #[test]
 fn relation_instance_test() {
@@ -54,6 +59,7 @@ fn relation_instance_test() {
         outbound_id,
         ty: ty.clone(),
         inbound_id,
+        version: None,
         description: description.to_string(),
         properties: properties.clone(),
         extensions: extensions.clone(),
@@ -95,6 +101,7 @@ fn edge_key_from_type_unique_id_test() {
         outbound_id,
         ty: ty.clone(),
         inbound_id,
+        version: None,
         description: r_string(),
         properties: HashMap::new(),
         extensions: Vec::new(),
@@ -126,6 +133,7 @@ fn edge_key_from_type_unique_for_instance_id_test() {
         outbound_id,
         ty: ty.clone(),
         inbound_id,
+        version: None,
         description: r_string(),
         properties: HashMap::new(),
         extensions: Vec::new(),
@@ -154,6 +162,7 @@ fn edge_key_from_type_with_random_instance_id_test() {
         outbound_id,
         ty: ty.clone(),
         inbound_id,
+        version: None,
         description: r_string(),
         properties: HashMap::new(),
         extensions: Vec::new(),
@@ -177,6 +186,7 @@ fn edge_key_with_long_namespace_test() {
         outbound_id,
         ty: ty.clone(),
         inbound_id,
+        version: None,
         description: r_string(),
         properties: HashMap::new(),
         extensions: Vec::new(),
@@ -199,6 +209,7 @@ fn edge_key_with_long_type_name_test() {
         outbound_id,
         ty: ty.clone(),
         inbound_id,
+        version: None,
         description: r_string(),
         properties: HashMap::new(),
         extensions: Vec::new(),
@@ -221,6 +232,7 @@ fn edge_key_with_long_namespace_and_type_name_test() {
         outbound_id,
         ty: ty.clone(),
         inbound_id,
+        version: None,
         description: r_string(),
         properties: HashMap::new(),
         extensions: Vec::new(),
@@ -337,6 +349,7 @@ fn relation_instance_get_key_test() {
         outbound_id,
         ty: ty.clone(),
         inbound_id,
+        version: None,
         description: description.to_string(),
         properties: properties.clone(),
         extensions: Vec::new(),
@@ -396,3 +409,53 @@ fn relation_instance_de_test() {
     assert_eq!("ext_name", extension.ty.type_name());
     assert_eq!(json!("ext_value"), extension.extension);
 }
+
+#[test]
+fn relation_instance_to_edge_properties_round_trip_test() {
+    let ty = RelationInstanceTypeId::new_unique_for_instance_id(RelationTypeId::new_from_type(r_string(), r_string()), r_string());
+    let mut properties = HashMap::new();
+    properties.insert(r_string(), json!(r_string()));
+    properties.insert(r_string(), json!(42));
+    let relation_instance = RelationInstance::new(Uuid::new_v4(), ty, Uuid::new_v4(), properties);
+
+    let edge_key = EdgeKey::from(&relation_instance);
+    assert_eq!(relation_instance.get_key(), edge_key);
+
+    let edge_properties = EdgeProperties::from(&relation_instance);
+    assert_eq!(edge_key, edge_properties.edge.key);
+    assert_eq!(2, edge_properties.props.len());
+
+    let relation_instance_2 = RelationInstance::try_from(edge_properties).unwrap();
+    assert_eq!(relation_instance.outbound_id, relation_instance_2.outbound_id);
+    assert_eq!(relation_instance.ty, relation_instance_2.ty);
+    assert_eq!(relation_instance.inbound_id, relation_instance_2.inbound_id);
+    assert_eq!(relation_instance.properties, relation_instance_2.properties);
+}
+
+#[test]
+fn relation_instance_to_edge_properties_reserved_properties_round_trip_test() {
+    let ty = RelationInstanceTypeId::new_unique_id(RelationTypeId::new_from_type(r_string(), r_string()));
+    let long_property_name = r_string_1000();
+    let reserved_property_name = format!("{}{}", RESERVED_PROPERTY_PREFIX, "description");
+    let mut properties = HashMap::new();
+    properties.insert(long_property_name.clone(), json!(r_string()));
+    properties.insert(reserved_property_name.clone(), json!(r_string()));
+    let mut relation_instance = RelationInstance::new(Uuid::new_v4(), ty, Uuid::new_v4(), properties);
+    relation_instance.description = r_string();
+    relation_instance.version = Some(2);
+    relation_instance
+        .extensions
+        .push(Extension::new_from_type(r_string(), r_string(), r_string(), json!([1, 2, 3])));
+
+    let edge_properties = EdgeProperties::from(&relation_instance);
+    assert!(edge_properties.props.iter().any(|p| p.name.as_str() == DESCRIPTION_PROPERTY));
+    assert!(edge_properties.props.iter().any(|p| p.name.as_str() == EXTENSIONS_PROPERTY));
+    assert!(edge_properties.props.iter().any(|p| p.name.as_str() == PROPERTY_NAMES_PROPERTY));
+    assert!(edge_properties.props.iter().any(|p| p.name.as_str() == VERSION_PROPERTY));
+
+    let relation_instance_2 = RelationInstance::try_from(edge_properties).unwrap();
+    assert_eq!(relation_instance.description, relation_instance_2.description);
+    assert_eq!(Some(2), relation_instance_2.version);
+    assert_eq!(relation_instance.extensions, relation_instance_2.extensions);
+    assert_eq!(relation_instance.properties, relation_instance_2.properties);
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::env;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::load_from_file;
+use crate::save_to_file;
+use crate::DataType;
+use crate::EntityInstance;
+use crate::EntityType;
+use crate::EntityTypeId;
+use crate::Extension;
+use crate::ExtensionTypeId;
+use crate::PropertyType;
+use crate::SerializationError;
+use crate::SerializationFormat;
+
+/// Returns all enabled formats.
+fn formats() -> Vec<SerializationFormat> {
+    vec![
+        SerializationFormat::Json,
+        #[cfg(feature = "yaml")]
+        SerializationFormat::Yaml,
+        #[cfg(feature = "toml")]
+        SerializationFormat::Toml,
+        #[cfg(feature = "ron")]
+        SerializationFormat::Ron,
+        #[cfg(feature = "msgpack")]
+        SerializationFormat::MessagePack,
+        #[cfg(feature = "cbor")]
+        SerializationFormat::Cbor,
+    ]
+}
+
+/// Returns an entity instance with property values of all kinds of JSON values except null.
+fn entity_instance() -> EntityInstance {
+    let mut properties = HashMap::new();
+    properties.insert("bool".to_string(), json!(true));
+    properties.insert("integer".to_string(), json!(-42));
+    properties.insert("unsigned".to_string(), json!(i64::MAX as u64));
+    properties.insert("float".to_string(), json!(1.0));
+    properties.insert("fraction".to_string(), json!(-0.125));
+    properties.insert("string".to_string(), json!("true"));
+    properties.insert("empty".to_string(), json!(""));
+    properties.insert("array".to_string(), json!([1, "two", [3.0], { "four": 4 }]));
+    properties.insert("object".to_string(), json!({ "nested": { "1": [] }, "key with spaces": {} }));
+    EntityInstance {
+        ty: EntityTypeId::new_from_type("logical", "and"),
+        id: Uuid::new_v4(),
+        version: Some(1),
+        description: "Logical and".to_string(),
+        properties,
+        extensions: vec![Extension {
+            ty: ExtensionTypeId::new_from_type("flow_editor", "position"),
+            description: String::new(),
+            extension: json!({ "x": 1, "y": 2.5 }),
+        }],
+    }
+}
+
+#[test]
+fn serialization_format_round_trip_test() {
+    let entity_instance = entity_instance();
+    for format in formats() {
+        let bytes = format.to_vec(&entity_instance).unwrap();
+        let loaded: EntityInstance = format.from_slice(&bytes).unwrap();
+        assert_eq!(entity_instance, loaded, "{}", format);
+        // Floats without fractional part stay floats
+        assert!(loaded.properties["float"].is_f64(), "{}", format);
+        assert!(loaded.properties["unsigned"].is_u64(), "{}", format);
+        assert!(loaded.properties["integer"].is_i64(), "{}", format);
+
+        let entity_type = EntityType::new_from_type(
+            "logical",
+            "and",
+            "Logical and",
+            Vec::new(),
+            vec![PropertyType::new("kind", DataType::Enum(vec!["a".to_string(), "b".to_string()]))],
+            Vec::new(),
+        );
+        let bytes = format.to_vec(&entity_type).unwrap();
+        let loaded: EntityType = format.from_slice(&bytes).unwrap();
+        assert_eq!(entity_type.ty, loaded.ty, "{}", format);
+        assert_eq!(entity_type.properties[0].data_type, loaded.properties[0].data_type, "{}", format);
+    }
+}
+
+#[test]
+fn serialization_format_null_test() {
+    let value = json!({ "null": null, "array": [null], "unsigned": u64::MAX });
+    for format in formats() {
+        #[cfg(feature = "toml")]
+        if format == SerializationFormat::Toml {
+            continue;
+        }
+        let bytes = format.value_to_vec(&value).unwrap();
+        assert_eq!(value, format.value_from_slice(&bytes).unwrap(), "{}", format);
+    }
+}
+
+#[test]
+fn serialization_format_from_extension_test() {
+    assert_eq!(Some(SerializationFormat::Json), SerializationFormat::from_extension("json"));
+    assert_eq!(Some(SerializationFormat::Json), SerializationFormat::from_path("types/entity_types/and.JSON"));
+    assert_eq!(None, SerializationFormat::from_extension("txt"));
+    assert_eq!(None, SerializationFormat::from_path("types/entity_types/and"));
+    for format in formats() {
+        assert_eq!(Some(format), SerializationFormat::from_extension(format.extension()));
+    }
+}
+
+#[test]
+fn load_from_file_and_save_to_file_test() {
+    let entity_instance = entity_instance();
+    for format in formats() {
+        let path = env::temp_dir().join(format!("{}.{}", Uuid::new_v4(), format.extension()));
+        save_to_file(&path, &entity_instance).unwrap();
+        let loaded: EntityInstance = load_from_file(&path).unwrap();
+        assert_eq!(entity_instance, loaded, "{}", format);
+        std::fs::remove_file(&path).unwrap();
+    }
+    let path = env::temp_dir().join(format!("{}.txt", Uuid::new_v4()));
+    assert!(matches!(save_to_file(&path, &entity_instance), Err(SerializationError::UnknownFormat(_))));
+    let path = env::temp_dir().join(format!("{}.json", Uuid::new_v4()));
+    assert!(matches!(load_from_file::<EntityInstance, _>(&path), Err(SerializationError::Io { .. })));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml_test() {
+    let format = SerializationFormat::Yaml;
+    let entity_type: EntityType = format
+        .from_slice(
+            b"namespace: logical
+type_name: and
+components:
+  - namespace: core
+    type_name: labeled
+properties:
+  - name: lhs
+    data_type: bool
+    socket_type: input
+  - name: kind
+    data_type:
+      enum: [a, b]
+",
+        )
+        .unwrap();
+    assert_eq!(EntityTypeId::new_from_type("logical", "and"), entity_type.ty);
+    assert_eq!(DataType::Enum(vec!["a".to_string(), "b".to_string()]), entity_type.properties[1].data_type);
+
+    // Strings which look like other scalars are quoted
+    let value = json!({ "bool": "true", "number": "1", "null": "~" });
+    let bytes = format.value_to_vec(&value).unwrap();
+    assert_eq!(value, format.value_from_slice(&bytes).unwrap());
+
+    // Plain scalar keys are read as their text
+    assert_eq!(json!({ "1": "one", "true": "yes" }), format.value_from_slice(b"1: one\ntrue: yes\n").unwrap());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_test() {
+    let format = SerializationFormat::Toml;
+    match format.value_to_vec(&json!({ "properties": { "value": null } })) {
+        Err(SerializationError::Unsupported { pointer, .. }) => assert_eq!("/properties/value", pointer),
+        _ => panic!("Expected null to be unsupported"),
+    }
+    match format.value_to_vec(&json!({ "values": [1, u64::MAX] })) {
+        Err(SerializationError::Unsupported { pointer, .. }) => assert_eq!("/values/1", pointer),
+        _ => panic!("Expected u64 to be unsupported"),
+    }
+    assert!(matches!(format.value_to_vec(&json!([1])), Err(SerializationError::Unsupported { .. })));
+    assert_eq!(
+        json!({ "created": "1979-05-27T07:32:00Z", "a": { "b": 1.5 } }),
+        format.value_from_slice(b"created = 1979-05-27T07:32:00Z\n[a]\nb = 1.5\n").unwrap()
+    );
+    // Local date-times, dates and times have no offset
+    assert_eq!(
+        json!({ "local": "1979-05-27T07:32:00", "date": "1979-05-27", "time": "07:32:00" }),
+        format
+            .value_from_slice(b"local = 1979-05-27T07:32:00\ndate = 1979-05-27\ntime = 07:32:00\n")
+            .unwrap()
+    );
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn ron_test() {
+    let format = SerializationFormat::Ron;
+    assert_eq!(json!({ "a": null, "b": 1 }), format.value_from_slice(b"{\"a\": (), \"b\": Some(1)}").unwrap());
+    assert!(format.value_from_slice(b"{1: \"one\"}").is_err());
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn message_pack_test() {
+    let format = SerializationFormat::MessagePack;
+    // { 1: "one" }
+    assert!(matches!(
+        format.value_from_slice(&[0x81, 0x01, 0xa3, b'o', b'n', b'e']),
+        Err(SerializationError::Deserialize { .. })
+    ));
+    // Binary data
+    assert!(format.value_from_slice(&[0xc4, 0x01, 0x00]).is_err());
+    // Compact integers
+    assert_eq!(vec![0x2a], format.value_to_vec(&json!(42)).unwrap());
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn cbor_test() {
+    let format = SerializationFormat::Cbor;
+    // { 1: "one" }
+    assert!(matches!(
+        format.value_from_slice(&[0xa1, 0x01, 0x63, b'o', b'n', b'e']),
+        Err(SerializationError::Deserialize { .. })
+    ));
+    // Binary data
+    assert!(format.value_from_slice(&[0x41, 0x00]).is_err());
+    let value = json!({ "float": 1.0, "integer": 1 });
+    let bytes = format.value_to_vec(&value).unwrap();
+    assert_eq!(value, format.value_from_slice(&bytes).unwrap());
+}
@@ -0,0 +1,656 @@
+use std::collections::HashMap;
+
+use indradb::EdgeProperties;
+use indradb::VertexProperties;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::convert_value;
+use crate::tests::utils::r_string;
+use crate::Component;
+use crate::ComponentTypeId;
+use crate::DataType;
+use crate::EntityInstance;
+use crate::EntityType;
+use crate::EntityTypeId;
+use crate::FlowInstance;
+use crate::MigrationError;
+use crate::MigrationStep;
+use crate::NamespacedTypeGetter;
+use crate::PropertyType;
+use crate::RelationInstance;
+use crate::RelationType;
+use crate::RelationTypeId;
+use crate::TypeDefinitionGetter;
+use crate::TypeMigration;
+use crate::TypeRegistry;
+use crate::ValueConverter;
+use crate::ValueMapping;
+use crate::VERSION_PROPERTY;
+
+/// Returns an entity type of version 3 which has been migrated from the properties `value` (a
+/// string) and `label` of the removed component to the properties `x` (an integer) and `result`.
+fn create_entity_type(namespace: &str, type_name: &str, component_ty: &ComponentTypeId) -> EntityType {
+    let properties = vec![PropertyType::new("x", DataType::Integer), PropertyType::output("result", DataType::Bool)];
+    let mut entity_type = EntityType::new(EntityTypeId::new_from_type(namespace, type_name), "", Vec::new(), properties, Vec::new());
+    entity_type.version = 3;
+    entity_type.migrations = vec![
+        TypeMigration::new(
+            3,
+            "Removes the label",
+            vec![MigrationStep::RemoveComponent {
+                component: component_ty.clone(),
+            }],
+        ),
+        TypeMigration::new(
+            2,
+            "Renames value to x and adds the result",
+            vec![
+                MigrationStep::RenameProperty {
+                    from: "value".to_string(),
+                    to: "x".to_string(),
+                },
+                MigrationStep::ChangeDataType {
+                    name: "x".to_string(),
+                    data_type: DataType::Integer,
+                    converter: ValueConverter::Convert,
+                },
+                MigrationStep::AddProperty {
+                    name: "result".to_string(),
+                    value: None,
+                },
+            ],
+        ),
+    ];
+    entity_type
+}
+
+fn create_component(component_ty: &ComponentTypeId) -> Component {
+    Component::new_without_extensions(component_ty.clone(), "", vec![PropertyType::string("label")])
+}
+
+#[test]
+fn entity_instance_migration_test() {
+    let namespace = r_string();
+    let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let components = vec![create_component(&component_ty)];
+    let entity_type = create_entity_type(&namespace, &r_string(), &component_ty);
+
+    let mut properties = HashMap::new();
+    properties.insert("value".to_string(), json!(" 42 "));
+    properties.insert("label".to_string(), json!(r_string()));
+    let mut entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+    entity_instance.version = Some(0);
+    assert!(entity_instance.migrate(&entity_type, &components).is_ok());
+    assert_eq!(Some(3), entity_instance.version);
+    assert_eq!(2, entity_instance.properties.len());
+    assert_eq!(json!(42), entity_instance.properties["x"]);
+    assert_eq!(json!(false), entity_instance.properties["result"]);
+    assert!(entity_instance.validate(&entity_type, &components).is_ok());
+
+    // Migrating an instance of the current version doesn't change it
+    let migrated = entity_instance.clone();
+    assert!(entity_instance.migrate(&entity_type, &components).is_ok());
+    assert_eq!(migrated, entity_instance);
+
+    // Only the migrations after the version of the instance are applied
+    let mut properties = HashMap::new();
+    properties.insert("x".to_string(), json!(1));
+    properties.insert("result".to_string(), json!(true));
+    properties.insert("label".to_string(), json!(r_string()));
+    let mut entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+    entity_instance.version = Some(2);
+    assert!(entity_instance.migrate(&entity_type, &components).is_ok());
+    assert_eq!(json!(1), entity_instance.properties["x"]);
+    assert_eq!(json!(true), entity_instance.properties["result"]);
+    assert!(!entity_instance.properties.contains_key("label"));
+}
+
+#[test]
+fn entity_instance_migration_failure_test() {
+    let namespace = r_string();
+    let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let components = vec![create_component(&component_ty)];
+    let entity_type = create_entity_type(&namespace, &r_string(), &component_ty);
+
+    // The instance is not changed if a migration fails
+    let mut properties = HashMap::new();
+    properties.insert("value".to_string(), json!("forty two"));
+    properties.insert("label".to_string(), json!(r_string()));
+    let mut entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+    entity_instance.version = Some(0);
+    let unmigrated = entity_instance.clone();
+    assert_eq!(
+        Err(MigrationError::ConversionFailed {
+            version: 2,
+            property_name: "x".to_string(),
+            value: json!("forty two"),
+            data_type: DataType::Integer,
+        }),
+        entity_instance.migrate(&entity_type, &components)
+    );
+    assert_eq!(unmigrated, entity_instance);
+
+    // The component of a migration is missing
+    entity_instance.properties.insert("value".to_string(), json!(42));
+    assert_eq!(
+        Err(MigrationError::MissingComponent {
+            version: 3,
+            component: component_ty,
+        }),
+        entity_instance.migrate(&entity_type, &Vec::<Component>::new())
+    );
+
+    // The instance has been saved against a newer version of the type
+    entity_instance.version = Some(4);
+    assert_eq!(
+        Err(MigrationError::NewerVersion {
+            instance_version: 4,
+            type_version: 3
+        }),
+        entity_instance.migrate(&entity_type, &components)
+    );
+
+    // The instance is of another type
+    let mut entity_instance = EntityInstance::new(EntityTypeId::new_from_type(r_string(), r_string()), Uuid::new_v4(), HashMap::new());
+    assert_eq!(
+        Err(MigrationError::TypeMismatch {
+            expected: entity_type.type_definition(),
+            actual: entity_instance.type_definition(),
+        }),
+        entity_instance.migrate(&entity_type, &components)
+    );
+}
+
+#[test]
+fn entity_instance_round_trip_migration_test() {
+    let namespace = r_string();
+    let mut entity_type = EntityType::new(
+        EntityTypeId::new_from_type(&namespace, &r_string()),
+        "",
+        Vec::new(),
+        vec![PropertyType::string("a"), PropertyType::string("b"), PropertyType::new("c", DataType::Integer)],
+        Vec::new(),
+    );
+    entity_type.version = 2;
+    entity_type.migrations = vec![
+        TypeMigration::new(
+            1,
+            "Renames a to b and drops c",
+            vec![
+                MigrationStep::RenameProperty {
+                    from: "a".to_string(),
+                    to: "b".to_string(),
+                },
+                MigrationStep::DropProperty { name: "c".to_string() },
+            ],
+        ),
+        TypeMigration::new(
+            2,
+            "Adds a and c",
+            vec![
+                MigrationStep::AddProperty {
+                    name: "a".to_string(),
+                    value: None,
+                },
+                MigrationStep::AddProperty {
+                    name: "c".to_string(),
+                    value: None,
+                },
+            ],
+        ),
+    ];
+    let components = Vec::<Component>::new();
+
+    let mut properties = HashMap::new();
+    properties.insert("a".to_string(), json!("a"));
+    properties.insert("b".to_string(), json!("b"));
+    properties.insert("c".to_string(), json!(5));
+    let mut entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+    entity_instance.version = Some(2);
+
+    // Reloading an instance of the current version doesn't migrate it again
+    let mut entity_instance_2 = EntityInstance::try_from(VertexProperties::from(&entity_instance)).unwrap();
+    assert_eq!(Some(2), entity_instance_2.version);
+    assert!(entity_instance_2.migrate(&entity_type, &components).is_ok());
+    assert_eq!(entity_instance.properties, entity_instance_2.properties);
+
+    // Reloading the instance as if it were of version 0 would rename a to the existing b
+    entity_instance_2.version = Some(0);
+    assert_eq!(
+        Err(MigrationError::PropertyExists {
+            version: 1,
+            property_name: "b".to_string()
+        }),
+        entity_instance_2.migrate(&entity_type, &components)
+    );
+
+    // Dropping and re-adding c would reset its value
+    entity_instance_2.properties.remove("b");
+    assert!(entity_instance_2.migrate(&entity_type, &components).is_ok());
+    assert_eq!(json!(0), entity_instance_2.properties["c"]);
+
+    // Instances which have been saved before the type had a version are migrated from version 0
+    let mut properties = HashMap::new();
+    properties.insert("a".to_string(), json!("x"));
+    properties.insert("c".to_string(), json!(5));
+    let legacy_entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+    let vertex_properties = VertexProperties::from(&legacy_entity_instance);
+    assert!(vertex_properties.props.iter().all(|property| property.name.as_str() != VERSION_PROPERTY));
+    let mut entity_instance_2 = EntityInstance::try_from(vertex_properties).unwrap();
+    assert_eq!(None, entity_instance_2.version);
+    assert!(entity_instance_2.migrate(&entity_type, &components).is_ok());
+    assert_eq!(Some(2), entity_instance_2.version);
+    assert_eq!(json!("x"), entity_instance_2.properties["b"]);
+    assert_eq!(json!(""), entity_instance_2.properties["a"]);
+    assert_eq!(json!(0), entity_instance_2.properties["c"]);
+
+    // Instances without version are only stamped if the migration succeeds
+    let mut entity_instance_2 = EntityInstance::try_from(VertexProperties::from(&entity_instance)).unwrap();
+    entity_instance_2.version = None;
+    assert!(entity_instance_2.migrate(&entity_type, &components).is_err());
+    assert_eq!(None, entity_instance_2.version);
+}
+
+#[test]
+fn relation_instance_round_trip_migration_test() {
+    let namespace = r_string();
+    let entity_ty = EntityTypeId::new_from_type(&namespace, &r_string());
+    let relation_ty = RelationTypeId::new_from_type(&namespace, &r_string());
+    let mut relation_type = RelationType::new(
+        entity_ty.clone(),
+        relation_ty.clone(),
+        entity_ty,
+        "",
+        Vec::new(),
+        vec![PropertyType::new("weight", DataType::Float)],
+        Vec::new(),
+    );
+    relation_type.version = 2;
+    relation_type.migrations = vec![
+        TypeMigration::new(1, "", vec![MigrationStep::DropProperty { name: "weight".to_string() }]),
+        TypeMigration::new(
+            2,
+            "",
+            vec![MigrationStep::AddProperty {
+                name: "weight".to_string(),
+                value: None,
+            }],
+        ),
+    ];
+    let mut properties = HashMap::new();
+    properties.insert("weight".to_string(), json!(0.5));
+    let mut relation_instance =
+        RelationInstance::new_from_type_unique_id(namespace.clone(), Uuid::new_v4(), relation_ty.type_name(), Uuid::new_v4(), properties);
+    relation_instance.version = Some(2);
+
+    let mut relation_instance_2 = RelationInstance::try_from(EdgeProperties::from(&relation_instance)).unwrap();
+    assert_eq!(Some(2), relation_instance_2.version);
+    assert!(relation_instance_2.migrate(&relation_type, &Vec::<Component>::new()).is_ok());
+    assert_eq!(json!(0.5), relation_instance_2.properties["weight"]);
+}
+
+#[test]
+fn component_migration_test() {
+    let namespace = r_string();
+    let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let mut component = Component::new_without_extensions(component_ty.clone(), "", vec![PropertyType::new("name", DataType::Integer)]);
+    component.version = 2;
+    component.migrations = vec![
+        TypeMigration::new(
+            1,
+            "Renames label to name",
+            vec![MigrationStep::RenameProperty {
+                from: "label".to_string(),
+                to: "name".to_string(),
+            }],
+        ),
+        TypeMigration::new(
+            2,
+            "Converts name into an integer",
+            vec![MigrationStep::ChangeDataType {
+                name: "name".to_string(),
+                data_type: DataType::Integer,
+                converter: ValueConverter::Convert,
+            }],
+        ),
+    ];
+    let components = vec![component];
+    let mut entity_type = EntityType::new(EntityTypeId::new_from_type(&namespace, &r_string()), "", vec![component_ty.clone()], Vec::new(), Vec::new());
+    entity_type.version = 1;
+    entity_type.migrations = vec![TypeMigration::new(
+        1,
+        "Migrates the component",
+        vec![MigrationStep::MigrateComponent {
+            component: component_ty.clone(),
+            from_version: 0,
+        }],
+    )];
+    let migrate = |entity_type: &EntityType, components: &Vec<Component>, properties: serde_json::Value| {
+        let properties: HashMap<String, serde_json::Value> = serde_json::from_value(properties).unwrap();
+        let mut entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+        entity_instance.version = Some(0);
+        entity_instance
+            .migrate(entity_type, components)
+            .map(|_| serde_json::to_value(entity_instance.properties).unwrap())
+    };
+
+    // All migrations of the component after the given version are applied
+    assert_eq!(Ok(json!({ "name": 42 })), migrate(&entity_type, &components, json!({ "label": "42" })));
+
+    // Only the migrations after the given version of the component are applied
+    if let MigrationStep::MigrateComponent { from_version, .. } = &mut entity_type.migrations[0].steps[0] {
+        *from_version = 1;
+    }
+    assert_eq!(Ok(json!({ "label": "42" })), migrate(&entity_type, &components, json!({ "label": "42" })));
+    assert_eq!(Ok(json!({ "name": 42 })), migrate(&entity_type, &components, json!({ "name": "42" })));
+
+    // Failing migrations of the component are reported with the component
+    assert_eq!(
+        Err(MigrationError::Component {
+            version: 1,
+            component: component_ty.clone(),
+            error: Box::new(MigrationError::ConversionFailed {
+                version: 2,
+                property_name: "name".to_string(),
+                value: json!("forty two"),
+                data_type: DataType::Integer,
+            }),
+        }),
+        migrate(&entity_type, &components, json!({ "name": "forty two" }))
+    );
+
+    // The instances have been saved against a newer version of the component
+    if let MigrationStep::MigrateComponent { from_version, .. } = &mut entity_type.migrations[0].steps[0] {
+        *from_version = 3;
+    }
+    assert_eq!(
+        Err(MigrationError::Component {
+            version: 1,
+            component: component_ty.clone(),
+            error: Box::new(MigrationError::NewerVersion {
+                instance_version: 3,
+                type_version: 2
+            }),
+        }),
+        migrate(&entity_type, &components, json!({ "name": 1 }))
+    );
+
+    // The component is missing
+    assert_eq!(
+        Err(MigrationError::MissingComponent {
+            version: 1,
+            component: component_ty,
+        }),
+        migrate(&entity_type, &Vec::new(), json!({ "name": 1 }))
+    );
+}
+
+#[test]
+fn migration_step_test() {
+    let namespace = r_string();
+    let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let components = vec![create_component(&component_ty)];
+    let mut entity_type = EntityType::new(
+        EntityTypeId::new_from_type(&namespace, &r_string()),
+        "",
+        Vec::new(),
+        vec![PropertyType::string("name"), PropertyType::string("label")],
+        Vec::new(),
+    );
+    entity_type.version = 1;
+    let migrate = |entity_type: &EntityType, properties: serde_json::Value| {
+        let properties: HashMap<String, serde_json::Value> = serde_json::from_value(properties).unwrap();
+        let mut entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+        entity_instance.version = Some(0);
+        entity_instance
+            .migrate(entity_type, &components)
+            .map(|_| serde_json::to_value(entity_instance.properties).unwrap())
+    };
+
+    // Renaming a property to an existing property
+    entity_type.migrations = vec![TypeMigration::new(
+        1,
+        "",
+        vec![MigrationStep::RenameProperty {
+            from: "a".to_string(),
+            to: "name".to_string(),
+        }],
+    )];
+    assert_eq!(Ok(json!({ "name": "b" })), migrate(&entity_type, json!({ "name": "b" })));
+    assert_eq!(
+        Err(MigrationError::PropertyExists {
+            version: 1,
+            property_name: "name".to_string()
+        }),
+        migrate(&entity_type, json!({ "a": "a", "name": "b" }))
+    );
+
+    // Adding properties with and without value
+    entity_type.migrations = vec![TypeMigration::new(
+        1,
+        "",
+        vec![
+            MigrationStep::AddProperty {
+                name: "name".to_string(),
+                value: Some(json!("unnamed")),
+            },
+            MigrationStep::AddProperty {
+                name: "undeclared".to_string(),
+                value: None,
+            },
+        ],
+    )];
+    assert_eq!(
+        Err(MigrationError::UndeclaredProperty {
+            version: 1,
+            property_name: "undeclared".to_string()
+        }),
+        migrate(&entity_type, json!({}))
+    );
+    entity_type.migrations[0].steps.pop();
+    assert_eq!(Ok(json!({ "name": "unnamed" })), migrate(&entity_type, json!({})));
+    assert_eq!(Ok(json!({ "name": "b" })), migrate(&entity_type, json!({ "name": "b" })));
+
+    // Dropping a property
+    entity_type.migrations = vec![TypeMigration::new(1, "", vec![MigrationStep::DropProperty { name: "a".to_string() }])];
+    assert_eq!(Ok(json!({ "name": "b" })), migrate(&entity_type, json!({ "a": 1, "name": "b" })));
+
+    // Adding a component keeps existing values
+    entity_type.migrations = vec![TypeMigration::new(
+        1,
+        "",
+        vec![MigrationStep::AddComponent {
+            component: component_ty.clone(),
+        }],
+    )];
+    assert_eq!(Ok(json!({ "label": "" })), migrate(&entity_type, json!({})));
+    assert_eq!(Ok(json!({ "label": "a" })), migrate(&entity_type, json!({ "label": "a" })));
+
+    // Removing a component keeps the properties which are still declared by the type
+    entity_type.migrations = vec![TypeMigration::new(1, "", vec![MigrationStep::RemoveComponent { component: component_ty }])];
+    assert_eq!(Ok(json!({ "label": "a" })), migrate(&entity_type, json!({ "label": "a" })));
+}
+
+#[test]
+fn value_converter_test() {
+    let data_type = DataType::Enum(vec!["on".to_string(), "off".to_string()]);
+    let converter = ValueConverter::Map(vec![ValueMapping::new(json!(true), json!("on")), ValueMapping::new(json!(false), json!("off"))]);
+    assert_eq!(Some(json!("on")), converter.convert(&json!(true), &data_type));
+    assert_eq!(Some(json!("off")), converter.convert(&json!("off"), &data_type));
+    assert_eq!(None, converter.convert(&json!("dimmed"), &data_type));
+    // Mappings to invalid values fail
+    let converter = ValueConverter::Map(vec![ValueMapping::new(json!(true), json!("yes"))]);
+    assert_eq!(None, converter.convert(&json!(true), &data_type));
+
+    assert_eq!(Some(json!("on")), ValueConverter::ConvertOrDefault.convert(&json!("dimmed"), &data_type));
+    assert_eq!(Some(json!("on")), ValueConverter::Default.convert(&json!("off"), &data_type));
+    assert_eq!(None, ValueConverter::Convert.convert(&json!("dimmed"), &data_type));
+}
+
+#[test]
+fn convert_value_test() {
+    assert_eq!(Some(json!(true)), convert_value(&json!(1), &DataType::Bool));
+    assert_eq!(Some(json!(false)), convert_value(&json!(" false"), &DataType::Bool));
+    assert_eq!(None, convert_value(&json!("no"), &DataType::Bool));
+    assert_eq!(Some(json!(1)), convert_value(&json!(true), &DataType::Integer));
+    assert_eq!(Some(json!(1.0)), convert_value(&json!(true), &DataType::Float));
+    assert_eq!(Some(json!(2)), convert_value(&json!(2.0), &DataType::Integer));
+    assert_eq!(None, convert_value(&json!(2.5), &DataType::Integer));
+    assert_eq!(Some(json!(u64::MAX)), convert_value(&json!(u64::MAX.to_string()), &DataType::Integer));
+    assert_eq!(Some(json!(2.5)), convert_value(&json!("2.5"), &DataType::Number));
+    assert_eq!(Some(json!(-3)), convert_value(&json!("-3"), &DataType::Number));
+    assert_eq!(Some(json!(1.5)), convert_value(&json!(1.5), &DataType::Float));
+    assert_eq!(Some(json!("1.5")), convert_value(&json!(1.5), &DataType::String));
+    assert_eq!(Some(json!("[1]")), convert_value(&json!([1]), &DataType::String));
+    assert_eq!(None, convert_value(&json!(null), &DataType::String));
+    assert_eq!(Some(json!("1")), convert_value(&json!(1), &DataType::Enum(vec!["1".to_string()])));
+    assert_eq!(Some(json!([null])), convert_value(&json!(null), &DataType::Array));
+    assert_eq!(None, convert_value(&json!(1), &DataType::Object));
+}
+
+#[test]
+fn relation_instance_migration_test() {
+    let namespace = r_string();
+    let entity_ty = EntityTypeId::new_from_type(&namespace, &r_string());
+    let relation_ty = RelationTypeId::new_from_type(&namespace, &r_string());
+    let mut relation_type = RelationType::new(
+        entity_ty.clone(),
+        relation_ty.clone(),
+        entity_ty.clone(),
+        "",
+        Vec::new(),
+        vec![PropertyType::new("weight", DataType::Float)],
+        Vec::new(),
+    );
+    relation_type.version = 1;
+    relation_type.migrations = vec![TypeMigration::new(
+        1,
+        "",
+        vec![MigrationStep::ChangeDataType {
+            name: "weight".to_string(),
+            data_type: DataType::Float,
+            converter: ValueConverter::ConvertOrDefault,
+        }],
+    )];
+    let mut properties = HashMap::new();
+    properties.insert("weight".to_string(), json!("heavy"));
+    let mut relation_instance =
+        RelationInstance::new_from_type_unique_id(namespace.clone(), Uuid::new_v4(), relation_ty.type_name(), Uuid::new_v4(), properties);
+    relation_instance.version = Some(0);
+    assert!(relation_instance.migrate(&relation_type, &Vec::<Component>::new()).is_ok());
+    assert_eq!(Some(1), relation_instance.version);
+    assert_eq!(json!(0.0), relation_instance.properties["weight"]);
+}
+
+#[test]
+fn flow_instance_migration_test() {
+    let namespace = r_string();
+    let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let entity_type = create_entity_type(&namespace, &r_string(), &component_ty);
+    let type_registry = TypeRegistry::new();
+    type_registry.register_component(create_component(&component_ty)).unwrap();
+    type_registry.register_entity_type(entity_type.clone()).unwrap();
+
+    let mut properties = HashMap::new();
+    properties.insert("value".to_string(), json!("1"));
+    let mut flow_instance = FlowInstance::from(EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties.clone()));
+    let entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+    let id = entity_instance.id;
+    flow_instance.entity_instances.push(entity_instance);
+    flow_instance
+        .entity_instances
+        .iter_mut()
+        .for_each(|entity_instance| entity_instance.version = Some(0));
+    assert!(flow_instance.migrate(&type_registry).is_ok());
+    assert!(flow_instance.entity_instances.iter().all(|entity_instance| entity_instance.version == Some(3)));
+
+    // The flow instance is not changed if an instance cannot be migrated
+    flow_instance.entity_instances[1].version = Some(4);
+    let unmigrated = flow_instance.entity_instances.clone();
+    assert_eq!(
+        Err(MigrationError::Instance {
+            instance: id.to_string(),
+            error: Box::new(MigrationError::NewerVersion {
+                instance_version: 4,
+                type_version: 3
+            }),
+        }),
+        flow_instance.migrate(&type_registry)
+    );
+    assert_eq!(unmigrated, flow_instance.entity_instances);
+
+    // The type of a relation instance is unknown
+    flow_instance.entity_instances[1].version = Some(3);
+    let relation_instance = RelationInstance::new_from_type_unique_id(namespace.clone(), id, r_string(), flow_instance.id, HashMap::new());
+    let instance = relation_instance.to_string();
+    let ty = relation_instance.relation_type_id().type_definition();
+    flow_instance.relation_instances.push(relation_instance);
+    assert_eq!(
+        Err(MigrationError::Instance {
+            instance,
+            error: Box::new(MigrationError::UnknownType(ty)),
+        }),
+        flow_instance.migrate(&type_registry)
+    );
+}
+
+#[test]
+fn type_migration_serde_test() {
+    let migration: TypeMigration = serde_json::from_value(json!({
+        "version": 2,
+        "steps": [
+            { "step": "rename_property", "from": "value", "to": "x" },
+            { "step": "change_data_type", "name": "x", "data_type": "integer" },
+            { "step": "change_data_type", "name": "y", "data_type": "string", "converter": { "map": [{ "from": 1, "to": "one" }] } },
+            { "step": "add_property", "name": "result" },
+            { "step": "drop_property", "name": "z" },
+            { "step": "add_component", "component": { "namespace": "core", "type_name": "labeled" } },
+            { "step": "migrate_component", "component": { "namespace": "core", "type_name": "labeled" }, "from_version": 1 }
+        ]
+    }))
+    .unwrap();
+    assert_eq!(2, migration.version);
+    assert_eq!("", migration.description);
+    assert_eq!(7, migration.steps.len());
+    assert_eq!(
+        MigrationStep::ChangeDataType {
+            name: "x".to_string(),
+            data_type: DataType::Integer,
+            converter: ValueConverter::Convert,
+        },
+        migration.steps[1]
+    );
+    assert_eq!(
+        MigrationStep::ChangeDataType {
+            name: "y".to_string(),
+            data_type: DataType::String,
+            converter: ValueConverter::Map(vec![ValueMapping::new(json!(1), json!("one"))]),
+        },
+        migration.steps[2]
+    );
+    assert_eq!(
+        MigrationStep::AddComponent {
+            component: ComponentTypeId::new_from_type("core", "labeled"),
+        },
+        migration.steps[5]
+    );
+    assert_eq!(
+        MigrationStep::MigrateComponent {
+            component: ComponentTypeId::new_from_type("core", "labeled"),
+            from_version: 1,
+        },
+        migration.steps[6]
+    );
+    let serialized = serde_json::to_value(&migration).unwrap();
+    assert_eq!(json!("rename_property"), serialized["steps"][0]["step"]);
+    assert_eq!(migration, serde_json::from_value(serialized).unwrap());
+
+    // Types without version are version 0
+    let entity_type: EntityType = serde_json::from_value(json!({ "namespace": "logical", "type_name": "and" })).unwrap();
+    assert_eq!(0, entity_type.version);
+    assert!(entity_type.migrations.is_empty());
+    let component: Component = serde_json::from_value(json!({ "namespace": "core", "type_name": "labeled" })).unwrap();
+    assert_eq!(0, component.version);
+    assert!(component.migrations.is_empty());
+}
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+use std::thread;
+
+use uuid::Uuid;
+
+use crate::tests::utils::r_string;
+use crate::Component;
+use crate::ComponentLookup;
+use crate::ComponentTypeId;
+use crate::EntityInstance;
+use crate::EntityType;
+use crate::EntityTypeId;
+use crate::FlowType;
+use crate::FlowTypeId;
+use crate::PropertyType;
+use crate::RegisteredType;
+use crate::RelationInstance;
+use crate::RelationInstanceTypeId;
+use crate::RelationType;
+use crate::RelationTypeId;
+use crate::TypeDefinitionGetter;
+use crate::TypeRegistry;
+use crate::TypeRegistryError;
+
+#[test]
+fn type_registry_register_and_unregister_test() {
+    let registry = TypeRegistry::new();
+    let namespace = r_string();
+    let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let entity_ty = EntityTypeId::new_from_type(&namespace, &r_string());
+    let relation_ty = RelationTypeId::new_from_type(&namespace, &r_string());
+    let flow_ty = FlowTypeId::new_from_type(&namespace, &r_string());
+
+    let component = Component::new_without_extensions(component_ty.clone(), "", vec![PropertyType::string("name")]);
+    let entity_type = EntityType::new(entity_ty.clone(), "", vec![component_ty.clone()], Vec::new(), Vec::new());
+    let relation_type = RelationType::new(component_ty.clone(), relation_ty.clone(), entity_ty.clone(), "", Vec::new(), Vec::new(), Vec::new());
+    let wrapper = EntityInstance::new_without_properties(entity_ty.clone(), Uuid::new_v4());
+    let entity_instance = EntityInstance::new_without_properties(entity_ty.clone(), Uuid::new_v4());
+    let relation_instance =
+        RelationInstance::new_without_properties(wrapper.id, RelationInstanceTypeId::new_unique_id(relation_ty.clone()), entity_instance.id);
+    let flow_type = FlowType::new(flow_ty.clone(), "", wrapper, vec![entity_instance], vec![relation_instance], Vec::new(), Vec::new());
+
+    // Dependencies have to be registered first
+    assert_eq!(
+        Err(TypeRegistryError::MissingDependency {
+            ty: entity_ty.type_definition(),
+            dependency: component_ty.type_definition(),
+        }),
+        registry.register_entity_type(entity_type.clone())
+    );
+    assert!(registry.register_component(component.clone()).is_ok());
+    assert_eq!(
+        Err(TypeRegistryError::AlreadyRegistered(component_ty.type_definition())),
+        registry.register_component(component)
+    );
+    assert!(registry.register_entity_type(entity_type).is_ok());
+    assert_eq!(
+        Err(TypeRegistryError::MissingDependency {
+            ty: flow_ty.type_definition(),
+            dependency: relation_ty.type_definition(),
+        }),
+        registry.register_flow_type(flow_type.clone())
+    );
+    assert!(registry.register_relation_type(relation_type).is_ok());
+    assert!(registry.register_flow_type(flow_type).is_ok());
+
+    assert!(registry.get_component(&component_ty).is_some());
+    assert!(registry.get_entity_type(&entity_ty).is_some());
+    assert!(registry.get_relation_type(&relation_ty).is_some());
+    assert!(registry.get_flow_type(&flow_ty).is_some());
+    assert!(matches!(registry.get_type(&entity_ty.type_definition()), Some(RegisteredType::EntityType(_))));
+    assert!(registry.has_type(&flow_ty.type_definition()));
+    assert_eq!(vec![namespace.clone()], registry.get_namespaces());
+    assert_eq!(1, registry.get_entity_types_by_namespace(&namespace).len());
+    assert_eq!(0, registry.get_entity_types_by_namespace(&r_string()).len());
+    assert_eq!(1, registry.get_entity_types_by_component(&component_ty).len());
+
+    // Types which are in use cannot be unregistered
+    assert_eq!(
+        Err(TypeRegistryError::InUse {
+            ty: relation_ty.type_definition(),
+            used_by: vec![flow_ty.type_definition()],
+        }),
+        registry.unregister_relation_type(&relation_ty).map(|_| ())
+    );
+    let Err(TypeRegistryError::InUse { used_by, .. }) = registry.unregister_component(&component_ty) else {
+        panic!("The component is in use");
+    };
+    assert_eq!(2, used_by.len());
+
+    // Unregister in reverse order
+    assert!(registry.unregister_flow_type(&flow_ty).is_ok());
+    assert!(registry.unregister_relation_type(&relation_ty).is_ok());
+    assert!(registry.unregister_entity_type(&entity_ty).is_ok());
+    assert!(registry.unregister_component(&component_ty).is_ok());
+    assert_eq!(
+        Err(TypeRegistryError::NotRegistered(component_ty.type_definition())),
+        registry.unregister_component(&component_ty).map(|_| ())
+    );
+    assert!(!registry.has_type(&component_ty.type_definition()));
+    assert!(registry.get_namespaces().is_empty());
+}
+
+#[test]
+fn type_registry_relation_types_between_test() {
+    let registry = TypeRegistry::new();
+    let namespace = r_string();
+    let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let entity_1_ty = EntityTypeId::new_from_type(&namespace, &r_string());
+    let entity_2_ty = EntityTypeId::new_from_type(&namespace, &r_string());
+    let relation_1_ty = RelationTypeId::new_from_type(&namespace, &r_string());
+    let relation_2_ty = RelationTypeId::new_from_type(&namespace, &r_string());
+    registry
+        .register_component(Component::new_without_extensions(component_ty.clone(), "", Vec::new()))
+        .unwrap();
+    registry
+        .register_entity_type(EntityType::new(entity_1_ty.clone(), "", vec![component_ty.clone()], Vec::new(), Vec::new()))
+        .unwrap();
+    registry
+        .register_entity_type(EntityType::new(entity_2_ty.clone(), "", Vec::new(), Vec::new(), Vec::new()))
+        .unwrap();
+    // Matches entity 1 via its component
+    registry
+        .register_relation_type(RelationType::new(
+            component_ty.clone(),
+            relation_1_ty.clone(),
+            entity_2_ty.clone(),
+            "",
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        ))
+        .unwrap();
+    registry
+        .register_relation_type(RelationType::new(
+            entity_2_ty.clone(),
+            relation_2_ty.clone(),
+            entity_1_ty.clone(),
+            "",
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        ))
+        .unwrap();
+
+    let relation_types = registry.get_relation_types_between(&entity_1_ty, &entity_2_ty);
+    assert_eq!(1, relation_types.len());
+    assert_eq!(relation_1_ty, relation_types[0].ty);
+    let relation_types = registry.get_relation_types_between(&entity_2_ty, &entity_1_ty);
+    assert_eq!(1, relation_types.len());
+    assert_eq!(relation_2_ty, relation_types[0].ty);
+    assert!(registry.get_relation_types_between(&entity_2_ty, &entity_2_ty).is_empty());
+    assert!(registry
+        .get_relation_types_between(&EntityTypeId::new_from_type(&namespace, &r_string()), &entity_2_ty)
+        .is_empty());
+}
+
+#[test]
+fn type_registry_concurrent_access_test() {
+    let registry = Arc::new(TypeRegistry::new());
+    let namespace = r_string();
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let registry = registry.clone();
+            let namespace = namespace.clone();
+            thread::spawn(move || {
+                let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+                registry
+                    .register_component(Component::new_without_extensions(component_ty.clone(), "", Vec::new()))
+                    .unwrap();
+                let entity_ty = EntityTypeId::new_from_type(&namespace, &r_string());
+                registry
+                    .register_entity_type(EntityType::new(entity_ty, "", vec![component_ty], Vec::new(), Vec::new()))
+                    .unwrap();
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(8, registry.get_components_by_namespace(&namespace).len());
+    assert_eq!(8, registry.get_entity_types().len());
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::tests::utils::r_string;
+use crate::Component;
+use crate::ComponentTypeId;
+use crate::DataType;
+use crate::EntityInstance;
+use crate::EntityType;
+use crate::EntityTypeId;
+use crate::PropertyType;
+use crate::RelationInstance;
+use crate::RelationInstanceTypeId;
+use crate::RelationType;
+use crate::RelationTypeId;
+use crate::SocketType;
+use crate::TypeDefinitionGetter;
+use crate::ValidationViolation;
+
+fn create_entity_type(namespace: &str, type_name: &str, components: Vec<ComponentTypeId>) -> EntityType {
+    let properties = vec![PropertyType::number("x"), PropertyType::output("result", DataType::Number)];
+    EntityType::new(EntityTypeId::new_from_type(namespace, type_name), "", components, properties, Vec::new())
+}
+
+#[test]
+fn entity_instance_validation_test() {
+    let namespace = r_string();
+    let type_name = r_string();
+    let component_ty = ComponentTypeId::new_from_type(&namespace, &r_string());
+    let component = Component::new_without_extensions(component_ty.clone(), "", vec![PropertyType::string("label")]);
+    let components = vec![component];
+    let entity_type = create_entity_type(&namespace, &type_name, vec![component_ty.clone()]);
+
+    let mut properties = HashMap::new();
+    properties.insert("x".to_string(), json!(1.5));
+    properties.insert("result".to_string(), json!(0));
+    properties.insert("label".to_string(), json!(r_string()));
+    let mut entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+    assert!(entity_instance.validate(&entity_type, &components).is_ok());
+
+    // Missing property, unknown property and invalid data type
+    entity_instance.properties.remove("label");
+    entity_instance.properties.insert("unknown".to_string(), json!(true));
+    entity_instance.properties.insert("x".to_string(), json!("not a number"));
+    let violations = entity_instance.validate(&entity_type, &components).unwrap_err();
+    assert_eq!(3, violations.len());
+    assert!(violations.contains(&ValidationViolation::MissingProperty("label".to_string())));
+    assert!(violations.contains(&ValidationViolation::UnknownProperty("unknown".to_string())));
+    assert!(violations.contains(&ValidationViolation::InvalidDataType {
+        property_name: "x".to_string(),
+        expected: DataType::Number,
+        value: json!("not a number"),
+    }));
+
+    // Missing component
+    let violations = entity_instance.validate(&entity_type, &Vec::<Component>::new()).unwrap_err();
+    assert!(violations.contains(&ValidationViolation::MissingComponent(component_ty)));
+}
+
+#[test]
+fn entity_instance_validation_immutable_output_test() {
+    let entity_type = create_entity_type(&r_string(), &r_string(), Vec::new());
+    let mut properties = HashMap::new();
+    properties.insert("x".to_string(), json!(1));
+    properties.insert("result".to_string(), json!(42));
+    let mut entity_instance = EntityInstance::new(entity_type.ty.clone(), Uuid::new_v4(), properties);
+    // The values of immutable outputs may have been computed by behaviours
+    assert!(entity_instance.validate(&entity_type, &Vec::<Component>::new()).is_ok());
+    // Imported instances must not contain values of immutable outputs
+    let violations = entity_instance.validate_import(&entity_type, &Vec::<Component>::new()).unwrap_err();
+    assert_eq!(vec![ValidationViolation::ImmutableOutputWritten("result".to_string())], violations);
+
+    // Default values are compared by their value
+    entity_instance.properties.insert("result".to_string(), json!(0.0));
+    assert!(entity_instance.validate_import(&entity_type, &Vec::<Component>::new()).is_ok());
+    entity_instance.properties.insert("result".to_string(), json!(0));
+    assert!(entity_instance.validate_import(&entity_type, &Vec::<Component>::new()).is_ok());
+}
+
+#[test]
+fn entity_instance_validation_type_mismatch_test() {
+    let entity_type = create_entity_type(&r_string(), &r_string(), Vec::new());
+    let mut properties = HashMap::new();
+    properties.insert("x".to_string(), json!(1));
+    properties.insert("result".to_string(), json!(0));
+    let entity_instance = EntityInstance::new(EntityTypeId::new_from_type(r_string(), r_string()), Uuid::new_v4(), properties);
+    let violations = entity_instance.validate(&entity_type, &Vec::<Component>::new()).unwrap_err();
+    assert_eq!(
+        vec![ValidationViolation::TypeMismatch {
+            expected: entity_type.type_definition(),
+            actual: entity_instance.type_definition(),
+        }],
+        violations
+    );
+}
+
+#[test]
+fn relation_instance_validation_test() {
+    let namespace = r_string();
+    let outbound_ty = EntityTypeId::new_from_type(&namespace, &r_string());
+    let inbound_ty = EntityTypeId::new_from_type(&namespace, &r_string());
+    let ty = RelationTypeId::new_from_type(&namespace, &r_string());
+    let relation_type = RelationType::new(outbound_ty, ty.clone(), inbound_ty, "", Vec::new(), vec![PropertyType::bool("enabled")], Vec::new());
+
+    let mut properties = HashMap::new();
+    properties.insert("enabled".to_string(), json!(true));
+    let mut relation_instance = RelationInstance::new(Uuid::new_v4(), RelationInstanceTypeId::new_with_random_instance_id(ty), Uuid::new_v4(), properties);
+    assert!(relation_instance.validate(&relation_type, &Vec::<Component>::new()).is_ok());
+
+    relation_instance.properties.insert("enabled".to_string(), json!(1));
+    let violations = relation_instance.validate(&relation_type, &Vec::<Component>::new()).unwrap_err();
+    assert_eq!(
+        vec![ValidationViolation::InvalidDataType {
+            property_name: "enabled".to_string(),
+            expected: DataType::Bool,
+            value: json!(1),
+        }],
+        violations
+    );
+
+    // Mutable outputs may be written by imports
+    let relation_type = RelationType::new(
+        relation_type.outbound_type.clone(),
+        relation_type.ty.clone(),
+        relation_type.inbound_type.clone(),
+        "",
+        Vec::new(),
+        vec![PropertyType::new_with_socket("weight", DataType::Float, SocketType::Output)],
+        Vec::new(),
+    );
+    relation_instance.properties.clear();
+    relation_instance.properties.insert("weight".to_string(), json!(0.5));
+    assert!(relation_instance.validate_import(&relation_type, &Vec::<Component>::new()).is_ok());
+}
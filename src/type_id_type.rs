@@ -2,6 +2,8 @@ use serde::Deserialize;
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::ModelError;
+
 /// Separator for the string representation of a type definition.
 pub static TYPE_ID_TYPE_SEPARATOR: &str = "__";
 
@@ -84,7 +86,7 @@ impl ToString for TypeIdType {
 }
 
 impl TryFrom<&str> for TypeIdType {
-    type Error = ();
+    type Error = ModelError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
@@ -94,7 +96,7 @@ impl TryFrom<&str> for TypeIdType {
             TYPE_ID_TYPE_EXTENSION => Ok(TypeIdType::Extension),
             TYPE_ID_TYPE_RELATION_TYPE => Ok(TypeIdType::RelationType),
             TYPE_ID_TYPE_FLOW_TYPE => Ok(TypeIdType::FlowType),
-            _ => Err(()),
+            _ => Err(ModelError::UnknownTypeIdType(s.to_string())),
         }
     }
 }
@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::resolve_effective_properties;
+use crate::ComponentLookup;
+use crate::ComponentTypeId;
+use crate::DataType;
+use crate::PropertyType;
+use crate::TypeDefinition;
+
+/// A migration of the instances of a type to a version of the type.
+///
+/// When a type evolves, its version is increased and a migration declares how the properties of
+/// instances which have been saved against an older version of the type are upgraded. The
+/// migrations of a type are applied in the order of their versions, starting with the first
+/// migration after the version of the instance. Versions without a migration don't change the
+/// properties of the instances.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct TypeMigration {
+    /// The version of the type which the migration upgrades to.
+    pub version: u32,
+
+    /// Textual description of the migration.
+    #[serde(default = "String::new")]
+    pub description: String,
+
+    /// The steps of the migration which are applied in order.
+    #[serde(default)]
+    pub steps: Vec<MigrationStep>,
+}
+
+impl TypeMigration {
+    pub fn new<S: Into<String>>(version: u32, description: S, steps: Vec<MigrationStep>) -> TypeMigration {
+        TypeMigration {
+            version,
+            description: description.into(),
+            steps,
+        }
+    }
+}
+
+/// A step of a migration which changes the properties of an instance.
+///
+/// Instances don't store their components, so adding or removing a component adds or removes the
+/// properties of the component.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum MigrationStep {
+    /// Renames a property. Instances without the property are not changed.
+    RenameProperty { from: String, to: String },
+
+    /// Converts the value of a property into another data type. Instances without the property
+    /// are not changed.
+    ChangeDataType {
+        name: String,
+        data_type: DataType,
+        #[serde(default)]
+        converter: ValueConverter,
+    },
+
+    /// Adds a property with the given value or, if no value is given, with the default value of
+    /// the data type which the property has in the current version of the type. The value of an
+    /// existing property is kept.
+    AddProperty {
+        name: String,
+        #[serde(default)]
+        value: Option<Value>,
+    },
+
+    /// Removes a property.
+    DropProperty { name: String },
+
+    /// Adds the properties of a component with the default values of their data types. The values
+    /// of existing properties are kept.
+    AddComponent { component: ComponentTypeId },
+
+    /// Removes the properties of a component, except the properties which are still declared by
+    /// the current version of the type.
+    RemoveComponent { component: ComponentTypeId },
+
+    /// Applies the migrations of a component after the given version of the component up to the
+    /// current version of the component.
+    ///
+    /// Instances only store the version of their type, so a type declares which version of a
+    /// component its instances have been saved against by migrating the component.
+    MigrateComponent { component: ComponentTypeId, from_version: u32 },
+}
+
+/// Converts the value of a property into another data type.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ValueConverter {
+    /// Converts the value. Fails if the value cannot be converted, see [`convert_value`].
+    #[default]
+    Convert,
+
+    /// Converts the value or uses the default value of the data type if the value cannot be
+    /// converted.
+    ConvertOrDefault,
+
+    /// Replaces the value with the default value of the data type.
+    Default,
+
+    /// Replaces the values which are equal to the `from` value of a mapping with the `to` value.
+    /// Other values are converted.
+    Map(Vec<ValueMapping>),
+}
+
+impl ValueConverter {
+    /// Converts the given value into the given data type. Returns None, if the value cannot be
+    /// converted.
+    pub fn convert(&self, value: &Value, data_type: &DataType) -> Option<Value> {
+        match self {
+            ValueConverter::Convert => convert_value(value, data_type),
+            ValueConverter::ConvertOrDefault => Some(convert_value(value, data_type).unwrap_or_else(|| data_type.default_value())),
+            ValueConverter::Default => Some(data_type.default_value()),
+            ValueConverter::Map(mappings) => match mappings.iter().find(|mapping| &mapping.from == value) {
+                Some(mapping) => Some(mapping.to.clone()).filter(|value| data_type.is_valid_value(value)),
+                None => convert_value(value, data_type),
+            },
+        }
+    }
+}
+
+/// Maps a value to another value.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ValueMapping {
+    pub from: Value,
+    pub to: Value,
+}
+
+impl ValueMapping {
+    pub fn new(from: Value, to: Value) -> ValueMapping {
+        ValueMapping { from, to }
+    }
+}
+
+/// Converts the given value into the given data type. Returns None, if the value cannot be
+/// converted.
+///
+/// Values which are valid values of the data type are kept. Otherwise:
+///
+/// * Numbers are converted into booleans (`0` is `false`) and booleans into numbers (`false` is `0`).
+/// * Floats without fractional part are converted into integers.
+/// * Strings are parsed as booleans, integers or floats.
+/// * Booleans and numbers are converted into strings and enum values. Arrays and objects are
+///   converted into strings containing their JSON representation.
+/// * Other values are wrapped into an array.
+///
+/// `null` cannot be converted into other data types than arrays.
+pub fn convert_value(value: &Value, data_type: &DataType) -> Option<Value> {
+    if data_type.is_valid_value(value) {
+        return Some(value.clone());
+    }
+    match (data_type, value) {
+        (DataType::Bool, Value::Number(number)) => number.as_f64().map(|number| Value::Bool(number != 0.0)),
+        (DataType::Bool, Value::String(string)) => string.trim().parse::<bool>().ok().map(Value::Bool),
+        (DataType::Integer | DataType::Number, Value::Bool(bool)) => Some(Value::from(*bool as i64)),
+        (DataType::Float, Value::Bool(bool)) => Some(Value::from(*bool as i64 as f64)),
+        (DataType::Integer, Value::Number(number)) => number
+            .as_f64()
+            .filter(|number| number.fract() == 0.0 && *number >= i64::MIN as f64 && *number <= i64::MAX as f64)
+            .map(|number| Value::from(number as i64)),
+        (DataType::Integer, Value::String(string)) => parse_integer(string.trim()),
+        (DataType::Number, Value::String(string)) => parse_integer(string.trim()).or_else(|| parse_float(string.trim())),
+        (DataType::Float, Value::String(string)) => parse_float(string.trim()),
+        (DataType::String, Value::Bool(_) | Value::Number(_) | Value::Array(_) | Value::Object(_)) => Some(Value::String(value.to_string())),
+        (DataType::Enum(_), Value::Bool(_) | Value::Number(_)) => Some(Value::String(value.to_string())).filter(|value| data_type.is_valid_value(value)),
+        (DataType::Array, value) => Some(Value::Array(vec![value.clone()])),
+        _ => None,
+    }
+}
+
+fn parse_integer(string: &str) -> Option<Value> {
+    string.parse::<i64>().map(Value::from).or_else(|_| string.parse::<u64>().map(Value::from)).ok()
+}
+
+fn parse_float(string: &str) -> Option<Value> {
+    string.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number)
+}
+
+/// Errors which can occur while migrating instances.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MigrationError {
+    /// The instance is not an instance of the type it has been migrated with.
+    TypeMismatch { expected: TypeDefinition, actual: TypeDefinition },
+
+    /// The instance has been saved against a newer version of the type.
+    NewerVersion { instance_version: u32, type_version: u32 },
+
+    /// The type of the instance is unknown.
+    UnknownType(TypeDefinition),
+
+    /// A component which is added or removed by a migration cannot be found.
+    MissingComponent { version: u32, component: ComponentTypeId },
+
+    /// A property cannot be renamed because the instance already contains a property with the new name.
+    PropertyExists { version: u32, property_name: String },
+
+    /// A property without value cannot be added because the type doesn't declare the property.
+    UndeclaredProperty { version: u32, property_name: String },
+
+    /// The value of a property cannot be converted into the new data type.
+    ConversionFailed {
+        version: u32,
+        property_name: String,
+        value: Value,
+        data_type: DataType,
+    },
+
+    /// The migrations of a component which is migrated by the migration to the given version fail.
+    Component {
+        version: u32,
+        component: ComponentTypeId,
+        error: Box<MigrationError>,
+    },
+
+    /// An instance of a flow cannot be migrated. Entity instances are identified by their id and
+    /// relation instances by their outbound id, type and inbound id.
+    Instance { instance: String, error: Box<MigrationError> },
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::TypeMismatch { expected, actual } => {
+                write!(f, "The instance is of type {} but has been migrated with type {}", actual.to_string(), expected.to_string())
+            }
+            MigrationError::NewerVersion {
+                instance_version,
+                type_version,
+            } => {
+                write!(
+                    f,
+                    "The instance has version {} which is newer than the version {} of the type",
+                    instance_version, type_version
+                )
+            }
+            MigrationError::UnknownType(ty) => write!(f, "The type {} is unknown", ty.to_string()),
+            MigrationError::MissingComponent { version, component } => {
+                write!(f, "The component {} of the migration to version {} cannot be found", component, version)
+            }
+            MigrationError::PropertyExists { version, property_name } => {
+                write!(
+                    f,
+                    "The migration to version {} cannot rename a property to {} because it already exists",
+                    version, property_name
+                )
+            }
+            MigrationError::UndeclaredProperty { version, property_name } => {
+                write!(f, "The migration to version {} cannot add the undeclared property {} without value", version, property_name)
+            }
+            MigrationError::ConversionFailed {
+                version,
+                property_name,
+                value,
+                data_type,
+            } => write!(
+                f,
+                "The migration to version {} cannot convert the value {} of the property {} into {:?}",
+                version, value, property_name, data_type
+            ),
+            MigrationError::Component { version, component, error } => {
+                write!(f, "The migration to version {} cannot migrate the component {}: {}", version, component, error)
+            }
+            MigrationError::Instance { instance, error } => write!(f, "The instance {} cannot be migrated: {}", instance, error),
+        }
+    }
+}
+
+impl Error for MigrationError {}
+
+/// Migrates the properties of an instance of the given version to the version of the type.
+///
+/// The type is defined by its version, its migrations, its own properties and its components. The
+/// components are resolved using the given component lookup. The properties are only changed if
+/// all migrations succeed.
+pub(crate) fn migrate_properties<L: ComponentLookup + ?Sized>(
+    type_version: u32,
+    migrations: &[TypeMigration],
+    own_properties: &[PropertyType],
+    components: &[ComponentTypeId],
+    component_lookup: &L,
+    instance_version: u32,
+    properties: &mut HashMap<String, Value>,
+) -> Result<(), MigrationError> {
+    if instance_version > type_version {
+        return Err(MigrationError::NewerVersion {
+            instance_version,
+            type_version,
+        });
+    }
+    if !migrations
+        .iter()
+        .any(|migration| migration.version > instance_version && migration.version <= type_version)
+    {
+        return Ok(());
+    }
+    let effective_properties = resolve_effective_properties(own_properties, components, component_lookup);
+    let declared_properties: Vec<&PropertyType> = effective_properties.properties.iter().map(|property| &property.property_type).collect();
+    let mut migrated = properties.clone();
+    apply_migrations(migrations, instance_version, type_version, &declared_properties, component_lookup, &mut migrated)?;
+    *properties = migrated;
+    Ok(())
+}
+
+/// Applies the migrations after the instance version up to the type version to the given
+/// properties. Properties without value are added with the default value of their declared data
+/// type.
+fn apply_migrations<L: ComponentLookup + ?Sized>(
+    migrations: &[TypeMigration],
+    instance_version: u32,
+    type_version: u32,
+    declared_properties: &[&PropertyType],
+    component_lookup: &L,
+    migrated: &mut HashMap<String, Value>,
+) -> Result<(), MigrationError> {
+    let mut migrations: Vec<&TypeMigration> = migrations
+        .iter()
+        .filter(|migration| migration.version > instance_version && migration.version <= type_version)
+        .collect();
+    migrations.sort_by_key(|migration| migration.version);
+    let declared_property = |name: &str| declared_properties.iter().find(|property_type| property_type.name == name);
+    for migration in migrations {
+        let version = migration.version;
+        for step in migration.steps.iter() {
+            match step {
+                MigrationStep::RenameProperty { from, to } => {
+                    if let Some(value) = migrated.remove(from) {
+                        if migrated.contains_key(to) {
+                            return Err(MigrationError::PropertyExists {
+                                version,
+                                property_name: to.clone(),
+                            });
+                        }
+                        migrated.insert(to.clone(), value);
+                    }
+                }
+                MigrationStep::ChangeDataType { name, data_type, converter } => {
+                    if let Some(value) = migrated.get_mut(name) {
+                        *value = converter.convert(value, data_type).ok_or_else(|| MigrationError::ConversionFailed {
+                            version,
+                            property_name: name.clone(),
+                            value: value.clone(),
+                            data_type: data_type.clone(),
+                        })?;
+                    }
+                }
+                MigrationStep::AddProperty { name, value } => {
+                    if !migrated.contains_key(name) {
+                        let value = match value {
+                            Some(value) => value.clone(),
+                            None => declared_property(name)
+                                .map(|property_type| property_type.data_type.default_value())
+                                .ok_or_else(|| MigrationError::UndeclaredProperty {
+                                    version,
+                                    property_name: name.clone(),
+                                })?,
+                        };
+                        migrated.insert(name.clone(), value);
+                    }
+                }
+                MigrationStep::DropProperty { name } => {
+                    migrated.remove(name);
+                }
+                MigrationStep::AddComponent { component } => {
+                    let component = component_lookup.get_component(component).ok_or_else(|| MigrationError::MissingComponent {
+                        version,
+                        component: component.clone(),
+                    })?;
+                    for property_type in component.properties.iter() {
+                        migrated
+                            .entry(property_type.name.clone())
+                            .or_insert_with(|| property_type.data_type.default_value());
+                    }
+                }
+                MigrationStep::MigrateComponent { component, from_version } => {
+                    let component_type = component_lookup.get_component(component).ok_or_else(|| MigrationError::MissingComponent {
+                        version,
+                        component: component.clone(),
+                    })?;
+                    let component_error = |error| MigrationError::Component {
+                        version,
+                        component: component.clone(),
+                        error: Box::new(error),
+                    };
+                    if *from_version > component_type.version {
+                        return Err(component_error(MigrationError::NewerVersion {
+                            instance_version: *from_version,
+                            type_version: component_type.version,
+                        }));
+                    }
+                    let component_properties: Vec<&PropertyType> = component_type.properties.iter().collect();
+                    apply_migrations(
+                        &component_type.migrations,
+                        *from_version,
+                        component_type.version,
+                        &component_properties,
+                        component_lookup,
+                        migrated,
+                    )
+                    .map_err(component_error)?;
+                }
+                MigrationStep::RemoveComponent { component } => {
+                    let component = component_lookup.get_component(component).ok_or_else(|| MigrationError::MissingComponent {
+                        version,
+                        component: component.clone(),
+                    })?;
+                    for property_type in component.properties.iter() {
+                        if declared_property(&property_type.name).is_none() {
+                            migrated.remove(&property_type.name);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -15,6 +15,7 @@ pub trait NamespacedTypeGetter {
 
 /// Defines the namespace and the name of a type.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NamespacedType {
     /// The namespace the component belongs to.
     #[serde(default = "String::new")]
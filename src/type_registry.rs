@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::RwLock;
+
+use crate::Component;
+use crate::ComponentLookup;
+use crate::ComponentOrEntityTypeId;
+use crate::ComponentTypeId;
+use crate::EntityType;
+use crate::EntityTypeId;
+use crate::FlowType;
+use crate::FlowTypeId;
+use crate::NamespacedTypeGetter;
+use crate::RelationType;
+use crate::RelationTypeId;
+use crate::TypeDefinition;
+use crate::TypeDefinitionGetter;
+use crate::TypeIdType;
+
+/// Errors which can occur while registering or unregistering types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeRegistryError {
+    /// A type with the same type definition is already registered.
+    AlreadyRegistered(TypeDefinition),
+
+    /// The type is not registered.
+    NotRegistered(TypeDefinition),
+
+    /// The type depends on a type which is not registered.
+    MissingDependency { ty: TypeDefinition, dependency: TypeDefinition },
+
+    /// The type cannot be unregistered because other types depend on it.
+    InUse { ty: TypeDefinition, used_by: Vec<TypeDefinition> },
+}
+
+impl Display for TypeRegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeRegistryError::AlreadyRegistered(ty) => write!(f, "The type {} is already registered", ty.to_string()),
+            TypeRegistryError::NotRegistered(ty) => write!(f, "The type {} is not registered", ty.to_string()),
+            TypeRegistryError::MissingDependency { ty, dependency } => {
+                write!(f, "The type {} depends on the unregistered type {}", ty.to_string(), dependency.to_string())
+            }
+            TypeRegistryError::InUse { ty, used_by } => {
+                let used_by: Vec<String> = used_by.iter().map(|ty| ty.to_string()).collect();
+                write!(f, "The type {} is used by {}", ty.to_string(), used_by.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for TypeRegistryError {}
+
+/// A type which is registered in the type registry.
+#[derive(Clone, Debug)]
+pub enum RegisteredType {
+    Component(Component),
+    EntityType(EntityType),
+    RelationType(RelationType),
+    FlowType(FlowType),
+}
+
+/// Thread-safe in-memory registry of components, entity types, relation types and flow types.
+///
+/// The registry ensures the referential integrity of the registered types: a type can only be
+/// registered if all types it depends on are registered and a type can only be unregistered if
+/// no other type depends on it.
+#[derive(Default)]
+pub struct TypeRegistry {
+    components: RwLock<HashMap<ComponentTypeId, Component>>,
+    entity_types: RwLock<HashMap<EntityTypeId, EntityType>>,
+    relation_types: RwLock<HashMap<RelationTypeId, RelationType>>,
+    flow_types: RwLock<HashMap<FlowTypeId, FlowType>>,
+}
+
+// The locks are always acquired in the order of the fields to prevent deadlocks.
+impl TypeRegistry {
+    pub fn new() -> TypeRegistry {
+        TypeRegistry::default()
+    }
+
+    /// Registers the given component.
+    pub fn register_component(&self, component: Component) -> Result<(), TypeRegistryError> {
+        let mut components = self.components.write().unwrap();
+        if components.contains_key(&component.ty) {
+            return Err(TypeRegistryError::AlreadyRegistered(component.type_definition()));
+        }
+        components.insert(component.ty.clone(), component);
+        Ok(())
+    }
+
+    /// Registers the given entity type. The components of the entity type have to be registered.
+    pub fn register_entity_type(&self, entity_type: EntityType) -> Result<(), TypeRegistryError> {
+        let components = self.components.read().unwrap();
+        let mut entity_types = self.entity_types.write().unwrap();
+        if entity_types.contains_key(&entity_type.ty) {
+            return Err(TypeRegistryError::AlreadyRegistered(entity_type.type_definition()));
+        }
+        for component_ty in entity_type.components.iter() {
+            if !components.contains_key(component_ty) {
+                return Err(TypeRegistryError::MissingDependency {
+                    ty: entity_type.type_definition(),
+                    dependency: component_ty.type_definition(),
+                });
+            }
+        }
+        entity_types.insert(entity_type.ty.clone(), entity_type);
+        Ok(())
+    }
+
+    /// Registers the given relation type. The components of the relation type and the outbound
+    /// and inbound types have to be registered.
+    pub fn register_relation_type(&self, relation_type: RelationType) -> Result<(), TypeRegistryError> {
+        let components = self.components.read().unwrap();
+        let entity_types = self.entity_types.read().unwrap();
+        let mut relation_types = self.relation_types.write().unwrap();
+        if relation_types.contains_key(&relation_type.ty) {
+            return Err(TypeRegistryError::AlreadyRegistered(relation_type.type_definition()));
+        }
+        let missing_dependency = |dependency: TypeDefinition| TypeRegistryError::MissingDependency {
+            ty: relation_type.type_definition(),
+            dependency,
+        };
+        for component_ty in relation_type.components.iter() {
+            if !components.contains_key(component_ty) {
+                return Err(missing_dependency(component_ty.type_definition()));
+            }
+        }
+        for ty in [&relation_type.outbound_type, &relation_type.inbound_type] {
+            let exists = match ty {
+                ComponentOrEntityTypeId::Component(ty) => components.contains_key(ty),
+                ComponentOrEntityTypeId::EntityType(ty) => entity_types.contains_key(ty),
+            };
+            if !exists {
+                return Err(missing_dependency(ty.type_definition()));
+            }
+        }
+        relation_types.insert(relation_type.ty.clone(), relation_type);
+        Ok(())
+    }
+
+    /// Registers the given flow type. The entity types of the entity instances and the relation
+    /// types of the relation instances have to be registered.
+    pub fn register_flow_type(&self, flow_type: FlowType) -> Result<(), TypeRegistryError> {
+        let entity_types = self.entity_types.read().unwrap();
+        let relation_types = self.relation_types.read().unwrap();
+        let mut flow_types = self.flow_types.write().unwrap();
+        if flow_types.contains_key(&flow_type.ty) {
+            return Err(TypeRegistryError::AlreadyRegistered(flow_type.type_definition()));
+        }
+        for entity_ty in flow_type.uses_entity_types() {
+            if !entity_types.contains_key(&entity_ty) {
+                return Err(TypeRegistryError::MissingDependency {
+                    ty: flow_type.type_definition(),
+                    dependency: entity_ty.type_definition(),
+                });
+            }
+        }
+        for relation_ty in flow_type.uses_relation_types() {
+            if !relation_types.contains_key(&relation_ty) {
+                return Err(TypeRegistryError::MissingDependency {
+                    ty: flow_type.type_definition(),
+                    dependency: relation_ty.type_definition(),
+                });
+            }
+        }
+        flow_types.insert(flow_type.ty.clone(), flow_type);
+        Ok(())
+    }
+
+    /// Unregisters the component with the given type. Fails if an entity type or a relation type
+    /// uses the component.
+    pub fn unregister_component(&self, ty: &ComponentTypeId) -> Result<Component, TypeRegistryError> {
+        let mut components = self.components.write().unwrap();
+        if !components.contains_key(ty) {
+            return Err(TypeRegistryError::NotRegistered(ty.type_definition()));
+        }
+        let mut used_by: Vec<TypeDefinition> = self
+            .entity_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|entity_type| entity_type.components.contains(ty))
+            .map(|entity_type| entity_type.type_definition())
+            .collect();
+        used_by.extend(
+            self.relation_types
+                .read()
+                .unwrap()
+                .values()
+                .filter(|relation_type| {
+                    relation_type.components.contains(ty) || relation_type.outbound_type.eq_component(ty) || relation_type.inbound_type.eq_component(ty)
+                })
+                .map(|relation_type| relation_type.type_definition()),
+        );
+        if !used_by.is_empty() {
+            return Err(TypeRegistryError::InUse {
+                ty: ty.type_definition(),
+                used_by,
+            });
+        }
+        Ok(components.remove(ty).unwrap())
+    }
+
+    /// Unregisters the entity type with the given type. Fails if a relation type or a flow type
+    /// uses the entity type.
+    pub fn unregister_entity_type(&self, ty: &EntityTypeId) -> Result<EntityType, TypeRegistryError> {
+        let mut entity_types = self.entity_types.write().unwrap();
+        if !entity_types.contains_key(ty) {
+            return Err(TypeRegistryError::NotRegistered(ty.type_definition()));
+        }
+        let mut used_by: Vec<TypeDefinition> = self
+            .relation_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|relation_type| relation_type.outbound_type.eq_entity_type(ty) || relation_type.inbound_type.eq_entity_type(ty))
+            .map(|relation_type| relation_type.type_definition())
+            .collect();
+        used_by.extend(
+            self.flow_types
+                .read()
+                .unwrap()
+                .values()
+                .filter(|flow_type| flow_type.uses_entity_types().contains(ty))
+                .map(|flow_type| flow_type.type_definition()),
+        );
+        if !used_by.is_empty() {
+            return Err(TypeRegistryError::InUse {
+                ty: ty.type_definition(),
+                used_by,
+            });
+        }
+        Ok(entity_types.remove(ty).unwrap())
+    }
+
+    /// Unregisters the relation type with the given type. Fails if a flow type uses the relation
+    /// type.
+    pub fn unregister_relation_type(&self, ty: &RelationTypeId) -> Result<RelationType, TypeRegistryError> {
+        let mut relation_types = self.relation_types.write().unwrap();
+        if !relation_types.contains_key(ty) {
+            return Err(TypeRegistryError::NotRegistered(ty.type_definition()));
+        }
+        let used_by: Vec<TypeDefinition> = self
+            .flow_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|flow_type| flow_type.uses_relation_types().contains(ty))
+            .map(|flow_type| flow_type.type_definition())
+            .collect();
+        if !used_by.is_empty() {
+            return Err(TypeRegistryError::InUse {
+                ty: ty.type_definition(),
+                used_by,
+            });
+        }
+        Ok(relation_types.remove(ty).unwrap())
+    }
+
+    /// Unregisters the flow type with the given type.
+    pub fn unregister_flow_type(&self, ty: &FlowTypeId) -> Result<FlowType, TypeRegistryError> {
+        self.flow_types
+            .write()
+            .unwrap()
+            .remove(ty)
+            .ok_or_else(|| TypeRegistryError::NotRegistered(ty.type_definition()))
+    }
+
+    pub fn get_entity_type(&self, ty: &EntityTypeId) -> Option<EntityType> {
+        self.entity_types.read().unwrap().get(ty).cloned()
+    }
+
+    pub fn get_relation_type(&self, ty: &RelationTypeId) -> Option<RelationType> {
+        self.relation_types.read().unwrap().get(ty).cloned()
+    }
+
+    pub fn get_flow_type(&self, ty: &FlowTypeId) -> Option<FlowType> {
+        self.flow_types.read().unwrap().get(ty).cloned()
+    }
+
+    /// Returns the type with the given type definition.
+    pub fn get_type(&self, type_definition: &TypeDefinition) -> Option<RegisteredType> {
+        match type_definition.type_id_type {
+            TypeIdType::Component => self
+                .get_component(&ComponentTypeId::try_from(type_definition).ok()?)
+                .map(RegisteredType::Component),
+            TypeIdType::EntityType => self
+                .get_entity_type(&EntityTypeId::try_from(type_definition).ok()?)
+                .map(RegisteredType::EntityType),
+            TypeIdType::RelationType => self
+                .get_relation_type(&RelationTypeId::try_from(type_definition).ok()?)
+                .map(RegisteredType::RelationType),
+            TypeIdType::FlowType => self.get_flow_type(&FlowTypeId::try_from(type_definition).ok()?).map(RegisteredType::FlowType),
+            _ => None,
+        }
+    }
+
+    /// Returns true, if a type with the given type definition is registered.
+    pub fn has_type(&self, type_definition: &TypeDefinition) -> bool {
+        self.get_type(type_definition).is_some()
+    }
+
+    /// Returns the namespaces of all registered types in alphabetical order.
+    pub fn get_namespaces(&self) -> Vec<String> {
+        let mut namespaces: Vec<String> = self.components.read().unwrap().keys().map(|ty| ty.namespace()).collect();
+        namespaces.extend(self.entity_types.read().unwrap().keys().map(|ty| ty.namespace()));
+        namespaces.extend(self.relation_types.read().unwrap().keys().map(|ty| ty.namespace()));
+        namespaces.extend(self.flow_types.read().unwrap().keys().map(|ty| ty.namespace()));
+        namespaces.sort();
+        namespaces.dedup();
+        namespaces
+    }
+
+    pub fn get_components(&self) -> Vec<Component> {
+        self.components.read().unwrap().values().cloned().collect()
+    }
+
+    pub fn get_entity_types(&self) -> Vec<EntityType> {
+        self.entity_types.read().unwrap().values().cloned().collect()
+    }
+
+    pub fn get_relation_types(&self) -> Vec<RelationType> {
+        self.relation_types.read().unwrap().values().cloned().collect()
+    }
+
+    pub fn get_flow_types(&self) -> Vec<FlowType> {
+        self.flow_types.read().unwrap().values().cloned().collect()
+    }
+
+    pub fn get_components_by_namespace(&self, namespace: &str) -> Vec<Component> {
+        self.components
+            .read()
+            .unwrap()
+            .values()
+            .filter(|c| c.namespace() == namespace)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_entity_types_by_namespace(&self, namespace: &str) -> Vec<EntityType> {
+        self.entity_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|e| e.namespace() == namespace)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_relation_types_by_namespace(&self, namespace: &str) -> Vec<RelationType> {
+        self.relation_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|r| r.namespace() == namespace)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_flow_types_by_namespace(&self, namespace: &str) -> Vec<FlowType> {
+        self.flow_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|f| f.namespace() == namespace)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the entity types which are composed with the given component.
+    pub fn get_entity_types_by_component(&self, ty: &ComponentTypeId) -> Vec<EntityType> {
+        self.entity_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|e| e.components.contains(ty))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the relation types which are composed with the given component.
+    pub fn get_relation_types_by_component(&self, ty: &ComponentTypeId) -> Vec<RelationType> {
+        self.relation_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|r| r.components.contains(ty))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the relation types which may connect an entity instance of the given outbound
+    /// entity type with an entity instance of the given inbound entity type.
+    ///
+    /// The outbound type and the inbound type of a relation type is either an entity type or a
+    /// component which is contained in the entity type.
+    pub fn get_relation_types_between(&self, outbound_ty: &EntityTypeId, inbound_ty: &EntityTypeId) -> Vec<RelationType> {
+        let entity_types = self.entity_types.read().unwrap();
+        let (Some(outbound_type), Some(inbound_type)) = (entity_types.get(outbound_ty), entity_types.get(inbound_ty)) else {
+            return Vec::new();
+        };
+        let matches = |ty: &ComponentOrEntityTypeId, entity_type: &EntityType| match ty {
+            ComponentOrEntityTypeId::Component(ty) => entity_type.components.contains(ty),
+            ComponentOrEntityTypeId::EntityType(ty) => &entity_type.ty == ty,
+        };
+        self.relation_types
+            .read()
+            .unwrap()
+            .values()
+            .filter(|r| matches(&r.outbound_type, outbound_type) && matches(&r.inbound_type, inbound_type))
+            .cloned()
+            .collect()
+    }
+}
+
+impl ComponentLookup for TypeRegistry {
+    fn get_component(&self, ty: &ComponentTypeId) -> Option<Component> {
+        self.components.read().unwrap().get(ty).cloned()
+    }
+}
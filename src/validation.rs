@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use serde_json::Value;
+
+use crate::resolve_effective_properties;
+use crate::ComponentLookup;
+use crate::ComponentTypeId;
+use crate::DataType;
+use crate::Mutability;
+use crate::PropertyConflict;
+use crate::PropertyType;
+use crate::SocketType;
+use crate::TypeDefinition;
+
+/// A violation of the schema of a type by an instance of the type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationViolation {
+    /// The instance is not an instance of the type it has been validated against.
+    TypeMismatch { expected: TypeDefinition, actual: TypeDefinition },
+
+    /// A component of the type cannot be found.
+    MissingComponent(ComponentTypeId),
+
+    /// Multiple components of the type declare the same property differently.
+    PropertyConflict(PropertyConflict),
+
+    /// A property which is declared by the type or one of its components is missing.
+    MissingProperty(String),
+
+    /// The instance contains a property which is neither declared by the type nor by one of its components.
+    UnknownProperty(String),
+
+    /// The value of a property doesn't match the data type of the property.
+    InvalidDataType { property_name: String, expected: DataType, value: Value },
+
+    /// An immutable output property contains another value than the default value of its data type.
+    ///
+    /// Immutable outputs are computed by behaviours. Only instances which haven't been processed
+    /// by behaviours, like imported instances, are checked for this violation.
+    ImmutableOutputWritten(String),
+}
+
+impl Display for ValidationViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationViolation::TypeMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "The instance is of type {} but has been validated against type {}",
+                    actual.to_string(),
+                    expected.to_string()
+                )
+            }
+            ValidationViolation::MissingComponent(ty) => write!(f, "The component {} cannot be found", ty),
+            ValidationViolation::PropertyConflict(conflict) => write!(f, "{}", conflict),
+            ValidationViolation::MissingProperty(property_name) => write!(f, "The property {} is missing", property_name),
+            ValidationViolation::UnknownProperty(property_name) => write!(f, "The property {} is not declared by the type or its components", property_name),
+            ValidationViolation::InvalidDataType {
+                property_name,
+                expected,
+                value,
+            } => write!(f, "The value {} of property {} is not of data type {}", value, property_name, expected),
+            ValidationViolation::ImmutableOutputWritten(property_name) => write!(f, "The immutable output property {} has been written", property_name),
+        }
+    }
+}
+
+/// Validates the given property values against the effective properties of a type (the own
+/// properties of a type and the properties of its components). If requested, immutable outputs
+/// have to contain the default value of their data type.
+pub(crate) fn validate_properties<L: ComponentLookup + ?Sized>(
+    own_properties: &[PropertyType],
+    components: &[ComponentTypeId],
+    component_lookup: &L,
+    properties: &HashMap<String, Value>,
+    check_immutable_outputs: bool,
+) -> Vec<ValidationViolation> {
+    let effective_properties = resolve_effective_properties(own_properties, components, component_lookup);
+    let property_types = effective_properties.property_types();
+    let mut violations: Vec<ValidationViolation> = effective_properties
+        .missing_components
+        .into_iter()
+        .map(ValidationViolation::MissingComponent)
+        .collect();
+    violations.extend(effective_properties.conflicts.into_iter().map(ValidationViolation::PropertyConflict));
+    for property_type in property_types.iter() {
+        match properties.get(&property_type.name) {
+            Some(value) => {
+                if !property_type.data_type.is_valid_value(value) {
+                    violations.push(ValidationViolation::InvalidDataType {
+                        property_name: property_type.name.clone(),
+                        expected: property_type.data_type.clone(),
+                        value: value.clone(),
+                    });
+                } else if check_immutable_outputs
+                    && property_type.socket_type == SocketType::Output
+                    && property_type.mutability == Mutability::Immutable
+                    && !is_default_value(value, &property_type.data_type)
+                {
+                    violations.push(ValidationViolation::ImmutableOutputWritten(property_type.name.clone()));
+                }
+            }
+            None => violations.push(ValidationViolation::MissingProperty(property_type.name.clone())),
+        }
+    }
+    let mut unknown_properties: Vec<&String> = properties
+        .keys()
+        .filter(|property_name| !property_types.iter().any(|p| &p.name == *property_name))
+        .collect();
+    unknown_properties.sort();
+    for property_name in unknown_properties {
+        violations.push(ValidationViolation::UnknownProperty(property_name.clone()));
+    }
+    violations
+}
+
+/// Returns true, if the value is the default value of the data type. Numbers are compared by
+/// their value, so that `0` and `0.0` are equal.
+fn is_default_value(value: &Value, data_type: &DataType) -> bool {
+    match (value, data_type.default_value()) {
+        (Value::Number(number), Value::Number(default_number)) => number.as_f64() == default_number.as_f64(),
+        (value, default_value) => value == &default_value,
+    }
+}